@@ -0,0 +1,275 @@
+// Claude Code keeps a JSONL transcript per conversation under
+// ~/.claude/projects/<encoded cwd>/. Reading it gives the agent's todo list,
+// last tool call, turn count and token usage directly, without scraping the terminal.
+
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone)]
+pub struct Todo {
+	pub content: String,
+	pub status: String, // pending, in_progress, completed
+}
+
+/// State gathered from a transcript, updated incrementally as the file grows
+#[derive(Debug, Clone, Default)]
+pub struct ClaudeState {
+	pub path: PathBuf,
+	pub todos: Vec<Todo>,
+	pub last_tool: Option<String>,
+	/// Text of the agent's latest reply, e.g. its closing summary
+	pub last_message: Option<String>,
+	/// The latest prompt you gave it (slash commands as "/name args") and when, unix seconds
+	pub last_prompt: Option<(String, Option<i64>)>,
+	pub turns: usize,
+	pub model: Option<String>,
+	pub usage: Usage,
+	offset: u64,
+	last_message_id: Option<String>,
+}
+
+/// Token counts summed over the conversation's assistant messages
+#[derive(Debug, Clone, Copy, Default, serde::Serialize)]
+pub struct Usage {
+	pub input_tokens: u64,
+	pub output_tokens: u64,
+	pub cache_write_tokens: u64,
+	pub cache_read_tokens: u64,
+}
+
+/// USD per million input/output tokens by model family (list prices; first match wins)
+const PRICES: &[(&str, f64, f64)] = &[
+	("opus-4-5", 5.0, 25.0),
+	("opus", 15.0, 75.0),
+	("haiku-4-5", 1.0, 5.0),
+	("haiku", 0.8, 4.0),
+	("sonnet", 3.0, 15.0),
+];
+
+impl ClaudeState {
+	pub fn todos_done(&self) -> usize {
+		self.todos.iter().filter(|t| t.status == "completed").count()
+	}
+
+	/// The item the agent is working on now (falls back to the next pending one)
+	pub fn current_todo(&self) -> Option<&Todo> {
+		self.todos
+			.iter()
+			.find(|t| t.status == "in_progress")
+			.or_else(|| self.todos.iter().find(|t| t.status == "pending"))
+	}
+
+	/// Rough spend so far from token usage and list prices (sonnet rates if unknown).
+	/// Cache writes bill at 1.25x input, cache reads at 0.1x.
+	pub fn cost_estimate(&self) -> f64 {
+		let model = self.model.as_deref().unwrap_or_default();
+		let (input, output) = PRICES
+			.iter()
+			.find(|(family, _, _)| model.contains(family))
+			.map(|(_, input, output)| (*input, *output))
+			.unwrap_or((3.0, 15.0));
+		let u = &self.usage;
+		(u.input_tokens as f64 * input
+			+ u.cache_write_tokens as f64 * input * 1.25
+			+ u.cache_read_tokens as f64 * input * 0.1
+			+ u.output_tokens as f64 * output)
+			/ 1_000_000.0
+	}
+}
+
+/// Claude's project directory for a working directory: every character that
+/// isn't alphanumeric becomes '-' (/Users/me/code/app -> -Users-me-code-app)
+pub fn project_dir(work_dir: &Path) -> Option<PathBuf> {
+	let encoded: String = work_dir
+		.to_string_lossy()
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '-' })
+		.collect();
+	Some(dirs::home_dir()?.join(".claude").join("projects").join(encoded))
+}
+
+/// Most recently written transcript for a working directory
+pub fn latest_transcript(work_dir: &Path) -> Option<PathBuf> {
+	let dir = project_dir(work_dir)?;
+	std::fs::read_dir(dir)
+		.ok()?
+		.filter_map(|entry| entry.ok())
+		.map(|entry| entry.path())
+		.filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+		.map(|path| {
+			let modified = path.metadata().and_then(|m| m.modified()).unwrap_or(SystemTime::UNIX_EPOCH);
+			(modified, path)
+		})
+		.max_by_key(|(modified, _)| *modified)
+		.map(|(_, path)| path)
+}
+
+/// Bring `state` up to date with the newest transcript for `work_dir`.
+/// Starts over when the agent moved to a new conversation. Returns true on change.
+pub fn refresh(state: &mut ClaudeState, work_dir: &Path) -> bool {
+	let Some(path) = latest_transcript(work_dir) else {
+		return false;
+	};
+	if state.path != path {
+		*state = ClaudeState {
+			path,
+			..Default::default()
+		};
+	}
+	let Ok(mut file) = File::open(&state.path) else {
+		return false;
+	};
+	let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+	if len <= state.offset {
+		return false;
+	}
+	if file.seek(SeekFrom::Start(state.offset)).is_err() {
+		return false;
+	}
+	let mut reader = BufReader::new(file);
+	let mut line = String::new();
+	let mut changed = false;
+	loop {
+		line.clear();
+		match reader.read_line(&mut line) {
+			Ok(0) | Err(_) => break,
+			Ok(n) => {
+				// A partial last line is still being written; pick it up next time
+				if !line.ends_with('\n') {
+					break;
+				}
+				state.offset += n as u64;
+				if let Ok(entry) = serde_json::from_str::<Value>(&line) {
+					changed |= apply_entry(state, &entry);
+				}
+			}
+		}
+	}
+	changed
+}
+
+fn apply_entry(state: &mut ClaudeState, entry: &Value) -> bool {
+	let content = &entry["message"]["content"];
+	match entry["type"].as_str() {
+		Some("user") => {
+			if entry["isMeta"].as_bool() == Some(true) {
+				return false;
+			}
+			// Tool results come back as user messages too; only typed prompts count as turns
+			let is_prompt = content.is_string()
+				|| content
+					.as_array()
+					.is_some_and(|items| items.iter().any(|i| i["type"] == "text"));
+			if is_prompt {
+				state.turns += 1;
+				if let Some(text) = prompt_text(content) {
+					let at = entry["timestamp"]
+						.as_str()
+						.and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+						.map(|t| t.timestamp());
+					state.last_prompt = Some((text, at));
+				}
+			}
+			is_prompt
+		}
+		Some("assistant") => {
+			let mut changed = false;
+			// One API message is logged as several entries (one per content block)
+			// repeating the same usage; count it once
+			let message = &entry["message"];
+			let id = message["id"].as_str().map(str::to_string);
+			if id.is_some() && id != state.last_message_id {
+				let usage = &message["usage"];
+				let tokens = |key: &str| usage[key].as_u64().unwrap_or(0);
+				state.usage.input_tokens += tokens("input_tokens");
+				state.usage.output_tokens += tokens("output_tokens");
+				state.usage.cache_write_tokens += tokens("cache_creation_input_tokens");
+				state.usage.cache_read_tokens += tokens("cache_read_input_tokens");
+				state.last_message_id = id;
+				if let Some(model) = message["model"].as_str().filter(|m| !m.starts_with('<')) {
+					state.model = Some(model.to_string());
+				}
+				changed = true;
+			}
+			for item in content.as_array().into_iter().flatten() {
+				if item["type"] == "text" {
+					if let Some(text) = item["text"].as_str().map(str::trim).filter(|t| !t.is_empty()) {
+						state.last_message = Some(text.to_string());
+						changed = true;
+					}
+					continue;
+				}
+				if item["type"] != "tool_use" {
+					continue;
+				}
+				let name = item["name"].as_str().unwrap_or("tool");
+				let input = &item["input"];
+				if name == "TodoWrite" {
+					state.todos = input["todos"]
+						.as_array()
+						.into_iter()
+						.flatten()
+						.map(|t| Todo {
+							content: t["content"].as_str().unwrap_or_default().to_string(),
+							status: t["status"].as_str().unwrap_or("pending").to_string(),
+						})
+						.collect();
+				}
+				state.last_tool = Some(tool_summary(name, input));
+				changed = true;
+			}
+			changed
+		}
+		_ => false,
+	}
+}
+
+/// What was typed in a prompt entry. Slash commands are logged as
+/// <command-name>/x</command-name><command-args>..</command-args> and come back
+/// as "/x .."; other tagged entries (command output and the like) are skipped.
+fn prompt_text(content: &Value) -> Option<String> {
+	let text = match content.as_str() {
+		Some(text) => text.to_string(),
+		None => content
+			.as_array()?
+			.iter()
+			.filter(|i| i["type"] == "text")
+			.filter_map(|i| i["text"].as_str())
+			.collect::<Vec<_>>()
+			.join("\n"),
+	};
+	let text = text.trim();
+	if let Some(name) = tag(text, "command-name") {
+		let args = tag(text, "command-args").unwrap_or_default();
+		return Some(format!("{name} {args}").trim_end().to_string());
+	}
+	(!text.is_empty() && !text.starts_with('<')).then(|| text.to_string())
+}
+
+/// Contents of the first <name>...</name> in `text`
+fn tag<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+	let start = text.find(&format!("<{name}>"))? + name.len() + 2;
+	let end = start + text[start..].find(&format!("</{name}>"))?;
+	Some(text[start..end].trim())
+}
+
+/// "Bash: cargo test", "Edit: src/main.rs", ...
+pub fn tool_summary(name: &str, input: &Value) -> String {
+	let key = ["command", "file_path", "notebook_path", "pattern", "url", "description", "query"]
+		.iter()
+		.find_map(|k| input[*k].as_str());
+	match key {
+		Some(arg) => {
+			let arg = arg.lines().next().unwrap_or_default();
+			if arg.chars().count() > 60 {
+				format!("{}: {}…", name, arg.chars().take(60).collect::<String>())
+			} else {
+				format!("{}: {}", name, arg)
+			}
+		}
+		None => name.to_string(),
+	}
+}
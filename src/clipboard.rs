@@ -0,0 +1,43 @@
+// System clipboard for the copy action. Uses the native clipboard when there is
+// one, else an OSC 52 escape so copying still works over SSH and inside tmux.
+
+use anyhow::Result;
+use std::io::Write;
+use std::sync::Mutex;
+
+// On X11/Wayland the copied text is served by the process that owns the
+// clipboard, so keep it alive for swarm's lifetime instead of per copy
+static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+
+/// Copy `text`; returns how it was copied for the status message
+pub fn copy(text: &str) -> Result<&'static str> {
+	let mut clipboard = CLIPBOARD.lock().unwrap_or_else(|e| e.into_inner());
+	if clipboard.is_none() {
+		*clipboard = arboard::Clipboard::new().ok();
+	}
+	if let Some(cb) = clipboard.as_mut() {
+		if cb.set_text(text).is_ok() {
+			return Ok("clipboard");
+		}
+	}
+	let mut out = std::io::stdout();
+	write!(out, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+	out.flush()?;
+	Ok("terminal clipboard (OSC 52)")
+}
+
+fn base64(input: &[u8]) -> String {
+	const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+	let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+	for chunk in input.chunks(3) {
+		let n = (chunk[0] as u32) << 16 | (*chunk.get(1).unwrap_or(&0) as u32) << 8 | *chunk.get(2).unwrap_or(&0) as u32;
+		for i in 0..4 {
+			if i <= chunk.len() {
+				out.push(TABLE[(n >> (18 - 6 * i) & 63) as usize] as char);
+			} else {
+				out.push('=');
+			}
+		}
+	}
+	out
+}
@@ -10,7 +10,17 @@ use std::path::{Path, PathBuf};
 const DEFAULT_CONFIG: &str = r#"
 [general]
 default_agent = "claude"
-poll_interval_ms = 1000
+poll_interval_ms = 1000   # session status refresh
+preview_poll_ms = 1000    # preview capture for the selected agent
+tasks_poll_ms = 5000      # re-read tasks_dir
+blur_poll_ms = 10000      # slower status refresh while the terminal is unfocused
+conflict_check_ms = 30000 # warn when agents edit the same files in a repo (0 = off)
+trash_days = 7            # keep orphaned logs/session data in ~/.swarm/trash this long
+max_agents = 0            # cap on running agents for `swarm run-all` (0 = no limit)
+claim_tasks = false       # write claimed_by: into tasks you start (shared/synced tasks_dir)
+claim_name = ""           # who you are in claims ("" = user@hostname)
+auto_interview = true     # start claude on one-line tasks with /interview
+auto_name = false         # name prompt-only `swarm new` sessions with a quick haiku call (off = first words of the prompt)
 logs_dir = "~/.swarm/logs"
 tasks_dir = "~/.swarm/tasks"
 daily_dir = "~/.swarm/daily"
@@ -20,10 +30,144 @@ enabled = true
 sound_needs_input = "Ping"
 sound_done = "Glass"
 sound_error = "Basso"
+digest_time = "09:00"  # morning digest of overdue/due-today tasks ("" to disable)
+due_reminder = "1h"    # remind this long before tasks with a due time (due: 2025-03-14 17:00; "" to disable)
+terminal_bell = false  # ring the terminal bell when an agent needs input
+tmux_alerts = false    # flag swarm's own tmux window (bell) when an agent needs input
+# Custom notifier instead of osascript/notify-send; gets SWARM_NOTIFY_TITLE,
+# SWARM_NOTIFY_MESSAGE and SWARM_NOTIFY_SOUND, e.g. "terminal-notifier -message \"$SWARM_NOTIFY_MESSAGE\""
+command = ""
+
+# Per task priority (`priority: P0` in the task's frontmatter): other sounds, and
+# flash = true blinks the agent's row while it needs input or errored
+# [notifications.priorities.P0]
+# sound_needs_input = "Sosumi"
+# sound_error = "Funk"
+# flash = true
 
 [keybindings]
 prefix = "ctrl-a"
 
+# Commit, pull and push tasks_dir when it's a git repo (sync tasks between machines)
+[task_sync]
+enabled = false
+remote = "origin"
+interval_secs = 300
+
+# Team mode: publish your fleet status and read teammates' with `swarm fleet`.
+# Locations are https URLs (PUT/GET), s3:// paths (aws CLI) or shared file paths.
+[team]
+name = ""                 # how you appear to teammates ("" = user@hostname)
+publish_to = ""           # e.g. "s3://team-bucket/swarm/alice.json" ("" = don't publish)
+interval_secs = 60
+members = []              # teammates' publish_to locations
+token = ""                # bearer token for http(s) publish_to/members ("" = none)
+
+# Phone notifications via ntfy.sh and/or Pushover ("" disables each sink)
+[push]
+ntfy_server = "https://ntfy.sh"
+ntfy_topic_needs_input = ""   # e.g. "alice-swarm-blocked" (pick something unguessable)
+ntfy_topic_done = ""
+ntfy_topic_error = ""
+pushover_token = ""           # application API token
+pushover_user = ""            # user key
+pushover_events = ["needs_input"]  # needs_input, done, error
+dashboard_url = ""            # `swarm serve` address for tap-to-open links, e.g. "http://mac.tailnet:7777/?token=..."
+
+# Stop runaway sessions: past a limit swarm warns, pauses (interrupts the agent) or kills it.
+# `swarm new --max-minutes/--max-cost/--on-limit` overrides these per session.
+[limits]
+max_minutes = 0           # runtime limit per session (0 = none)
+max_cost = 0.0            # estimated spend limit in USD for Claude agents (0 = none)
+action = "pause"          # warn, pause or kill
+yolo_action = "kill"      # YOLO sessions get a hard stop
+
+# Where agents may be started. Globs: `*` is one directory, `**` any depth;
+# a worktree counts as its main repo. Empty allow = anywhere not denied.
+[repos]
+allow = []                # e.g. ["~/code/**"]
+deny = []                 # e.g. ["~", "~/code/prod-ops"]
+yolo_allow = []           # stricter list for YOLO agents (empty = same as allow)
+
+# Shell commands run in the session's working directory, with SWARM_SESSION,
+# SWARM_NAME, SWARM_AGENT, SWARM_STATUS, SWARM_WORK_DIR and SWARM_TASK set.
+# A repo's .swarm.toml can add its own [hooks] (run after these).
+# Output goes to ~/.swarm/logs/hooks.log.
+[hooks]
+on_create = ""            # before the agent starts; failing stops the launch, e.g. "cp ../main/.env . && pnpm i"
+on_done = ""              # e.g. "gh pr create --fill"
+on_kill = ""              # e.g. "./scripts/cleanup.sh"
+
+# base_branch: what new worktrees branch from - a branch name, "current" (the
+# checkout's current branch) or "" for origin's default branch (main, master, develop...).
+# on_conflict: when the worktree path or branch already exists - ask (prompt in the
+# terminal; the TUI adds a suffix), reuse the existing worktree, suffix (name-2),
+# checkout the existing branch into a new worktree, or abort.
+# copy/symlink: untracked files to bring from the main checkout into worktrees (globs,
+# relative to the repo; existing files are left alone). Repos can add more in .swarm.toml.
+[worktrees]
+base_branch = ""
+on_conflict = "ask"
+copy = []                 # e.g. [".env", "config/local.yml"]
+symlink = []              # e.g. ["certs", "node_modules"]
+
+# `L` on a finished worktree session: rebase on the base branch, run these in the
+# worktree (all must pass), push, open/update the PR. Repos add their own in .swarm.toml.
+[land]
+checks = []               # e.g. ["cargo test"]
+
+# Obsidian vault: tasks and daily logs inside it open in Obsidian (obsidian:// links)
+# instead of Cursor, and task files written there use Obsidian's property formats.
+# daily_notes sends swarm's daily-log entries to the vault's daily note instead of
+# daily_dir (folder and file name as in the Daily notes plugin, chrono format).
+[obsidian]
+vault = ""                # e.g. "~/Notes" ("" = no vault)
+open_in_obsidian = true
+daily_notes = false
+daily_note_folder = ""    # e.g. "Journal"
+daily_note_format = "%Y-%m-%d"
+
+# Self-update (daily on startup, or `swarm update`). releases_url points the check
+# at a mirror of GitHub's releases API, e.g. GitHub Enterprise; GITHUB_TOKEN is
+# sent with the request when set. HTTPS_PROXY / NO_PROXY are honored.
+[updates]
+releases_url = ""         # "" = https://api.github.com/repos/whopio/swarm/releases
+homebrew = "upgrade"      # installed with Homebrew: "upgrade" runs brew upgrade swarm, "skip" leaves it to you
+
+# Dates and times in the TUI ("" = from the locale: LC_ALL, LC_TIME, LANG).
+# Status JSON, task files and logs always use ISO 8601.
+[dates]
+clock = ""                # "12h" (5:30pm) or "24h" (17:30)
+week_start = ""           # first day of the agenda's weeks: "monday", "sunday" or "saturday"
+date_order = ""           # "month-day" (Mar 14), "day-month" (14 Mar) or "iso" (03-14)
+
+# Saved views: f cycles through them in the agents and tasks lists. A filter is
+# space-separated terms that all have to match: status:needs_input (or blocked),
+# tag:backend, repo:billing, agent:codex, age>10m / age<1h (since the agent's last
+# output, or the task file's last edit), due:today|week|overdue|none, due<3d,
+# has:agent / no:agent; other words match names and titles. / takes the same terms.
+# [[views]]
+# name = "Blocked >10m"
+# filter = "status:blocked age>10m"
+#
+# [[views]]
+# name = "Due this week, no agent"
+# filter = "due:week no:agent"
+
+# Status timing per agent: output in the last running_secs = Running, then Idle.
+# Raise running_secs for slow models that legitimately go quiet mid-task.
+# health_check_secs: once an agent has been quiet this long, check its pane for
+# a prompt and its process tree, and mark it Error if the agent process is gone.
+# [detection.claude]
+# running_secs = 5
+# idle_secs = 30
+# health_check_secs = 600
+
+# Default model per agent (override per session with --model or the new-agent dialog)
+[models]
+# claude = "sonnet"   # sonnet, opus, haiku, ...
+# codex = "gpt-5-codex"
+
 # Bash commands that run without permission prompts in Claude Code
 # Format: "Bash(command:*)" where :* allows any arguments
 [allowed_tools]
@@ -181,12 +325,74 @@ pub struct Config {
 	pub keybindings: Keybindings,
 	#[serde(default)]
 	pub allowed_tools: AllowedTools,
+	/// Default model per agent, e.g. claude = "sonnet"
+	#[serde(default)]
+	pub models: std::collections::HashMap<String, String>,
+	#[serde(default)]
+	pub task_sync: TaskSync,
+	#[serde(default)]
+	pub team: Team,
+	#[serde(default)]
+	pub push: Push,
+	#[serde(default)]
+	pub limits: Limits,
+	#[serde(default)]
+	pub repos: Repos,
+	#[serde(default)]
+	pub hooks: Hooks,
+	#[serde(default)]
+	pub worktrees: Worktrees,
+	#[serde(default)]
+	pub land: Land,
+	#[serde(default)]
+	pub obsidian: Obsidian,
+	#[serde(default)]
+	pub updates: Updates,
+	#[serde(default)]
+	pub dates: Dates,
+	/// Saved filters for the agents and tasks lists, in the order f cycles them
+	#[serde(default)]
+	pub views: Vec<View>,
+	/// Status thresholds per agent, e.g. [detection.claude] running_secs = 10
+	#[serde(default)]
+	pub detection: std::collections::HashMap<String, AgentDetection>,
+}
+
+impl Config {
+	/// Model to launch an agent with: explicit choice, else the [models] default
+	pub fn model_for(&self, agent: &str, requested: Option<&str>) -> Option<String> {
+		requested
+			.map(str::trim)
+			.filter(|m| !m.is_empty() && *m != "default")
+			.map(str::to_string)
+			.or_else(|| self.models.get(agent).cloned())
+	}
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct General {
 	pub default_agent: String,
-	pub poll_interval_ms: u64,
+	pub poll_interval_ms: u64, // session status refresh
+	#[serde(default = "default_preview_poll_ms")]
+	pub preview_poll_ms: u64,
+	#[serde(default = "default_tasks_poll_ms")]
+	pub tasks_poll_ms: u64,
+	#[serde(default = "default_blur_poll_ms")]
+	pub blur_poll_ms: u64, // all polling while the terminal is unfocused
+	#[serde(default = "default_conflict_check_ms")]
+	pub conflict_check_ms: u64, // compare agents' touched files (0 = off)
+	#[serde(default = "default_trash_days")]
+	pub trash_days: u64, // orphan cleanup moves files to ~/.swarm/trash for this long
+	#[serde(default)]
+	pub max_agents: usize, // 0 = unlimited
+	#[serde(default)]
+	pub claim_tasks: bool, // Mark started tasks as claimed so teammates skip them
+	#[serde(default)]
+	pub claim_name: String, // "" = user@hostname
+	#[serde(default = "default_auto_interview")]
+	pub auto_interview: bool, // Interview first when a claude agent starts on a task with no body
+	#[serde(default)]
+	pub auto_name: bool, // Ask claude -p (haiku) for a name when `swarm new` gets only a prompt
 	pub logs_dir: String,
 	#[serde(default = "default_daily_dir")]
 	pub daily_dir: String,
@@ -200,6 +406,30 @@ pub struct General {
 	pub hooks_installed: bool, // Track if we've installed Claude hooks
 }
 
+fn default_auto_interview() -> bool {
+	true
+}
+
+fn default_preview_poll_ms() -> u64 {
+	1000
+}
+
+fn default_tasks_poll_ms() -> u64 {
+	5000
+}
+
+fn default_blur_poll_ms() -> u64 {
+	10_000
+}
+
+fn default_conflict_check_ms() -> u64 {
+	30_000
+}
+
+fn default_trash_days() -> u64 {
+	7
+}
+
 fn default_status_style() -> String {
 	"text".to_string()
 }
@@ -221,6 +451,387 @@ pub struct Notifications {
 	pub sound_needs_input: String,
 	pub sound_done: String,
 	pub sound_error: String,
+	#[serde(default = "default_digest_time")]
+	pub digest_time: String, // "HH:MM", empty disables the daily digest
+	#[serde(default = "default_due_reminder")]
+	pub due_reminder: String, // lead time like "1h" for tasks due at a time, empty disables
+	#[serde(default)]
+	pub terminal_bell: bool,
+	#[serde(default)]
+	pub tmux_alerts: bool,
+	/// Shell command run instead of the built-in notifier
+	#[serde(default)]
+	pub command: String,
+	/// Overrides keyed by task priority ("P0")
+	#[serde(default)]
+	pub priorities: std::collections::HashMap<String, PriorityStyle>,
+}
+
+/// Sounds and emphasis for agents on tasks of one priority; unset sounds fall back
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PriorityStyle {
+	#[serde(default)]
+	pub sound_needs_input: Option<String>,
+	#[serde(default)]
+	pub sound_done: Option<String>,
+	#[serde(default)]
+	pub sound_error: Option<String>,
+	#[serde(default)]
+	pub flash: bool,
+}
+
+impl Notifications {
+	fn priority(&self, priority: Option<&str>) -> Option<&PriorityStyle> {
+		let priority = priority?;
+		self.priorities
+			.iter()
+			.find(|(k, _)| k.eq_ignore_ascii_case(priority))
+			.map(|(_, v)| v)
+	}
+
+	/// Sound for `event` ("needs_input", "done" or "error") on a task of `priority`
+	pub fn sound(&self, event: &str, priority: Option<&str>) -> &str {
+		let style = self.priority(priority);
+		let (base, over) = match event {
+			"needs_input" => (&self.sound_needs_input, style.and_then(|s| s.sound_needs_input.as_ref())),
+			"done" => (&self.sound_done, style.and_then(|s| s.sound_done.as_ref())),
+			_ => (&self.sound_error, style.and_then(|s| s.sound_error.as_ref())),
+		};
+		over.unwrap_or(base)
+	}
+
+	/// Whether rows for tasks of `priority` blink while they need attention
+	pub fn flash(&self, priority: Option<&str>) -> bool {
+		self.priority(priority).is_some_and(|s| s.flash)
+	}
+}
+
+fn default_digest_time() -> String {
+	"09:00".to_string()
+}
+
+fn default_due_reminder() -> String {
+	"1h".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskSync {
+	#[serde(default)]
+	pub enabled: bool,
+	#[serde(default = "default_sync_remote")]
+	pub remote: String,
+	#[serde(default = "default_sync_interval_secs")]
+	pub interval_secs: u64,
+}
+
+impl Default for TaskSync {
+	fn default() -> Self {
+		Self {
+			enabled: false,
+			remote: default_sync_remote(),
+			interval_secs: default_sync_interval_secs(),
+		}
+	}
+}
+
+fn default_sync_remote() -> String {
+	"origin".to_string()
+}
+
+fn default_sync_interval_secs() -> u64 {
+	300
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Team {
+	#[serde(default)]
+	pub name: String,
+	#[serde(default)]
+	pub publish_to: String,
+	#[serde(default = "default_team_interval_secs")]
+	pub interval_secs: u64,
+	#[serde(default)]
+	pub members: Vec<String>,
+	/// Sent as `Authorization: Bearer` to http(s) publish_to and members
+	#[serde(default)]
+	pub token: String,
+}
+
+impl Default for Team {
+	fn default() -> Self {
+		Self {
+			name: String::new(),
+			publish_to: String::new(),
+			interval_secs: default_team_interval_secs(),
+			members: Vec::new(),
+			token: String::new(),
+		}
+	}
+}
+
+fn default_team_interval_secs() -> u64 {
+	60
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Limits {
+	#[serde(default)]
+	pub max_minutes: u64, // 0 = none
+	#[serde(default)]
+	pub max_cost: f64, // USD, 0 = none
+	#[serde(default = "default_limit_action")]
+	pub action: String,
+	#[serde(default = "default_yolo_limit_action")]
+	pub yolo_action: String,
+}
+
+impl Default for Limits {
+	fn default() -> Self {
+		Self {
+			max_minutes: 0,
+			max_cost: 0.0,
+			action: default_limit_action(),
+			yolo_action: default_yolo_limit_action(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Repos {
+	#[serde(default)]
+	pub allow: Vec<String>,
+	#[serde(default)]
+	pub deny: Vec<String>,
+	#[serde(default)]
+	pub yolo_allow: Vec<String>,
+}
+
+impl Repos {
+	/// Refuse `dir` unless the lists permit it. `main_repo` is the repository a
+	/// worktree belongs to, so worktrees of an allowed repo are allowed too.
+	pub fn check(&self, dir: &Path, main_repo: Option<&Path>, yolo: bool) -> Result<()> {
+		let candidates: Vec<&Path> = std::iter::once(dir).chain(main_repo).collect();
+		let matching = |patterns: &[String]| {
+			patterns
+				.iter()
+				.find(|p| candidates.iter().any(|c| repo_pattern_matches(p, c)))
+				.cloned()
+		};
+		if let Some(pattern) = matching(&self.deny) {
+			return Err(anyhow::anyhow!(
+				"refusing to start an agent in {}: it matches [repos] deny \"{}\" in ~/.swarm/config.toml",
+				dir.display(),
+				pattern
+			));
+		}
+		let (list, key) = if yolo && !self.yolo_allow.is_empty() {
+			(&self.yolo_allow, "yolo_allow")
+		} else {
+			(&self.allow, "allow")
+		};
+		if !list.is_empty() && matching(list).is_none() {
+			return Err(anyhow::anyhow!(
+				"refusing to start {} agent in {}: not in [repos] {} in ~/.swarm/config.toml",
+				if yolo { "a YOLO" } else { "an" },
+				dir.display(),
+				key
+			));
+		}
+		Ok(())
+	}
+}
+
+fn repo_pattern_matches(pattern: &str, dir: &Path) -> bool {
+	let expanded = expand_path(pattern);
+	let expanded = expanded.trim_end_matches('/');
+	if Path::new(expanded) == dir {
+		return true;
+	}
+	let options = glob::MatchOptions {
+		require_literal_separator: true,
+		..Default::default()
+	};
+	glob::Pattern::new(expanded).is_ok_and(|p| p.matches_path_with(dir, options))
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Hooks {
+	#[serde(default)]
+	pub on_create: String,
+	#[serde(default)]
+	pub on_done: String,
+	#[serde(default)]
+	pub on_kill: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Worktrees {
+	/// Branch new worktrees start from: a branch name, or "current"; "" = origin's default branch
+	#[serde(default)]
+	pub base_branch: String,
+	/// When the worktree path or branch already exists: ask, reuse, suffix, checkout or abort
+	#[serde(default = "default_on_conflict")]
+	pub on_conflict: String,
+	#[serde(default)]
+	pub copy: Vec<String>,
+	#[serde(default)]
+	pub symlink: Vec<String>,
+}
+
+impl Default for Worktrees {
+	fn default() -> Self {
+		Self {
+			base_branch: String::new(),
+			on_conflict: default_on_conflict(),
+			copy: Vec::new(),
+			symlink: Vec::new(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Obsidian {
+	/// Vault folder ("" = no vault)
+	#[serde(default)]
+	pub vault: String,
+	#[serde(default = "default_open_in_obsidian")]
+	pub open_in_obsidian: bool,
+	/// Daily-log entries go to the vault's daily note instead of daily_dir
+	#[serde(default)]
+	pub daily_notes: bool,
+	/// Daily notes folder, relative to the vault
+	#[serde(default)]
+	pub daily_note_folder: String,
+	/// Daily note file name (chrono format, without .md)
+	#[serde(default = "default_daily_note_format")]
+	pub daily_note_format: String,
+}
+
+impl Default for Obsidian {
+	fn default() -> Self {
+		Self {
+			vault: String::new(),
+			open_in_obsidian: true,
+			daily_notes: false,
+			daily_note_folder: String::new(),
+			daily_note_format: default_daily_note_format(),
+		}
+	}
+}
+
+fn default_open_in_obsidian() -> bool {
+	true
+}
+
+fn default_daily_note_format() -> String {
+	"%Y-%m-%d".to_string()
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Updates {
+	/// Releases API to check instead of GitHub's ("" = whopio/swarm on api.github.com)
+	#[serde(default)]
+	pub releases_url: String,
+	/// Homebrew installs are never self-replaced
+	#[serde(default)]
+	pub homebrew: Homebrew,
+}
+
+/// What a Homebrew install does when a new version is out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Homebrew {
+	/// Run brew upgrade swarm
+	#[default]
+	Upgrade,
+	/// Only say there's a new version
+	Skip,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct View {
+	pub name: String,
+	pub filter: String, // e.g. "status:blocked age>10m"
+}
+
+/// How the TUI shows dates (see dates.rs); empty fields follow the locale
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Dates {
+	#[serde(default)]
+	pub clock: String, // "12h", "24h"
+	#[serde(default)]
+	pub week_start: String, // "monday", "sunday", "saturday"
+	#[serde(default)]
+	pub date_order: String, // "month-day", "day-month", "iso"
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Land {
+	#[serde(default)]
+	pub checks: Vec<String>,
+}
+
+fn default_on_conflict() -> String {
+	"ask".to_string()
+}
+
+fn default_limit_action() -> String {
+	"pause".to_string()
+}
+
+fn default_yolo_limit_action() -> String {
+	"kill".to_string()
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentDetection {
+	pub running_secs: Option<u64>,
+	pub idle_secs: Option<u64>,
+	pub health_check_secs: Option<u64>, // quiet this long: check the agent process is alive
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Push {
+	#[serde(default = "default_ntfy_server")]
+	pub ntfy_server: String,
+	#[serde(default)]
+	pub ntfy_topic_needs_input: String,
+	#[serde(default)]
+	pub ntfy_topic_done: String,
+	#[serde(default)]
+	pub ntfy_topic_error: String,
+	#[serde(default)]
+	pub pushover_token: String,
+	#[serde(default)]
+	pub pushover_user: String,
+	#[serde(default = "default_pushover_events")]
+	pub pushover_events: Vec<String>,
+	/// Base URL of `swarm serve`; notifications link to <dashboard_url>/session/<name>
+	#[serde(default)]
+	pub dashboard_url: String,
+}
+
+impl Default for Push {
+	fn default() -> Self {
+		Self {
+			ntfy_server: default_ntfy_server(),
+			ntfy_topic_needs_input: String::new(),
+			ntfy_topic_done: String::new(),
+			ntfy_topic_error: String::new(),
+			pushover_token: String::new(),
+			pushover_user: String::new(),
+			pushover_events: default_pushover_events(),
+			dashboard_url: String::new(),
+		}
+	}
+}
+
+fn default_ntfy_server() -> String {
+	"https://ntfy.sh".to_string()
+}
+
+fn default_pushover_events() -> Vec<String> {
+	vec!["needs_input".to_string()]
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -435,6 +1046,10 @@ pub fn load_or_init() -> Result<Config> {
 	cfg.general.logs_dir = expand_path(&cfg.general.logs_dir);
 	cfg.general.daily_dir = expand_path(&cfg.general.daily_dir);
 	cfg.general.tasks_dir = expand_path(&cfg.general.tasks_dir);
+	cfg.obsidian.vault = expand_path(&cfg.obsidian.vault);
+	for (agent, model) in &cfg.models {
+		validate_model(model).map_err(|e| anyhow::anyhow!("[models] {agent}: {e}"))?;
+	}
 	for path in [
 		cfg.general.logs_dir.as_str(),
 		cfg.general.daily_dir.as_str(),
@@ -446,6 +1061,16 @@ pub fn load_or_init() -> Result<Config> {
 	Ok(cfg)
 }
 
+/// Models go into the agent's shell command, so only plain names pass:
+/// letters, digits and . _ : / -
+pub fn validate_model(model: &str) -> Result<()> {
+	let plain = !model.is_empty() && model.chars().all(|c| c.is_ascii_alphanumeric() || "._:/-".contains(c));
+	if !plain {
+		return Err(anyhow::anyhow!("invalid model {model:?} (letters, digits and . _ : / - only)"));
+	}
+	Ok(())
+}
+
 pub fn expand_path(input: &str) -> String {
 	if input.starts_with("~/") {
 		if let Some(home) = dirs::home_dir() {
@@ -484,6 +1109,10 @@ pub fn snapshots_dir() -> Result<PathBuf> {
 	Ok(dir)
 }
 
+pub fn status_cache_path() -> Result<PathBuf> {
+	Ok(base_dir()?.join("status.json"))
+}
+
 /// Save config back to file (for updating hooks_installed, etc.)
 pub fn save_config(cfg: &Config) -> Result<()> {
 	let config_path = base_dir()?.join("config.toml");
@@ -500,6 +1129,86 @@ fn migrate_config(config_path: &Path) -> Result<()> {
 	// Each migration checks if a key exists and adds it if missing
 	let migrations: Vec<(&str, &str, &str)> = vec![
 		// Add new migrations here as needed
+		(
+			"preview_poll_ms",
+			"[general]",
+			"preview_poll_ms = 1000    # preview capture for the selected agent",
+		),
+		(
+			"tasks_poll_ms",
+			"[general]",
+			"tasks_poll_ms = 5000      # re-read tasks_dir",
+		),
+		(
+			"blur_poll_ms",
+			"[general]",
+			"blur_poll_ms = 10000      # slower status refresh while the terminal is unfocused",
+		),
+		(
+			"conflict_check_ms",
+			"[general]",
+			"conflict_check_ms = 30000 # warn when agents edit the same files in a repo (0 = off)",
+		),
+		(
+			"trash_days",
+			"[general]",
+			"trash_days = 7            # keep orphaned logs/session data in ~/.swarm/trash this long",
+		),
+		(
+			"max_agents",
+			"[general]",
+			"max_agents = 0            # cap on running agents for `swarm run-all` (0 = no limit)",
+		),
+		(
+			"claim_tasks",
+			"[general]",
+			"claim_tasks = false       # write claimed_by: into tasks you start (shared/synced tasks_dir)",
+		),
+		(
+			"claim_name",
+			"[general]",
+			"claim_name = \"\"           # who you are in claims (\"\" = user@hostname)",
+		),
+		(
+			"auto_interview",
+			"[general]",
+			"auto_interview = true     # start claude on one-line tasks with /interview",
+		),
+		(
+			"auto_name",
+			"[general]",
+			"auto_name = false         # name prompt-only `swarm new` sessions with a quick haiku call (off = first words of the prompt)",
+		),
+		(
+			"homebrew",
+			"[updates]",
+			"homebrew = \"upgrade\"      # installed with Homebrew: \"upgrade\" runs brew upgrade swarm, \"skip\" leaves it to you",
+		),
+		(
+			"digest_time",
+			"[notifications]",
+			"digest_time = \"09:00\"  # morning digest of overdue/due-today tasks (\"\" to disable)",
+		),
+		(
+			"due_reminder",
+			"[notifications]",
+			"due_reminder = \"1h\"    # remind this long before tasks with a due time (due: 2025-03-14 17:00; \"\" to disable)",
+		),
+		(
+			"terminal_bell",
+			"[notifications]",
+			"terminal_bell = false  # ring the terminal bell when an agent needs input",
+		),
+		(
+			"tmux_alerts",
+			"[notifications]",
+			"tmux_alerts = false    # flag swarm's own tmux window (bell) when an agent needs input",
+		),
+		(
+			"token",
+			"[team]",
+			"token = \"\"                # bearer token for http(s) publish_to/members (\"\" = none)",
+		),
 	];
 
 	let mut modified_content = content.clone();
@@ -0,0 +1,97 @@
+// Spots agents about to step on each other: files touched (uncommitted, or
+// committed on the branch since it left the base) by sessions in different
+// checkouts of the same repo, and sessions sharing one checkout that has edits.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Another session touching the same files as this one
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+	pub other: String,      // display name of the other session
+	pub paths: Vec<String>, // empty = same checkout, anything changed there is shared
+}
+
+fn git(dir: &Path, args: &[&str]) -> Option<String> {
+	let output = Command::new("git").arg("-C").arg(dir).args(args).output().ok()?;
+	output
+		.status
+		.success()
+		.then(|| String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+}
+
+/// Repo identity (its shared .git dir) and the paths changed in `dir`
+fn touched_files(dir: &Path) -> Option<(PathBuf, HashSet<String>)> {
+	let repo = PathBuf::from(git(dir, &["rev-parse", "--path-format=absolute", "--git-common-dir"])?);
+	let mut paths: HashSet<String> = git(dir, &["status", "--porcelain"])
+		.unwrap_or_default()
+		.lines()
+		.filter_map(|l| l.get(3..))
+		.map(|p| p.rsplit(" -> ").next().unwrap_or(p).trim_matches('"').to_string())
+		.collect();
+	// Work already committed on this branch counts too, until it's merged
+	let base = git(dir, &["symbolic-ref", "--short", "refs/remotes/origin/HEAD"]).or_else(|| {
+		["origin/main", "origin/master", "main", "master"]
+			.into_iter()
+			.find(|b| git(dir, &["rev-parse", "--verify", "--quiet", b]).is_some())
+			.map(str::to_string)
+	});
+	if let Some(merge_base) = base.and_then(|b| git(dir, &["merge-base", "HEAD", &b])) {
+		if let Some(committed) = git(dir, &["diff", "--name-only", &format!("{merge_base}..HEAD")]) {
+			paths.extend(committed.lines().map(str::to_string));
+		}
+	}
+	Some((repo, paths))
+}
+
+/// `sessions` are (session name, display name, working dir). Returns the
+/// conflicts per session name; sessions without any are left out.
+pub fn find(sessions: &[(String, String, PathBuf)]) -> HashMap<String, Vec<Conflict>> {
+	let mut touched: HashMap<PathBuf, Option<(PathBuf, HashSet<String>)>> = HashMap::new();
+	for (_, _, dir) in sessions {
+		touched.entry(dir.clone()).or_insert_with(|| touched_files(dir));
+	}
+	let mut found: HashMap<String, Vec<Conflict>> = HashMap::new();
+	for (i, (session_a, name_a, dir_a)) in sessions.iter().enumerate() {
+		let Some((repo_a, paths_a)) = &touched[dir_a] else {
+			continue;
+		};
+		for (session_b, name_b, dir_b) in &sessions[i + 1..] {
+			let Some((repo_b, paths_b)) = &touched[dir_b] else {
+				continue;
+			};
+			if repo_a != repo_b || paths_a.is_empty() || paths_b.is_empty() {
+				continue;
+			}
+			let paths: Vec<String> = if dir_a == dir_b {
+				Vec::new()
+			} else {
+				let mut shared: Vec<String> = paths_a.intersection(paths_b).cloned().collect();
+				if shared.is_empty() {
+					continue;
+				}
+				shared.sort();
+				shared
+			};
+			found.entry(session_a.clone()).or_default().push(Conflict {
+				other: name_b.clone(),
+				paths: paths.clone(),
+			});
+			found.entry(session_b.clone()).or_default().push(Conflict {
+				other: name_a.clone(),
+				paths,
+			});
+		}
+	}
+	found
+}
+
+/// One-line description, e.g. "also touched by auth-v2: src/auth.rs (+2)"
+pub fn describe(conflict: &Conflict) -> String {
+	match conflict.paths.split_first() {
+		None => format!("shares its checkout (which has changes) with {}", conflict.other),
+		Some((first, [])) => format!("also touched by {}: {first}", conflict.other),
+		Some((first, rest)) => format!("also touched by {}: {first} (+{})", conflict.other, rest.len()),
+	}
+}
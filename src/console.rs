@@ -0,0 +1,100 @@
+// Internal errors from the TUI and its background threads. Printing them with
+// eprintln! would scribble over the alternate screen, so they're kept here for
+// the console panel (E) and appended to ~/.swarm/swarm.log.
+//
+// Diagnostics (tmux calls, refresh cycles and their timings) go through
+// `tracing` to daily files in ~/.swarm/trace/, at the level from --log-level
+// or SWARM_LOG (default warn).
+
+use std::io::Write;
+use std::sync::Mutex;
+
+/// How many errors the console keeps in memory
+const MAX_ENTRIES: usize = 200;
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+	pub at: chrono::DateTime<chrono::Local>,
+	pub context: String,
+	pub message: String,
+}
+
+struct Console {
+	entries: Vec<Entry>,
+	// Errors since the panel was last opened, for the header badge
+	unseen: usize,
+}
+
+static CONSOLE: Mutex<Console> = Mutex::new(Console {
+	entries: Vec::new(),
+	unseen: 0,
+});
+
+/// Send `tracing` output to ~/.swarm/trace/swarm.YYYY-MM-DD.log (last 7 days
+/// kept). Closing spans are logged too, so slow tmux calls show their time.
+pub fn init_tracing(level: Option<&str>) {
+	let level = level
+		.map(str::to_string)
+		.or_else(|| std::env::var("SWARM_LOG").ok())
+		.and_then(|l| l.parse::<tracing::level_filters::LevelFilter>().ok())
+		.unwrap_or(tracing::level_filters::LevelFilter::WARN);
+	if level == tracing::level_filters::LevelFilter::OFF {
+		return;
+	}
+	let Ok(dir) = crate::config::base_dir().map(|d| d.join("trace")) else {
+		return;
+	};
+	// The appender prunes old files on start and complains if the folder is missing
+	let _ = std::fs::create_dir_all(&dir);
+	let Ok(appender) = tracing_appender::rolling::RollingFileAppender::builder()
+		.rotation(tracing_appender::rolling::Rotation::DAILY)
+		.filename_prefix("swarm")
+		.filename_suffix("log")
+		.max_log_files(7)
+		.build(dir)
+	else {
+		return;
+	};
+	let _ = tracing_subscriber::fmt()
+		.with_writer(appender)
+		.with_ansi(false)
+		.with_max_level(level)
+		.with_thread_names(true)
+		.with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
+		.try_init();
+}
+
+/// Record an error, e.g. `error("start session", e)`
+pub fn error(context: &str, message: impl std::fmt::Display) {
+	let entry = Entry {
+		at: chrono::Local::now(),
+		context: context.to_string(),
+		message: message.to_string(),
+	};
+	tracing::error!(context = %entry.context, "{}", entry.message);
+	if let Ok(path) = crate::config::base_dir().map(|d| d.join("swarm.log")) {
+		if let Ok(mut log) = std::fs::OpenOptions::new().create(true).append(true).open(path) {
+			let _ = writeln!(log, "[{}] {}: {}", entry.at.format("%Y-%m-%d %H:%M:%S"), entry.context, entry.message);
+		}
+	}
+	let mut console = CONSOLE.lock().unwrap_or_else(|e| e.into_inner());
+	console.entries.push(entry);
+	if console.entries.len() > MAX_ENTRIES {
+		console.entries.remove(0);
+	}
+	console.unseen += 1;
+}
+
+/// Recorded errors, oldest first
+pub fn entries() -> Vec<Entry> {
+	CONSOLE.lock().unwrap_or_else(|e| e.into_inner()).entries.clone()
+}
+
+/// Errors recorded since the last `mark_seen`
+pub fn unseen() -> usize {
+	CONSOLE.lock().unwrap_or_else(|e| e.into_inner()).unseen
+}
+
+pub fn mark_seen() {
+	CONSOLE.lock().unwrap_or_else(|e| e.into_inner()).unseen = 0;
+}
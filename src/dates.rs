@@ -0,0 +1,131 @@
+// Dates and times as the TUI shows them: 12- or 24-hour clock, "Mar 14" or
+// "14 Mar", and the weekday the agenda's weeks start on. Each comes from [dates]
+// in config, or when left empty from the locale (LC_ALL, LC_TIME, LANG), so
+// en_US gets 5:30pm and Sunday weeks while de_DE gets 17:30 and "14 Mar".
+// Month and day names stay English. Everything machine-read (status JSON, task
+// frontmatter, logs, the calendar feed) keeps ISO 8601.
+
+use chrono::{NaiveDate, NaiveTime, Weekday};
+use std::sync::OnceLock;
+
+/// Regions that read the clock in 12 hours
+const TWELVE_HOUR: &[&str] = &["US", "CA", "AU", "NZ", "IN", "PH", "PK", "EG"];
+
+/// Regions whose calendars start on Sunday
+const SUNDAY_FIRST: &[&str] = &["US", "CA", "JP", "KR", "TW", "HK", "IL", "BR", "MX", "PH", "ZA"];
+
+/// Regions that put the month first ("Mar 14")
+const MONTH_FIRST: &[&str] = &["US", "CA", "PH"];
+
+/// Regions that write dates year-month-day
+const YEAR_FIRST: &[&str] = &["CN", "JP", "KR", "TW", "HU", "LT", "SE"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Order {
+	MonthDay,
+	DayMonth,
+	Iso,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Style {
+	twelve_hour: bool,
+	week_start: Weekday,
+	order: Order,
+}
+
+static STYLE: OnceLock<Style> = OnceLock::new();
+
+/// Settle the style from config and the locale; call once at startup
+pub fn init(cfg: &crate::config::Dates) {
+	let region = region();
+	let region = region.as_deref();
+	let from_locale = |list: &[&str]| region.is_some_and(|r| list.contains(&r));
+	let twelve_hour = match cfg.clock.trim() {
+		"12h" => true,
+		"24h" => false,
+		_ => from_locale(TWELVE_HOUR),
+	};
+	let week_start = match cfg.week_start.trim().to_lowercase().as_str() {
+		"sunday" => Weekday::Sun,
+		"saturday" => Weekday::Sat,
+		"monday" => Weekday::Mon,
+		_ if from_locale(SUNDAY_FIRST) => Weekday::Sun,
+		_ => Weekday::Mon,
+	};
+	let order = match cfg.date_order.trim() {
+		"month-day" => Order::MonthDay,
+		"day-month" => Order::DayMonth,
+		"iso" => Order::Iso,
+		// No locale (C, POSIX, unset) keeps the month first, as before
+		_ if region.is_none() || from_locale(MONTH_FIRST) => Order::MonthDay,
+		_ if from_locale(YEAR_FIRST) => Order::Iso,
+		_ => Order::DayMonth,
+	};
+	let _ = STYLE.set(Style {
+		twelve_hour,
+		week_start,
+		order,
+	});
+}
+
+fn style() -> Style {
+	*STYLE.get().unwrap_or(&Style {
+		twelve_hour: false,
+		week_start: Weekday::Mon,
+		order: Order::MonthDay,
+	})
+}
+
+/// "US" from LC_ALL / LC_TIME / LANG like "en_US.UTF-8"; None for C and POSIX
+fn region() -> Option<String> {
+	let locale = ["LC_ALL", "LC_TIME", "LANG"]
+		.iter()
+		.find_map(|key| std::env::var(key).ok().filter(|v| !v.trim().is_empty()))?;
+	let locale = locale.split(['.', '@']).next().unwrap_or_default();
+	let region = locale.split(['_', '-']).nth(1)?;
+	Some(region.to_uppercase())
+}
+
+/// "17:30" or "5:30pm"
+pub fn time(t: NaiveTime) -> String {
+	if style().twelve_hour {
+		t.format("%-I:%M%P").to_string()
+	} else {
+		t.format("%H:%M").to_string()
+	}
+}
+
+/// "17:30:05" or "5:30:05pm"
+pub fn time_secs(t: NaiveTime) -> String {
+	if style().twelve_hour {
+		t.format("%-I:%M:%S%P").to_string()
+	} else {
+		t.format("%H:%M:%S").to_string()
+	}
+}
+
+/// "Mar 14", "14 Mar" or "03-14"
+pub fn short_date(d: NaiveDate) -> String {
+	match style().order {
+		Order::MonthDay => d.format("%b %-d"),
+		Order::DayMonth => d.format("%-d %b"),
+		Order::Iso => d.format("%m-%d"),
+	}
+	.to_string()
+}
+
+/// "Friday Mar 14", "Friday 14 Mar" or "Friday 2025-03-14"
+pub fn long_date(d: NaiveDate) -> String {
+	match style().order {
+		Order::MonthDay => d.format("%A %b %-d"),
+		Order::DayMonth => d.format("%A %-d %b"),
+		Order::Iso => d.format("%A %Y-%m-%d"),
+	}
+	.to_string()
+}
+
+/// First day of the agenda's weeks
+pub fn week_start() -> Weekday {
+	style().week_start
+}
@@ -1,4 +1,6 @@
+use crate::config::AgentDetection;
 use crate::model::AgentStatus;
+use once_cell::sync::Lazy;
 use regex::Regex;
 use std::time::Duration;
 
@@ -6,9 +8,14 @@ pub struct DetectionConfig {
 	pub needs_input_patterns: Vec<Regex>,
 	pub running_threshold: Duration,
 	pub idle_threshold: Duration,
+	/// Quiet this long (and not waiting on a prompt): check the agent is still alive
+	pub health_check: Option<Duration>,
 }
 
-pub fn detection_for_agent(agent: &str) -> DetectionConfig {
+pub fn detection_for_agent(
+	agent: &str,
+	overrides: &std::collections::HashMap<String, AgentDetection>,
+) -> DetectionConfig {
 	// Defaults are tuned for Claude Code; other agents fall back to same set.
 	let patterns = vec![
 		// Permission prompts (high confidence)
@@ -30,18 +37,29 @@ pub fn detection_for_agent(agent: &str) -> DetectionConfig {
 		Regex::new(r"Type your answer").unwrap(),
 	];
 
-	let running_threshold = Duration::from_secs(5);
-	let idle_threshold = Duration::from_secs(30);
+	// [detection.<agent>] in config overrides the timing, e.g. for slow models
+	let thresholds = overrides.get(agent);
+	let running_threshold = Duration::from_secs(thresholds.and_then(|t| t.running_secs).unwrap_or(5));
+	let idle_threshold = Duration::from_secs(thresholds.and_then(|t| t.idle_secs).unwrap_or(30));
+	let health_check = thresholds
+		.and_then(|t| t.health_check_secs)
+		.filter(|secs| *secs > 0)
+		.map(Duration::from_secs);
 
-	match agent {
-		_ => DetectionConfig {
-			needs_input_patterns: patterns,
-			running_threshold,
-			idle_threshold,
-		},
+	DetectionConfig {
+		needs_input_patterns: patterns,
+		running_threshold,
+		idle_threshold,
+		health_check,
 	}
 }
 
+/// A prompt only counts while it's still on screen: within this many non-empty
+/// lines of the bottom. Output after it means it was answered (or moved past).
+pub const PROMPT_WINDOW: usize = 20;
+
+/// Status from the agent's recent output. `lines` should only hold output written
+/// since input was last sent (see `tail_lines_from`), so answered prompts drop out.
 pub fn detect_status(
 	lines: &[String],
 	detection: &DetectionConfig,
@@ -55,8 +73,9 @@ pub fn detect_status(
 		return AgentStatus::Done;
 	}
 
-	// Regex prompts.
-	if lines.iter().any(|l| {
+	// Regex prompts, only near the bottom of the output.
+	let recent = lines.iter().rev().filter(|l| !l.trim().is_empty()).take(PROMPT_WINDOW);
+	if recent.into_iter().any(|l| {
 		detection
 			.needs_input_patterns
 			.iter()
@@ -77,3 +96,228 @@ pub fn detect_status(
 
 	AgentStatus::Unknown
 }
+
+/// Claude's tool banner, e.g. "⏺ Bash(cargo test)" or "⏺ Update(src/foo.rs)"
+static TOOL_BANNER: Lazy<Regex> =
+	Lazy::new(|| Regex::new(r"^\s*[⏺●•]\s+([A-Z][A-Za-z]+)\((.*)\)\s*$").unwrap());
+/// Plain activity lines, e.g. "Running: cargo test" or codex's "• Edited src/foo.rs (+3 -1)"
+static ACTIVITY_LINE: Lazy<Regex> = Lazy::new(|| {
+	Regex::new(r"^\s*([⏺●•]\s+)?(Running|Ran|Editing|Edited|Reading|Writing|Searching|Fetching)(:?)\s+(\S.*)$")
+		.unwrap()
+});
+
+/// Most recent tool action in the output, as "Running cargo test" / "Editing src/foo.rs"
+pub fn latest_activity(lines: &[String]) -> Option<String> {
+	lines.iter().rev().find_map(|line| {
+		if let Some(caps) = TOOL_BANNER.captures(line) {
+			let arg = caps[2].trim().trim_matches('"');
+			let verb = match &caps[1] {
+				"Bash" => "Running",
+				"Read" => "Reading",
+				"Edit" | "Update" | "MultiEdit" => "Editing",
+				"Write" => "Writing",
+				"Grep" | "Glob" | "Search" => "Searching",
+				"WebFetch" | "Fetch" => "Fetching",
+				"WebSearch" => "Searching web",
+				"Task" => "Delegating",
+				other => other,
+			};
+			return Some(format!("{} {}", verb, arg));
+		}
+		// Without a bullet or colon it's probably just prose ("Reading the docs, ...")
+		let caps = ACTIVITY_LINE.captures(line)?;
+		if caps.get(1).is_none() && caps[3].is_empty() {
+			return None;
+		}
+		Some(format!("{} {}", &caps[2], caps[4].trim()))
+	})
+}
+
+/// Done/total from the latest todo or plan checklist in the output. Understands
+/// Claude's ☐/☒ list, codex's □/✔ plan and markdown "- [ ]"/"- [x]" items.
+pub fn todo_progress(lines: &[String]) -> Option<(usize, usize)> {
+	let items = latest_checklist(lines);
+	let done = items.iter().filter(|(checked, _)| *checked).count();
+	// A lone ✓ line is more likely a status message than a one-item plan
+	(items.len() > 1).then_some((done, items.len()))
+}
+
+/// Items of the latest checklist in the output, top to bottom: (checked, text)
+pub fn latest_checklist(lines: &[String]) -> Vec<(bool, String)> {
+	let mut items = Vec::new();
+	// Walk up from the newest line; the list ends at the first non-item above it
+	for line in lines.iter().rev() {
+		match checklist_item(line) {
+			Some(item) => items.push(item),
+			None if !items.is_empty() => break,
+			None => {}
+		}
+	}
+	items.reverse();
+	items
+}
+
+/// A checklist line as (checked, text), e.g. "☒ Run tests" → (true, "Run tests")
+pub fn checklist_item(line: &str) -> Option<(bool, String)> {
+	let item = line.trim_start().trim_start_matches('⎿').trim_start();
+	let item = item.strip_prefix("- ").unwrap_or(item);
+	let mut chars = item.chars();
+	let checked = match chars.next()? {
+		'☒' | '☑' | '✔' | '✓' | '◼' | '■' => true,
+		'☐' | '□' | '◻' | '▢' => false,
+		'[' => match (chars.next()?, chars.next()?) {
+			('x' | 'X', ']') => true,
+			(' ', ']') => false,
+			_ => return None,
+		},
+		_ => return None,
+	};
+	Some((checked, chars.as_str().trim().to_string()))
+}
+
+/// A suggested answer for the send-input modal: `label` is shown, `text` is sent
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuickReply {
+	pub label: String,
+	pub text: String,
+}
+
+/// Numbered menu entries, e.g. "❯ 1. Yes" or "  2) No, and tell Claude what to do"
+static MENU_OPTION: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*[│|]?\s*[❯>›]?\s*(\d)[.)]\s+(\S.*?)\s*[│|]?\s*$").unwrap());
+
+/// Replies that fit the prompt currently on screen: menu numbers for option lists,
+/// y/n for [y/N] prompts, yes/no/continue for questions. Empty when nothing matched.
+pub fn quick_replies(lines: &[String], detection: &DetectionConfig) -> Vec<QuickReply> {
+	let recent: Vec<&String> = lines.iter().rev().filter(|l| !l.trim().is_empty()).take(PROMPT_WINDOW).collect();
+	let Some(pos) = recent
+		.iter()
+		.position(|l| detection.needs_input_patterns.iter().any(|re| re.is_match(l)))
+	else {
+		return Vec::new();
+	};
+	let prompt = recent[pos].as_str();
+	let reply = |label: &str, text: &str| QuickReply {
+		label: label.to_string(),
+		text: text.to_string(),
+	};
+
+	// Option menus are printed below (i.e. more recently than) the question
+	let mut options: Vec<QuickReply> = recent[..pos]
+		.iter()
+		.rev()
+		.filter_map(|l| MENU_OPTION.captures(l))
+		.map(|caps| reply(&format!("{} {}", &caps[1], caps[2].trim()), &caps[1]))
+		.collect();
+	options.dedup_by(|a, b| a.text == b.text);
+	if !options.is_empty() {
+		return options;
+	}
+	if ["[Y/n]", "[y/N]", "(y/N)", "(Y/n)"].iter().any(|p| prompt.contains(p)) {
+		return vec![reply("y", "y"), reply("n", "n")];
+	}
+	if prompt.contains("Type your answer") {
+		return Vec::new();
+	}
+	vec![reply("yes", "yes"), reply("no", "no"), reply("continue", "continue")]
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::HashMap;
+
+	fn lines(text: &[&str]) -> Vec<String> {
+		text.iter().map(|l| l.to_string()).collect()
+	}
+
+	fn status(output: &[String], age_secs: u64) -> AgentStatus {
+		let detection = detection_for_agent("claude", &HashMap::new());
+		detect_status(output, &detection, Some(Duration::from_secs(age_secs)))
+	}
+
+	#[test]
+	fn prompt_at_bottom_needs_input() {
+		let output = lines(&["⏺ Bash(rm -rf target)", "Do you want to proceed?", "❯ 1. Yes", "  2. No"]);
+		assert_eq!(status(&output, 60), AgentStatus::NeedsInput);
+	}
+
+	#[test]
+	fn answered_prompt_scrolled_up_is_ignored() {
+		let mut output = lines(&["Overwrite config? [y/N]"]);
+		output.extend((0..PROMPT_WINDOW).map(|i| format!("compiling crate {i}")));
+		assert_eq!(status(&output, 1), AgentStatus::Running);
+	}
+
+	#[test]
+	fn blank_lines_do_not_push_prompt_out_of_window() {
+		let mut output = lines(&["Continue? (y/N)"]);
+		output.extend((0..PROMPT_WINDOW * 2).map(|_| "   ".to_string()));
+		assert_eq!(status(&output, 60), AgentStatus::NeedsInput);
+	}
+
+	#[test]
+	fn no_output_since_input_falls_back_to_age() {
+		// The caller passes only lines written after the last input; an answered
+		// prompt with nothing new leaves this empty
+		assert_eq!(status(&[], 2), AgentStatus::Running);
+		assert_eq!(status(&[], 60), AgentStatus::Idle);
+	}
+
+	#[test]
+	fn prose_mentioning_a_prompt_far_above_is_ignored() {
+		let mut output = lines(&["Would you like me to also update the docs? I'll do it anyway."]);
+		output.extend((0..PROMPT_WINDOW).map(|i| format!("⏺ Edit(src/file{i}.rs)")));
+		assert_eq!(status(&output, 3), AgentStatus::Running);
+	}
+
+	#[test]
+	fn thresholds_come_from_config() {
+		let mut overrides = HashMap::new();
+		overrides.insert(
+			"claude".to_string(),
+			AgentDetection {
+				running_secs: Some(60),
+				idle_secs: None,
+				health_check_secs: Some(600),
+			},
+		);
+		let detection = detection_for_agent("claude", &overrides);
+		assert_eq!(detection.running_threshold, Duration::from_secs(60));
+		assert_eq!(detection.idle_threshold, Duration::from_secs(30));
+		let codex = detection_for_agent("codex", &overrides);
+		assert_eq!(codex.running_threshold, Duration::from_secs(5));
+		assert_eq!(detection.health_check, Some(Duration::from_secs(600)));
+		assert_eq!(codex.health_check, None);
+	}
+
+	#[test]
+	fn quick_replies_for_option_menu() {
+		let detection = detection_for_agent("claude", &HashMap::new());
+		let output = lines(&[
+			"│ Do you want to proceed?                │",
+			"│ ❯ 1. Yes                               │",
+			"│   2. Yes, and don't ask again          │",
+			"│   3. No, and tell Claude what to do    │",
+		]);
+		let replies = quick_replies(&output, &detection);
+		let texts: Vec<&str> = replies.iter().map(|r| r.text.as_str()).collect();
+		assert_eq!(texts, ["1", "2", "3"]);
+		assert_eq!(replies[0].label, "1 Yes");
+	}
+
+	#[test]
+	fn quick_replies_for_yes_no_prompt() {
+		let detection = detection_for_agent("codex", &HashMap::new());
+		let replies = quick_replies(&lines(&["Apply patch? [y/N]"]), &detection);
+		assert_eq!(replies.iter().map(|r| r.text.as_str()).collect::<Vec<_>>(), ["y", "n"]);
+		assert!(quick_replies(&lines(&["All tests passed"]), &detection).is_empty());
+	}
+
+	#[test]
+	fn latest_checklist_is_the_newest_block() {
+		let output = lines(&["☒ Old plan", "☐ Old step", "", "⎿ ☒ Write parser", "  ☐ Add tests", "Working…"]);
+		assert_eq!(latest_checklist(&output), [(true, "Write parser".to_string()), (false, "Add tests".to_string())]);
+		assert_eq!(todo_progress(&output), Some((1, 2)));
+		assert_eq!(todo_progress(&lines(&["✓ Saved"])), None);
+	}
+}
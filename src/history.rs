@@ -0,0 +1,243 @@
+// Finished runs. Marking a session done archives what it did (task, timing,
+// outcome, changes, Process Log, last output) to ~/.swarm/history/ before the
+// session and its log are deleted, for the History view (O) and `swarm history`.
+// The same records feed the stats (s in the History view, `swarm stats`).
+
+use crate::model::AgentStatus;
+use anyhow::Result;
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Run {
+	pub name: String,
+	pub session: String,
+	pub agent: String,
+	pub model: Option<String>,
+	pub task: Option<String>, // task title
+	pub task_path: Option<PathBuf>,
+	pub group: Option<String>,
+	pub work_dir: Option<PathBuf>,
+	pub branch: Option<String>,
+	pub started: Option<i64>, // unix seconds; None for PTY sessions
+	pub ended: i64,
+	#[serde(default)]
+	pub blocked: Option<u64>, // seconds spent needing input, as timed by the TUI
+	pub status: AgentStatus, // last status seen before it was marked done
+	pub exit_code: Option<i32>,
+	pub outcome: String,
+	#[serde(default)]
+	pub diff: Vec<String>,
+	#[serde(default)]
+	pub timeline: Vec<String>,
+	#[serde(default)]
+	pub log_tail: Vec<String>,
+}
+
+impl Run {
+	pub fn duration(&self) -> Option<Duration> {
+		let secs = self.ended - self.started?;
+		Some(Duration::from_secs(secs.max(0) as u64))
+	}
+
+	pub fn succeeded(&self) -> bool {
+		self.outcome == "done"
+	}
+
+	/// Monday of the week it ended in
+	pub fn week(&self) -> NaiveDate {
+		let ended = chrono::DateTime::from_timestamp(self.ended, 0)
+			.unwrap_or_default()
+			.with_timezone(&chrono::Local)
+			.date_naive();
+		ended - chrono::Days::new(ended.weekday().num_days_from_monday() as u64)
+	}
+
+	/// Case-insensitive match on the task title or file, or the session name
+	pub fn matches(&self, query: &str) -> bool {
+		let query = query.to_lowercase();
+		[self.task.as_deref(), self.task_path.as_ref().and_then(|p| p.to_str()), Some(self.name.as_str())]
+			.into_iter()
+			.flatten()
+			.any(|s| s.to_lowercase().contains(&query))
+	}
+}
+
+/// "done", "failed (exit 1)", "stopped while running"
+pub fn outcome(status: AgentStatus, exit_code: Option<i32>) -> String {
+	match (exit_code, status) {
+		(Some(0), _) | (None, AgentStatus::Done) => "done".to_string(),
+		(Some(code), _) => format!("failed (exit {code})"),
+		(None, AgentStatus::Error) => "error".to_string(),
+		(None, status) => format!("stopped while {}", status.label()),
+	}
+}
+
+fn dir() -> Result<PathBuf> {
+	let dir = crate::config::base_dir()?.join("history");
+	fs::create_dir_all(&dir)?;
+	Ok(dir)
+}
+
+/// Write one run as history/<ended>-<name>.json
+pub fn save(run: &Run) -> Result<PathBuf> {
+	let ended = chrono::DateTime::from_timestamp(run.ended, 0)
+		.unwrap_or_default()
+		.with_timezone(&chrono::Local);
+	let path = dir()?.join(format!("{}-{}.json", ended.format("%Y%m%d-%H%M%S"), slug::slugify(&run.name)));
+	fs::write(&path, serde_json::to_string_pretty(run)?)?;
+	Ok(path)
+}
+
+/// Every archived run, newest first; unreadable files are skipped
+pub fn load() -> Vec<Run> {
+	let Ok(entries) = dir().and_then(|d| Ok(fs::read_dir(d)?)) else {
+		return Vec::new();
+	};
+	let mut runs: Vec<Run> = entries
+		.flatten()
+		.filter(|e| e.path().extension().is_some_and(|x| x == "json"))
+		.filter_map(|e| serde_json::from_str(&fs::read_to_string(e.path()).ok()?).ok())
+		.collect();
+	runs.sort_by_key(|r| std::cmp::Reverse(r.ended));
+	runs
+}
+
+/// Runs, successes and averages for one slice of the history
+#[derive(Debug, Clone, Default)]
+pub struct Tally {
+	pub runs: usize,
+	pub succeeded: usize,
+	durations: Vec<Duration>,
+	blocked: Vec<Duration>,
+}
+
+impl Tally {
+	fn add(&mut self, run: &Run) {
+		self.runs += 1;
+		self.succeeded += run.succeeded() as usize;
+		self.durations.extend(run.duration());
+		self.blocked.extend(run.blocked.map(Duration::from_secs));
+	}
+
+	/// Percent of runs that finished "done"
+	pub fn success_rate(&self) -> Option<f64> {
+		(self.runs > 0).then(|| self.succeeded as f64 * 100.0 / self.runs as f64)
+	}
+
+	pub fn avg_duration(&self) -> Option<Duration> {
+		average(&self.durations)
+	}
+
+	pub fn avg_blocked(&self) -> Option<Duration> {
+		average(&self.blocked)
+	}
+}
+
+fn average(values: &[Duration]) -> Option<Duration> {
+	(!values.is_empty()).then(|| values.iter().sum::<Duration>() / values.len() as u32)
+}
+
+pub struct Stats {
+	pub total: Tally,
+	/// The last `weeks` weeks by their Monday, oldest first, empty weeks included
+	pub weeks: Vec<(NaiveDate, Tally)>,
+	/// By "agent" or "agent (model)", most runs first
+	pub agents: Vec<(String, Tally)>,
+}
+
+pub fn stats(runs: &[Run], weeks: usize, today: NaiveDate) -> Stats {
+	let this_week = today - chrono::Days::new(today.weekday().num_days_from_monday() as u64);
+	let mut by_week: BTreeMap<NaiveDate, Tally> = (0..weeks as u64)
+		.map(|n| (this_week - chrono::Days::new(7 * n), Tally::default()))
+		.collect();
+	let mut by_agent: BTreeMap<String, Tally> = BTreeMap::new();
+	let mut total = Tally::default();
+	for run in runs {
+		total.add(run);
+		if let Some(week) = by_week.get_mut(&run.week()) {
+			week.add(run);
+		}
+		let agent = match &run.model {
+			Some(model) => format!("{} ({model})", run.agent),
+			None => run.agent.clone(),
+		};
+		by_agent.entry(agent).or_default().add(run);
+	}
+	let mut agents: Vec<(String, Tally)> = by_agent.into_iter().collect();
+	agents.sort_by_key(|(_, t)| std::cmp::Reverse(t.runs));
+	Stats {
+		total,
+		weeks: by_week.into_iter().collect(),
+		agents,
+	}
+}
+
+/// `swarm stats --csv weeks|agents|runs`
+pub fn csv(stats: &Stats, runs: &[Run], table: &str) -> String {
+	let minutes = |d: Option<Duration>| d.map(|d| format!("{:.1}", d.as_secs_f64() / 60.0)).unwrap_or_default();
+	let rate = |t: &Tally| t.success_rate().map(|r| format!("{r:.0}")).unwrap_or_default();
+	let mut rows = Vec::new();
+	match table {
+		"weeks" => {
+			rows.push("week,completed,runs,success_pct,avg_minutes,avg_blocked_minutes".to_string());
+			for (week, t) in &stats.weeks {
+				rows.push(format!(
+					"{week},{},{},{},{},{}",
+					t.succeeded,
+					t.runs,
+					rate(t),
+					minutes(t.avg_duration()),
+					minutes(t.avg_blocked())
+				));
+			}
+		}
+		"agents" => {
+			rows.push("agent,runs,succeeded,success_pct,avg_minutes,avg_blocked_minutes".to_string());
+			for (agent, t) in &stats.agents {
+				rows.push(format!(
+					"{},{},{},{},{},{}",
+					csv_field(agent),
+					t.runs,
+					t.succeeded,
+					rate(t),
+					minutes(t.avg_duration()),
+					minutes(t.avg_blocked())
+				));
+			}
+		}
+		_ => {
+			rows.push("ended,name,agent,model,task,outcome,minutes,blocked_minutes".to_string());
+			for run in runs {
+				let ended = chrono::DateTime::from_timestamp(run.ended, 0)
+					.unwrap_or_default()
+					.with_timezone(&chrono::Local);
+				rows.push(format!(
+					"{},{},{},{},{},{},{},{}",
+					ended.format("%Y-%m-%d %H:%M"),
+					csv_field(&run.name),
+					csv_field(&run.agent),
+					csv_field(run.model.as_deref().unwrap_or_default()),
+					csv_field(run.task.as_deref().unwrap_or_default()),
+					csv_field(&run.outcome),
+					minutes(run.duration()),
+					minutes(run.blocked.map(Duration::from_secs))
+				));
+			}
+		}
+	}
+	rows.join("\n") + "\n"
+}
+
+/// Quote fields with commas, quotes or newlines
+fn csv_field(value: &str) -> String {
+	if value.contains([',', '"', '\n', '\r']) {
+		format!("\"{}\"", value.replace('"', "\"\""))
+	} else {
+		value.to_string()
+	}
+}
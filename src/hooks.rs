@@ -0,0 +1,98 @@
+// User-defined shell commands run on session lifecycle events ([hooks] in
+// config and in a repo's .swarm.toml). They run in the session's working
+// directory with the session's details in SWARM_* env vars; output goes to
+// ~/.swarm/logs/hooks.log. on_create blocks the launch, the rest run in the
+// background.
+
+use anyhow::Result;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// What a hook is told about the session it runs for
+#[derive(Debug, Clone)]
+pub struct HookContext {
+	pub event: &'static str,
+	pub session: String,
+	pub name: String,
+	pub agent: String,
+	pub status: String,
+	pub work_dir: Option<PathBuf>,
+	pub task: Option<PathBuf>,
+}
+
+impl HookContext {
+	fn env(&self) -> Vec<(&'static str, String)> {
+		let mut env = vec![
+			("SWARM_EVENT", self.event.to_string()),
+			("SWARM_SESSION", self.session.clone()),
+			("SWARM_NAME", self.name.clone()),
+			("SWARM_AGENT", self.agent.clone()),
+			("SWARM_STATUS", self.status.clone()),
+		];
+		if let Some(dir) = &self.work_dir {
+			env.push(("SWARM_WORK_DIR", dir.display().to_string()));
+		}
+		if let Some(task) = &self.task {
+			env.push(("SWARM_TASK", task.display().to_string()));
+		}
+		env
+	}
+}
+
+/// Run `command` for `ctx` in the background; empty commands are ignored
+pub fn run(command: &str, ctx: HookContext) {
+	if command.trim().is_empty() {
+		return;
+	}
+	let command = command.to_string();
+	std::thread::spawn(move || {
+		execute(&command, &ctx);
+	});
+}
+
+/// Run `command` and wait for it, failing if it exits non-zero
+pub fn run_blocking(command: &str, ctx: &HookContext) -> Result<()> {
+	if command.trim().is_empty() {
+		return Ok(());
+	}
+	match execute(command, ctx) {
+		Some(0) => Ok(()),
+		code => Err(anyhow::anyhow!(
+			"on_{} hook `{}` failed{} - see ~/.swarm/logs/hooks.log",
+			ctx.event,
+			command,
+			code.map(|c| format!(" (exit {c})")).unwrap_or_default()
+		)),
+	}
+}
+
+/// Run and log; returns the exit code (None if it didn't start or was signalled)
+fn execute(command: &str, ctx: &HookContext) -> Option<i32> {
+	let mut cmd = Command::new("sh");
+	cmd.args(["-c", command]).envs(ctx.env()).stdin(Stdio::null());
+	if let Some(dir) = ctx.work_dir.as_ref().filter(|d| d.is_dir()) {
+		cmd.current_dir(dir);
+	}
+	let result = cmd.output();
+	let code = result.as_ref().ok().and_then(|o| o.status.code());
+	let log_path = crate::config::base_dir().map(|d| d.join("logs").join("hooks.log"));
+	let Ok(mut log) = log_path.and_then(|p| Ok(std::fs::OpenOptions::new().create(true).append(true).open(p)?)) else {
+		return code;
+	};
+	let stamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+	let _ = match result {
+		Ok(output) => {
+			let _ = writeln!(
+				log,
+				"[{stamp}] {} {}: `{command}` exited {}",
+				ctx.event,
+				ctx.session,
+				code.map_or("by signal".to_string(), |c| c.to_string())
+			);
+			log.write_all(&output.stdout).and_then(|_| log.write_all(&output.stderr))
+		}
+		Err(e) => writeln!(log, "[{stamp}] {} {}: `{command}` failed to start: {e}", ctx.event, ctx.session),
+	};
+	code
+}
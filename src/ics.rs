@@ -0,0 +1,84 @@
+// Task due dates as an iCalendar feed (`swarm task ics`, /calendar.ics from
+// `swarm serve`), so deadlines set in swarm show up in a real calendar. Dated
+// tasks become all-day events; tasks due at a time become a short event at
+// that time, with an alarm `[notifications] due_reminder` ahead.
+
+use crate::model::Due;
+use std::time::Duration;
+
+/// One task on the calendar
+pub struct Event {
+	/// Stable across exports so calendar apps update instead of duplicating
+	pub uid: String,
+	pub summary: String,
+	pub description: String,
+	pub due: Due,
+}
+
+/// A whole VCALENDAR; `alarm` adds a reminder that long before timed events
+pub fn calendar(events: &[Event], alarm: Option<Duration>) -> String {
+	let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+	let mut lines = vec![
+		"BEGIN:VCALENDAR".to_string(),
+		"VERSION:2.0".to_string(),
+		"PRODID:-//whopio//swarm//EN".to_string(),
+		"CALSCALE:GREGORIAN".to_string(),
+		"X-WR-CALNAME:swarm tasks".to_string(),
+	];
+	for event in events {
+		lines.push("BEGIN:VEVENT".to_string());
+		lines.push(format!("UID:{}", escape(&event.uid)));
+		lines.push(format!("DTSTAMP:{stamp}"));
+		match event.due.at() {
+			// Floating local time: the deadline is wherever you are
+			Some(at) => {
+				lines.push(format!("DTSTART:{}", at.format("%Y%m%dT%H%M%S")));
+				lines.push("DURATION:PT15M".to_string());
+			}
+			None => {
+				lines.push(format!("DTSTART;VALUE=DATE:{}", event.due.date.format("%Y%m%d")));
+				let next = event.due.date.succ_opt().unwrap_or(event.due.date);
+				lines.push(format!("DTEND;VALUE=DATE:{}", next.format("%Y%m%d")));
+			}
+		}
+		lines.push(format!("SUMMARY:{}", escape(&event.summary)));
+		if !event.description.is_empty() {
+			lines.push(format!("DESCRIPTION:{}", escape(&event.description)));
+		}
+		if let (Some(lead), Some(_)) = (alarm, event.due.time) {
+			lines.push("BEGIN:VALARM".to_string());
+			lines.push("ACTION:DISPLAY".to_string());
+			lines.push(format!("DESCRIPTION:{}", escape(&event.summary)));
+			lines.push(format!("TRIGGER:-PT{}M", lead.as_secs().div_ceil(60)));
+			lines.push("END:VALARM".to_string());
+		}
+		lines.push("END:VEVENT".to_string());
+	}
+	lines.push("END:VCALENDAR".to_string());
+	lines.iter().map(|l| fold(l)).collect::<Vec<_>>().join("\r\n") + "\r\n"
+}
+
+/// TEXT values escape backslashes, separators and newlines
+fn escape(text: &str) -> String {
+	text.replace('\\', "\\\\")
+		.replace(';', "\\;")
+		.replace(',', "\\,")
+		.replace('\r', "")
+		.replace('\n', "\\n")
+}
+
+/// Lines longer than 75 bytes continue on the next line after a space,
+/// never splitting a UTF-8 character
+fn fold(line: &str) -> String {
+	let mut out = String::with_capacity(line.len() + line.len() / 74 * 3);
+	let mut width = 0;
+	for c in line.chars() {
+		if width + c.len_utf8() > 75 {
+			out.push_str("\r\n ");
+			width = 1;
+		}
+		width += c.len_utf8();
+		out.push(c);
+	}
+	out
+}
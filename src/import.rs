@@ -0,0 +1,205 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+/// A task parsed from meeting notes or a spreadsheet export
+#[derive(Debug, Clone)]
+pub struct ImportedTask {
+	pub title: String,
+	pub body: String,
+	pub due: Option<String>,
+	pub notify: Option<String>,
+}
+
+/// Parse a markdown or CSV file into tasks (CSV chosen by `.csv` extension)
+pub fn parse_file(path: &Path) -> Result<Vec<ImportedTask>> {
+	let content = fs::read_to_string(path)?;
+	let is_csv = path
+		.extension()
+		.map(|e| e.eq_ignore_ascii_case("csv"))
+		.unwrap_or(false);
+	if is_csv {
+		parse_csv(&content)
+	} else {
+		Ok(parse_markdown(&content))
+	}
+}
+
+/// One task per top-level bullet (nested bullets become its body).
+/// Files without bullets fall back to one task per heading.
+pub fn parse_markdown(content: &str) -> Vec<ImportedTask> {
+	let has_bullets = content.lines().any(|l| bullet_text(l).is_some());
+	let mut tasks: Vec<ImportedTask> = Vec::new();
+
+	for line in content.lines() {
+		let indent = line.len() - line.trim_start().len();
+		let trimmed = line.trim();
+		if trimmed.is_empty() {
+			continue;
+		}
+		if has_bullets {
+			if let Some(text) = bullet_text(line) {
+				if indent == 0 {
+					tasks.push(new_task(text));
+					continue;
+				}
+			}
+			// Indented lines belong to the previous top-level bullet
+			if indent > 0 {
+				if let Some(last) = tasks.last_mut() {
+					last.body.push_str(trimmed);
+					last.body.push('\n');
+				}
+			}
+		} else if trimmed.starts_with('#') {
+			let text = trimmed.trim_start_matches('#').trim();
+			if !text.is_empty() {
+				tasks.push(new_task(text));
+			}
+		} else if let Some(last) = tasks.last_mut() {
+			last.body.push_str(trimmed);
+			last.body.push('\n');
+		}
+	}
+
+	tasks.retain(|t| !t.title.is_empty());
+	tasks
+}
+
+fn new_task(title: &str) -> ImportedTask {
+	ImportedTask {
+		title: title.to_string(),
+		body: String::new(),
+		due: None,
+		notify: None,
+	}
+}
+
+/// Text of a markdown bullet (`-`, `*`, `+`, `1.`), without any `[ ]` checkbox
+fn bullet_text(line: &str) -> Option<&str> {
+	let trimmed = line.trim_start();
+	let rest = if let Some(rest) = trimmed
+		.strip_prefix("- ")
+		.or_else(|| trimmed.strip_prefix("* "))
+		.or_else(|| trimmed.strip_prefix("+ "))
+	{
+		rest
+	} else {
+		let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+		if digits == 0 {
+			return None;
+		}
+		trimmed[digits..].strip_prefix(". ")?
+	};
+	let rest = rest
+		.strip_prefix("[ ] ")
+		.or_else(|| rest.strip_prefix("[x] "))
+		.or_else(|| rest.strip_prefix("[X] "))
+		.unwrap_or(rest);
+	Some(rest.trim())
+}
+
+/// CSV with a header row. Recognized columns (case-insensitive):
+/// title/task/summary, due, notify/owner, body/description/notes
+pub fn parse_csv(content: &str) -> Result<Vec<ImportedTask>> {
+	let mut rows = content.lines().filter(|l| !l.trim().is_empty());
+	let header = rows
+		.next()
+		.ok_or_else(|| anyhow::anyhow!("CSV file is empty"))?;
+	let columns: Vec<String> = split_csv_line(header)
+		.into_iter()
+		.map(|c| c.trim().to_lowercase())
+		.collect();
+	let find = |names: &[&str]| columns.iter().position(|c| names.contains(&c.as_str()));
+	let title_col = find(&["title", "task", "summary"])
+		.ok_or_else(|| anyhow::anyhow!("CSV needs a title, task, or summary column"))?;
+	let due_col = find(&["due", "due_date", "due date"]);
+	let notify_col = find(&["notify", "owner", "assignee"]);
+	let body_col = find(&["body", "description", "notes"]);
+
+	let mut tasks = Vec::new();
+	for row in rows {
+		let fields = split_csv_line(row);
+		let get = |col: Option<usize>| {
+			col.and_then(|i| fields.get(i))
+				.map(|f| f.trim().to_string())
+				.filter(|f| !f.is_empty())
+		};
+		let Some(title) = get(Some(title_col)) else {
+			continue;
+		};
+		tasks.push(ImportedTask {
+			title,
+			body: get(body_col).unwrap_or_default(),
+			due: get(due_col),
+			notify: get(notify_col),
+		});
+	}
+	Ok(tasks)
+}
+
+/// Split one CSV line, honoring double-quoted fields and `""` escapes
+fn split_csv_line(line: &str) -> Vec<String> {
+	let mut fields = Vec::new();
+	let mut current = String::new();
+	let mut in_quotes = false;
+	let mut chars = line.chars().peekable();
+	while let Some(c) = chars.next() {
+		match c {
+			'"' if in_quotes && chars.peek() == Some(&'"') => {
+				current.push('"');
+				chars.next();
+			}
+			'"' => in_quotes = !in_quotes,
+			',' if !in_quotes => fields.push(std::mem::take(&mut current)),
+			_ => current.push(c),
+		}
+	}
+	fields.push(current);
+	fields
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn markdown_nested_bullets_become_the_body() {
+		let tasks = parse_markdown(
+			"# Standup\n\n- [ ] Fix login redirect\n  - happens on Safari\n    * only after logout\n- [x] Ship release notes\n1. Update the changelog\n",
+		);
+		let titles: Vec<&str> = tasks.iter().map(|t| t.title.as_str()).collect();
+		assert_eq!(titles, ["Fix login redirect", "Ship release notes", "Update the changelog"]);
+		assert_eq!(tasks[0].body, "- happens on Safari\n* only after logout\n");
+		assert!(tasks[1].body.is_empty());
+	}
+
+	#[test]
+	fn markdown_without_bullets_splits_on_headings() {
+		let tasks = parse_markdown("intro line\n## Migrate billing\nMove to the new API\n\n### \n## Audit logs\n");
+		let titles: Vec<&str> = tasks.iter().map(|t| t.title.as_str()).collect();
+		assert_eq!(titles, ["Migrate billing", "Audit logs"]);
+		assert_eq!(tasks[0].body, "Move to the new API\n");
+	}
+
+	#[test]
+	fn csv_quoted_commas_and_escaped_quotes() {
+		let csv = "Summary,Due Date,Owner,Notes\n\"Fix login, then logout\",2025-03-14,alice,\"Says \"\"works for me\"\"\"\n,2025-03-15,bob,no title\nPlain task,,,\n";
+		let tasks = parse_csv(csv).unwrap();
+		assert_eq!(tasks.len(), 2);
+		assert_eq!(tasks[0].title, "Fix login, then logout");
+		assert_eq!(tasks[0].due.as_deref(), Some("2025-03-14"));
+		assert_eq!(tasks[0].notify.as_deref(), Some("alice"));
+		assert_eq!(tasks[0].body, "Says \"works for me\"");
+		assert_eq!(tasks[1].title, "Plain task");
+		assert_eq!(tasks[1].due, None);
+		assert!(tasks[1].body.is_empty());
+	}
+
+	#[test]
+	fn csv_needs_a_title_column() {
+		assert!(parse_csv("name,due\nx,2025-03-14\n").is_err());
+		assert!(parse_csv("\n\n").is_err());
+		assert_eq!(split_csv_line("a,\"b,c\",\"\"\"\",d"), ["a", "b,c", "\"", "d"]);
+	}
+}
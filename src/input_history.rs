@@ -0,0 +1,89 @@
+// Messages sent from the send-input modal (and prompts given to `swarm new`),
+// kept in ~/.swarm/input_history.jsonl so Up/Down can recall them: the session's
+// own messages first, then ones sent to any other agent. The newest one per
+// session is shown in the Details pane.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Older entries are dropped once the file grows past this
+const MAX_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+	session: String,
+	text: String,
+	#[serde(default)]
+	at: Option<i64>, // unix seconds; missing in older files
+}
+
+fn history_path() -> Result<PathBuf> {
+	Ok(crate::config::base_dir()?.join("input_history.jsonl"))
+}
+
+fn load() -> Vec<Entry> {
+	let Ok(content) = history_path().and_then(|p| Ok(fs::read_to_string(p)?)) else {
+		return Vec::new();
+	};
+	content.lines().filter_map(|l| serde_json::from_str(l).ok()).collect()
+}
+
+/// Remember a sent message
+pub fn record(session: &str, text: &str) -> Result<()> {
+	if text.trim().is_empty() {
+		return Ok(());
+	}
+	let path = history_path()?;
+	let entry = Entry {
+		session: session.to_string(),
+		text: text.to_string(),
+		at: Some(chrono::Local::now().timestamp()),
+	};
+	let mut entries = load();
+	if entries.last().is_some_and(|e| e.session == entry.session && e.text == entry.text) {
+		return Ok(());
+	}
+	if entries.len() >= MAX_ENTRIES * 2 {
+		// Compact now and then instead of rewriting on every send
+		entries.push(entry);
+		let keep = &entries[entries.len() - MAX_ENTRIES..];
+		let body: String = keep
+			.iter()
+			.filter_map(|e| serde_json::to_string(e).ok())
+			.map(|l| l + "\n")
+			.collect();
+		let tmp = path.with_extension("jsonl.tmp");
+		fs::write(&tmp, body)?;
+		fs::rename(&tmp, &path)?;
+		return Ok(());
+	}
+	let mut file = fs::OpenOptions::new().create(true).append(true).open(&path)?;
+	writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+	Ok(())
+}
+
+/// The last message sent to `session` and when (unix seconds)
+pub fn last(session: &str) -> Option<(String, Option<i64>)> {
+	load()
+		.into_iter()
+		.rev()
+		.find(|e| e.session == session)
+		.map(|e| (e.text, e.at))
+}
+
+/// Messages to cycle through for `session`, most recent first: its own, then the rest
+pub fn recall(session: &str) -> Vec<String> {
+	let entries = load();
+	let own = entries.iter().rev().filter(|e| e.session == session);
+	let others = entries.iter().rev().filter(|e| e.session != session);
+	let mut out: Vec<String> = Vec::new();
+	for entry in own.chain(others) {
+		if !out.contains(&entry.text) {
+			out.push(entry.text.clone());
+		}
+	}
+	out
+}
@@ -0,0 +1,224 @@
+// "Land this" for a finished worktree session: rebase its branch on the latest
+// base, run the [land] checks, push, and open (or update) the PR. Runs on a
+// background thread and reports each step over a channel for the progress modal.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StepState {
+	Pending,
+	Running,
+	Ok,
+	Failed,
+}
+
+#[derive(Debug, Clone)]
+pub struct Step {
+	pub name: String,
+	pub state: StepState,
+	pub detail: String,
+}
+
+/// Progress for step `step`
+#[derive(Debug, Clone)]
+pub struct Update {
+	pub step: usize,
+	pub state: StepState,
+	pub detail: String,
+}
+
+/// What will happen, in order (the modal shows these before any run)
+pub fn steps(base: &str, checks: &[String]) -> Vec<Step> {
+	let mut names = vec![
+		"Check for uncommitted changes".to_string(),
+		format!("Rebase on latest {base}"),
+	];
+	names.extend(checks.iter().map(|c| format!("Run `{c}`")));
+	names.push("Push branch".to_string());
+	names.push("Open or update PR".to_string());
+	names
+		.into_iter()
+		.map(|name| Step {
+			name,
+			state: StepState::Pending,
+			detail: String::new(),
+		})
+		.collect()
+}
+
+/// Stops a run (Esc in the modal): kills the command in flight and starts no more
+#[derive(Debug, Clone, Default)]
+pub struct Cancel(Arc<Mutex<CancelState>>);
+
+#[derive(Debug, Default)]
+struct CancelState {
+	cancelled: bool,
+	running: Option<u32>,
+}
+
+impl Cancel {
+	pub fn cancel(&self) {
+		let mut state = self.0.lock().unwrap_or_else(|e| e.into_inner());
+		state.cancelled = true;
+		if let Some(pid) = state.running {
+			crate::procs::kill_tree(pid);
+		}
+	}
+
+	/// Run `cmd` to completion unless cancelled first or meanwhile
+	fn output(&self, cmd: &mut Command) -> std::io::Result<std::process::Output> {
+		let child = {
+			let mut state = self.0.lock().unwrap_or_else(|e| e.into_inner());
+			if state.cancelled {
+				return Err(std::io::Error::new(std::io::ErrorKind::Interrupted, "cancelled"));
+			}
+			let child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+			state.running = Some(child.id());
+			child
+		};
+		let output = child.wait_with_output();
+		self.0.lock().unwrap_or_else(|e| e.into_inner()).running = None;
+		output
+	}
+}
+
+/// Start landing `dir`'s branch; stops at the first failing step
+pub fn spawn(dir: PathBuf, base: String, checks: Vec<String>) -> (Receiver<Update>, Cancel) {
+	let (tx, rx) = std::sync::mpsc::channel();
+	let cancel = Cancel::default();
+	let handle = cancel.clone();
+	std::thread::spawn(move || {
+		let _ = run(&dir, &base, &checks, &tx, &cancel);
+	});
+	(rx, handle)
+}
+
+/// Sends the state of the current step
+struct Progress<'a> {
+	tx: &'a Sender<Update>,
+	step: usize,
+}
+
+impl Progress<'_> {
+	fn send(&self, state: StepState, detail: String) {
+		let _ = self.tx.send(Update {
+			step: self.step,
+			state,
+			detail,
+		});
+	}
+
+	/// Move on to the next step
+	fn next(&mut self) {
+		self.step += 1;
+		self.send(StepState::Running, String::new());
+	}
+
+	/// Record how the current step went; None stops the run
+	fn finish(&self, result: Result<String, String>) -> Option<()> {
+		match result {
+			Ok(detail) => {
+				self.send(StepState::Ok, detail);
+				Some(())
+			}
+			Err(e) => {
+				self.send(StepState::Failed, e);
+				None
+			}
+		}
+	}
+}
+
+fn run(dir: &Path, base: &str, checks: &[String], tx: &Sender<Update>, cancel: &Cancel) -> Option<()> {
+	let mut progress = Progress { tx, step: 0 };
+	progress.send(StepState::Running, String::new());
+	progress.finish(git(cancel, dir, &["status", "--porcelain"]).and_then(|out| {
+		if out.is_empty() {
+			Ok("clean".to_string())
+		} else {
+			Err(format!("{} uncommitted file(s) - commit or stash them first", out.lines().count()))
+		}
+	}))?;
+
+	progress.next();
+	progress.finish(rebase(cancel, dir, base))?;
+
+	for check in checks {
+		progress.next();
+		progress.finish(shell(cancel, dir, check).map(|_| "passed".to_string()))?;
+	}
+
+	progress.next();
+	progress.finish(
+		git(cancel, dir, &["push", "--force-with-lease", "-u", "origin", "HEAD"])
+			.map(|_| git(cancel, dir, &["branch", "--show-current"]).unwrap_or_default()),
+	)?;
+
+	progress.next();
+	progress.finish(pull_request(cancel, dir, base))
+}
+
+fn rebase(cancel: &Cancel, dir: &Path, base: &str) -> Result<String, String> {
+	if let Some(remote_branch) = base.strip_prefix("origin/") {
+		git(cancel, dir, &["fetch", "origin", remote_branch])?;
+	}
+	if let Err(e) = git(cancel, dir, &["rebase", base]) {
+		// Also after a cancel, which refuses to start anything else
+		let _ = Command::new("git").arg("-C").arg(dir).args(["rebase", "--abort"]).output();
+		return Err(format!("{e} (rebase aborted - resolve by hand)"));
+	}
+	let ahead = git(cancel, dir, &["rev-list", "--count", &format!("{base}..HEAD")]).unwrap_or_default();
+	Ok(format!("{ahead} commit(s) ahead"))
+}
+
+/// Pushing already updated an open PR; otherwise (none yet, or the branch's
+/// last one was closed or merged) open one
+fn pull_request(cancel: &Cancel, dir: &Path, base: &str) -> Result<String, String> {
+	if let Ok(view) = gh(cancel, dir, &["pr", "view", "--json", "state,url", "--jq", ".state + \" \" + .url"]) {
+		if let Some(url) = view.strip_prefix("OPEN ") {
+			return Ok(format!("updated {url}"));
+		}
+	}
+	let target = base.strip_prefix("origin/").unwrap_or(base);
+	gh(cancel, dir, &["pr", "create", "--fill", "--base", target])
+		.map(|out| format!("opened {}", out.lines().last().unwrap_or_default()))
+}
+
+fn git(cancel: &Cancel, dir: &Path, args: &[&str]) -> Result<String, String> {
+	output(cancel, Command::new("git").arg("-C").arg(dir).args(args), "git")
+}
+
+fn gh(cancel: &Cancel, dir: &Path, args: &[&str]) -> Result<String, String> {
+	output(cancel, Command::new("gh").args(args).current_dir(dir), "gh")
+}
+
+fn shell(cancel: &Cancel, dir: &Path, command: &str) -> Result<(), String> {
+	output(cancel, Command::new("sh").args(["-c", command]).current_dir(dir), "sh").map(|_| ())
+}
+
+/// stdout on success, else the last line of output as the error
+fn output(cancel: &Cancel, cmd: &mut Command, program: &str) -> Result<String, String> {
+	let out = cancel
+		.output(cmd.stdin(Stdio::null()))
+		.map_err(|e| format!("failed to run {program}: {e}"))?;
+	let stdout = String::from_utf8_lossy(&out.stdout).trim().to_string();
+	if out.status.success() {
+		return Ok(stdout);
+	}
+	let stderr = String::from_utf8_lossy(&out.stderr);
+	let last = stdout
+		.lines()
+		.chain(stderr.lines())
+		.rev()
+		.find(|l| !l.trim().is_empty())
+		.unwrap_or("")
+		.trim()
+		.to_string();
+	Err(match out.status.code() {
+		Some(code) if last.is_empty() => format!("exit {code}"),
+		_ => last,
+	})
+}
@@ -1,10 +1,16 @@
 use anyhow::Result;
 use std::collections::VecDeque;
 use std::fs::File;
-use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
 use std::path::Path;
+use std::time::Duration;
 
 pub fn tail_lines(path: &Path, max_lines: usize) -> Result<Vec<String>> {
+	tail_lines_from(path, 0, max_lines)
+}
+
+/// Like `tail_lines`, but only output written after byte offset `start`
+pub fn tail_lines_from(path: &Path, start: u64, max_lines: usize) -> Result<Vec<String>> {
 	if !path.exists() {
 		return Ok(vec![]);
 	}
@@ -12,36 +18,34 @@ pub fn tail_lines(path: &Path, max_lines: usize) -> Result<Vec<String>> {
 
 	// Seek to end and read backwards to only process tail of file
 	let file_size = file.metadata()?.len();
-	if file_size == 0 {
+	if file_size <= start {
 		return Ok(vec![]);
 	}
 
 	// Read last ~64KB max (enough for most previews)
-	let read_size = std::cmp::min(file_size, 65536);
+	let read_size = std::cmp::min(file_size - start, 65536);
 	let start_pos = file_size.saturating_sub(read_size);
 	file.seek(SeekFrom::Start(start_pos))?;
 
 	let reader = BufReader::new(file);
 	let mut buf = VecDeque::with_capacity(max_lines);
 
-	for line in reader.lines() {
-		if let Ok(line) = line {
-			for piece in split_cr_lines(&line) {
-				// Keep only the latest segment for carriage-return updates to avoid flooding.
-				let segment = if piece.contains('\r') {
-					piece.rsplit('\r').next().unwrap_or(piece)
-				} else {
-					piece
-				};
-				if buf.len() == max_lines {
-					buf.pop_front();
-				}
-				let stripped = strip_ansi_fast(segment);
-				if stripped.is_empty() {
-					continue;
-				}
-				buf.push_back(stripped);
+	for line in reader.lines().map_while(Result::ok) {
+		for piece in split_cr_lines(&line) {
+			// Keep only the latest segment for carriage-return updates to avoid flooding.
+			let segment = if piece.contains('\r') {
+				piece.rsplit('\r').next().unwrap_or(piece)
+			} else {
+				piece
+			};
+			if buf.len() == max_lines {
+				buf.pop_front();
+			}
+			let stripped = strip_ansi_fast(segment);
+			if stripped.is_empty() {
+				continue;
 			}
+			buf.push_back(stripped);
 		}
 	}
 	Ok(buf.into_iter().collect())
@@ -60,7 +64,7 @@ fn strip_ansi_fast(input: &str) -> String {
 				// Skip parameter bytes (0x30-0x3F) and intermediate bytes (0x20-0x2F)
 				// until final byte (0x40-0x7E)
 				while let Some(&next) = chars.peek() {
-					if next >= '@' && next <= '~' {
+					if ('@'..='~').contains(&next) {
 						chars.next(); // consume final byte
 						break;
 					}
@@ -94,3 +98,38 @@ fn split_cr_lines(input: &str) -> Vec<&str> {
 		vec![input]
 	}
 }
+
+/// Play back a piped session log to stdout.
+/// Lines are written with `delay` between them (ANSI preserved so colors replay),
+/// or a screenful at a time when `step` is set (Enter for next page, q to quit).
+pub fn replay(path: &Path, delay: Duration, step: bool) -> Result<()> {
+	let bytes = std::fs::read(path)?;
+	let page_size = crossterm::terminal::size()
+		.map(|(_, rows)| rows.saturating_sub(1).max(1) as usize)
+		.unwrap_or(40);
+	let mut out = std::io::stdout();
+	let stdin = std::io::stdin();
+
+	for (idx, line) in bytes.split(|b| *b == b'\n').enumerate() {
+		out.write_all(line)?;
+		out.write_all(b"\r\n")?;
+		out.flush()?;
+		if step {
+			if (idx + 1) % page_size == 0 {
+				out.write_all(b"\x1b[0m-- more (Enter: next page, q: quit) --")?;
+				out.flush()?;
+				let mut answer = String::new();
+				stdin.lock().read_line(&mut answer)?;
+				if answer.trim().eq_ignore_ascii_case("q") {
+					break;
+				}
+			}
+		} else if !delay.is_zero() {
+			std::thread::sleep(delay);
+		}
+	}
+	// Reset any color state left over from the log
+	out.write_all(b"\x1b[0m\r\n")?;
+	out.flush()?;
+	Ok(())
+}
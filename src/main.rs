@@ -1,23 +1,50 @@
+mod claude_session;
+mod clipboard;
 mod config;
+mod conflicts;
+mod console;
+mod dates;
 mod detection;
+mod history;
+mod hooks;
+mod ics;
+mod import;
+mod input_history;
+mod land;
 mod logs;
 mod model;
+mod net;
 mod notify;
+mod obsidian;
+mod pipeline;
+mod procs;
+mod project;
+mod pty;
+mod serve;
+mod task_sync;
+mod team;
+mod text;
 mod tmux;
 
 use ansi_to_tui::IntoText as _;
 use anyhow::{Context, Result};
 use chrono::{Datelike, Local, NaiveDate, Timelike};
 use clap::{Parser, Subcommand};
-use config::{Config, session_store_dir, snapshots_dir};
+use config::{Config, session_store_dir, snapshots_dir, status_cache_path};
 use crossterm::{
-	event::{self, Event, KeyCode, KeyEventKind},
+	event::{
+		self, DisableBracketedPaste, DisableFocusChange, EnableBracketedPaste, EnableFocusChange, Event, KeyCode,
+		KeyEventKind, KeyModifiers,
+	},
 	execute,
 	terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use detection::{detect_status, detection_for_agent};
+use detection::{DetectionConfig, QuickReply, detect_status, detection_for_agent, latest_activity, quick_replies, todo_progress};
 use logs::tail_lines;
-use model::{AgentSession, AgentStatus, DailyEntry, TaskEntry, TaskInfo};
+use model::{
+	AgentSession, AgentStatus, SessionLimits, DailyEntry, Due, FleetSnapshot, StageInfo, TaskEntry, TaskInfo, TeamSession,
+	TeamStatus, TeamTask, VariantInfo,
+};
 use ratatui::{
 	prelude::*,
 	text::{Line, Text},
@@ -194,18 +221,29 @@ fn get_cumulative_release_notes(current: &str, releases: &[GitHubRelease]) -> Op
 
 /// Check for updates and return the latest version if newer
 /// Returns (version, download_url, release_notes)
-fn check_for_update() -> Result<Option<(String, String, Option<String>)>> {
+fn check_for_update(updates: &config::Updates) -> Result<Option<(String, String, Option<String>)>> {
+	if net::offline() {
+		anyhow::bail!("offline (--offline, or no connection)");
+	}
+	// reqwest picks up HTTPS_PROXY / NO_PROXY from the environment
 	let client = reqwest::blocking::Client::builder()
 		.user_agent("swarm-updater")
 		.timeout(Duration::from_secs(10))
 		.build()?;
 
 	// Fetch recent releases (not just latest) for cumulative changelog
-	let url = format!(
-		"https://api.github.com/repos/{}/releases?per_page=10",
-		GITHUB_REPO
-	);
-	let response = client.get(&url).send()?;
+	let base = match updates.releases_url.trim() {
+		"" => format!("https://api.github.com/repos/{}/releases", GITHUB_REPO),
+		url => url.to_string(),
+	};
+	let mut request = client.get(&base).query(&[("per_page", "10")]);
+	// Authenticated requests get GitHub's higher rate limit (and pass mirrors that refuse anonymous ones)
+	if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+		if !token.trim().is_empty() {
+			request = request.bearer_auth(token.trim());
+		}
+	}
+	let response = request.send()?;
 
 	if !response.status().is_success() {
 		return Ok(None);
@@ -251,11 +289,35 @@ fn check_for_update() -> Result<Option<(String, String, Option<String>)>> {
 	Ok(None)
 }
 
+/// Whether this binary lives in a Homebrew Cellar; brew links it into bin/, and
+/// replacing the file underneath it leaves brew confused about what's installed
+fn installed_with_homebrew() -> bool {
+	std::env::current_exe()
+		.and_then(|p| p.canonicalize())
+		.is_ok_and(|p| p.components().any(|c| c.as_os_str() == "Cellar"))
+}
+
 /// Check for updates and install if available
-fn check_and_install_update() -> Result<()> {
+fn check_and_install_update(updates: &config::Updates) -> Result<()> {
 	println!("Checking for updates...");
 
-	match check_for_update()? {
+	match check_for_update(updates)? {
+		Some((version, _, _)) if installed_with_homebrew() => {
+			println!("New version available: v{} (current: v{})", version, CURRENT_VERSION);
+			if updates.homebrew == config::Homebrew::Skip {
+				println!("swarm was installed with Homebrew - update it with `brew upgrade swarm`");
+				return Ok(());
+			}
+			println!("Installed with Homebrew, running `brew upgrade swarm`...");
+			let status = Command::new("brew")
+				.args(["upgrade", "swarm"])
+				.status()
+				.context("failed to run brew")?;
+			if !status.success() {
+				anyhow::bail!("brew upgrade swarm failed");
+			}
+			println!("✓ Updated! Restart swarm to use the new version.");
+		}
 		Some((version, url, release_notes)) => {
 			println!("New version available: v{} (current: v{})", version, CURRENT_VERSION);
 			println!("Downloading update...");
@@ -307,7 +369,7 @@ fn check_and_install_update() -> Result<()> {
 
 /// Auto-update on startup (runs in background, once per day)
 /// Returns Some((version, release_notes)) if we just updated on a previous run
-fn auto_update_on_startup() -> Option<(String, Option<String>)> {
+fn auto_update_on_startup(updates: &config::Updates) -> Option<(String, Option<String>)> {
 	let swarm_dir = dirs::home_dir()?.join(".swarm");
 	let just_updated_file = swarm_dir.join(".just-updated");
 	let update_notes_file = swarm_dir.join(".update-notes");
@@ -331,11 +393,32 @@ fn auto_update_on_startup() -> Option<(String, Option<String>)> {
 	}
 
 	// Check and auto-update in background thread
+	let updates = updates.clone();
 	std::thread::spawn(move || {
+		// Offline doesn't count as today's check, so it runs again once back online
+		if net::offline() {
+			return;
+		}
 		let _ = fs::create_dir_all(&swarm_dir);
 		let _ = fs::write(&last_check_file, "");
 
-		if let Ok(Some((version, url, release_notes))) = check_for_update() {
+		if let Ok(Some((version, url, release_notes))) = check_for_update(&updates) {
+			if installed_with_homebrew() {
+				let upgraded = updates.homebrew != config::Homebrew::Skip
+					&& Command::new("brew")
+						.args(["upgrade", "swarm"])
+						.stdout(std::process::Stdio::null())
+						.stderr(std::process::Stdio::null())
+						.status()
+						.is_ok_and(|s| s.success());
+				if upgraded {
+					let _ = fs::write(&just_updated_file, format!("v{}", version));
+					if let Some(notes) = release_notes {
+						let _ = fs::write(swarm_dir.join(".update-notes"), notes);
+					}
+				}
+				return;
+			}
 			// Download update
 			let client = reqwest::blocking::Client::builder()
 				.user_agent("swarm-updater")
@@ -379,18 +462,73 @@ fn auto_update_on_startup() -> Option<(String, Option<String>)> {
 struct Cli {
 	#[command(subcommand)]
 	command: Option<Commands>,
+	/// Diagnostics written to ~/.swarm/trace/ (default: $SWARM_LOG, else warn)
+	#[arg(long, global = true, value_name = "LEVEL", value_parser = ["off", "error", "warn", "info", "debug", "trace"])]
+	log_level: Option<String>,
+	/// Stay off the network: no update check, PR lookups, team publishing or push (also SWARM_OFFLINE=1)
+	#[arg(long, global = true, default_value_t = false)]
+	offline: bool,
 }
 
 #[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)]
 enum Commands {
 	/// Print JSON status for all swarm-* sessions
-	Status,
+	Status {
+		/// Print only the blocked-agent count from cached state (fast, for shell prompts)
+		#[arg(long, default_value_t = false)]
+		prompt_segment: bool,
+		/// Detailed JSON for one session (task, timeline, cost, preview, PR, processes)
+		#[arg(long)]
+		session: Option<String>,
+	},
+	/// Orphaned logs and session data (tmux session gone): list them, or move them to
+	/// ~/.swarm/trash with --apply. Also deletes trash older than general.trash_days.
+	Clean {
+		/// Only list what would happen (the default)
+		#[arg(long, default_value_t = false)]
+		dry_run: bool,
+		/// Do it
+		#[arg(long, default_value_t = false, conflicts_with = "dry_run")]
+		apply: bool,
+	},
+	/// Do not disturb: silence desktop notifications, sounds and pushes (M in the TUI)
+	Mute {
+		/// How long (e.g. 30m, 1h); omitted = until `swarm mute --off`
+		duration: Option<String>,
+		/// Unmute
+		#[arg(long, default_value_t = false, conflicts_with = "duration")]
+		off: bool,
+	},
+	/// One-line fleet summary for menu bars (xbar/SwiftBar/sketchybar)
+	Statusline {
+		/// Append an xbar/SwiftBar dropdown listing each agent
+		#[arg(long, default_value_t = false)]
+		menu: bool,
+	},
+	/// Other agents needing you, for a tmux status-right inside an agent session
+	TmuxStatus {
+		/// The session you're attached to (pass '#S'); left out of the counts
+		session: Option<String>,
+	},
+	/// Move a tmux client to the next/previous agent session or back to the
+	/// dashboard (what the Alt+. / Alt+, / Alt+0 bindings in ~/.swarm/tmux.conf run)
+	TmuxSwitch {
+		#[arg(value_parser = ["next", "prev", "dashboard"])]
+		to: String,
+		/// The client to move (#{client_name}); default: the most recent one
+		#[arg(long)]
+		client: Option<String>,
+		/// The session it's on now (#{session_name})
+		#[arg(long)]
+		from: Option<String>,
+	},
 	/// Check for and install updates
 	Update,
 	/// Create a new agent session
 	New {
-		/// Name for the session (without swarm- prefix)
-		name: String,
+		/// Name for the session (without swarm- prefix); left out, it's made from --prompt
+		name: Option<String>,
 		/// Agent type (defaults to claude)
 		#[arg(long, default_value = "claude")]
 		agent: String,
@@ -406,22 +544,272 @@ enum Commands {
 		/// Start Claude in auto-accept mode (sends Shift+Tab after launch)
 		#[arg(long, default_value_t = false)]
 		auto_accept: bool,
+		/// Launch N agents on the same work, each in its own git worktree, to compare
+		#[arg(long)]
+		variants: Option<usize>,
+		/// Model for the agent (e.g. sonnet, opus, haiku); defaults to [models] in config
+		#[arg(long, value_parser = parse_model_arg)]
+		model: Option<String>,
+		/// Stop the agent after this many minutes (see --on-limit)
+		#[arg(long)]
+		max_minutes: Option<u64>,
+		/// Stop a Claude agent once its estimated spend passes this many USD
+		#[arg(long)]
+		max_cost: Option<f64>,
+		/// What to do at a limit (defaults to [limits] action in config)
+		#[arg(long, value_parser = ["warn", "pause", "kill"])]
+		on_limit: Option<String>,
+		/// Soft deadline, e.g. 2h: shows runtime vs. budget on the row and notifies once past it
+		#[arg(long, value_name = "DURATION")]
+		within: Option<String>,
+		/// Branch worktrees start from ("current" = the repo's current branch; defaults to [worktrees] base_branch)
+		#[arg(long)]
+		base: Option<String>,
+		/// If a variant's worktree or branch already exists (defaults to [worktrees] on_conflict)
+		#[arg(long, value_parser = ["ask", "reuse", "suffix", "checkout", "abort"])]
+		on_conflict: Option<String>,
+		/// Add the agent(s) to a named group for group-wide actions (G in the TUI)
+		#[arg(long)]
+		group: Option<String>,
+	},
+	/// Hand a session's work to a new agent (snapshots context, ends the old session)
+	Handoff {
+		/// Session to hand off (with or without swarm- prefix)
+		session: String,
+		/// Agent type for the new session (defaults to the current agent)
+		#[arg(long)]
+		agent: Option<String>,
+		/// Extra instructions for the next agent
+		#[arg(long)]
+		note: Option<String>,
+	},
+	/// Have a planner agent split a big task into subtask files, then start a
+	/// worker agent per subtask in one group when it's done (while the TUI runs)
+	Fanout {
+		/// Task file to break up
+		task: String,
+		/// Group for the planner and workers (defaults to the task's name)
+		#[arg(long)]
+		group: Option<String>,
+		/// Agent type for the planner (defaults to general.default_agent)
+		#[arg(long)]
+		agent: Option<String>,
+	},
+	/// Rename a session (tmux session, log file and session metadata)
+	Rename {
+		/// Current session name (with or without swarm- prefix)
+		old: String,
+		/// New session name
+		new: String,
+	},
+	/// Block until agents finish or need input (exit 0), for scripts and CI.
+	/// Exits 2 on timeout, 3 if an agent failed or went away first.
+	Wait {
+		/// Session to wait for (with or without swarm- prefix)
+		#[arg(long, required_unless_present = "all", conflicts_with = "all")]
+		session: Option<String>,
+		/// Wait on every running agent: all done, or any one needing input
+		#[arg(long, default_value_t = false)]
+		all: bool,
+		/// Condition to wait for
+		#[arg(long, default_value = "done", value_parser = ["done", "needs_input"])]
+		until: String,
+		/// Give up after this long (e.g. 90s, 30m, 1h; plain numbers are seconds)
+		#[arg(long)]
+		timeout: Option<String>,
+	},
+	/// Stream fleet changes as NDJSON (session_created, status_changed, session_closed,
+	/// task_done), starting with one "session" event per running agent
+	Events {
+		/// Keep running and emit events as they happen
+		#[arg(long, short = 'f', default_value_t = false)]
+		follow: bool,
+	},
+	/// Start agents for many task files at once (directories, files or globs),
+	/// queueing any beyond general.max_agents until a slot frees up
+	RunAll {
+		/// Task files, directories of tasks, or quoted globs like "~/.swarm/tasks/*.md"
+		#[arg(required = true)]
+		paths: Vec<String>,
+		/// Don't wait to start queued tasks; just list them
+		#[arg(long, default_value_t = false)]
+		no_queue: bool,
+		/// Show what would be started without launching anything
+		#[arg(long, default_value_t = false)]
+		dry_run: bool,
+	},
+	/// Fuzzy-find a session or task and attach to it (tasks without an agent get one)
+	Pick,
+	/// Read-only web dashboard (agents, tasks, live previews) for checking in from a phone
+	Serve {
+		/// Address to listen on (use 0.0.0.0 to reach it from other devices)
+		#[arg(long, default_value = "127.0.0.1")]
+		host: String,
+		#[arg(long, default_value_t = 7777)]
+		port: u16,
+		/// Require ?token=... on every request (defaults to $SWARM_SERVE_TOKEN)
+		#[arg(long)]
+		token: Option<String>,
+	},
+	/// Review a repo's .swarm.toml commands (checks, hooks) and task template and
+	/// allow them; asked again whenever they change
+	Trust {
+		/// Repo directory (default: current directory)
+		#[arg(default_value = ".")]
+		repo: String,
+		/// Trust without asking for confirmation
+		#[arg(long, short = 'y', default_value_t = false)]
+		yes: bool,
+	},
+	/// Show teammates' published agent fleets ([team] members in config)
+	Fleet {
+		/// Publish this machine's status once instead (e.g. from cron)
+		#[arg(long, default_value_t = false)]
+		publish: bool,
+	},
+	/// Manage task files
+	Task {
+		#[command(subcommand)]
+		action: TaskCommands,
+	},
+	/// Past runs archived when sessions were marked done, newest first (O in the TUI)
+	History {
+		/// Only runs whose task title, task file or session name contains this
+		#[arg(long)]
+		task: Option<String>,
+		/// Print the full archived records (log tail, changes, timeline) as JSON
+		#[arg(long, default_value_t = false)]
+		json: bool,
+		/// How many runs to show
+		#[arg(long, short = 'n', default_value_t = 20)]
+		limit: usize,
+	},
+	/// Throughput from the run history: completed per week, average duration and
+	/// time blocked, success rate by agent/model (s in the History view)
+	Stats {
+		/// How many weeks back to chart
+		#[arg(long, default_value_t = 8)]
+		weeks: usize,
+		/// Print one table as CSV instead
+		#[arg(long, value_name = "TABLE", value_parser = ["weeks", "agents", "runs"])]
+		csv: Option<String>,
+	},
+	/// Play back a session log (by session name or log file path)
+	Replay {
+		/// Session name (with or without swarm- prefix) or path to a log file
+		target: String,
+		/// Delay between lines in milliseconds
+		#[arg(long, default_value_t = 20)]
+		delay_ms: u64,
+		/// Page through the log a screenful at a time instead of timed playback
+		#[arg(long, default_value_t = false)]
+		step: bool,
+	},
+}
+
+#[derive(Subcommand)]
+enum TaskCommands {
+	/// Create a task file and print its path; fails if a task with the same
+	/// file name exists
+	Add {
+		/// What the task is (first line becomes the title)
+		description: String,
+		/// MM-DD or YYYY-MM-DD, optionally followed by HH:MM (default: tomorrow)
+		#[arg(long)]
+		due: Option<String>,
+		/// Who to tell when it's done
+		#[arg(long)]
+		notify: Option<String>,
+		/// Comma-separated tags for the frontmatter
+		#[arg(long, default_value = DEFAULT_TASK_TAGS)]
+		tags: String,
+		/// Overwrite an existing task file with the same name
+		#[arg(long, default_value_t = false, conflicts_with = "suffix")]
+		force: bool,
+		/// Keep the existing task and write this one as <name>-2.md
+		#[arg(long, default_value_t = false)]
+		suffix: bool,
+	},
+	/// Split a markdown (one task per bullet/heading) or CSV file into task files
+	Import {
+		/// Markdown or .csv file to import
+		file: String,
+		/// Write without asking for confirmation
+		#[arg(long, short = 'y', default_value_t = false)]
+		yes: bool,
+		/// Only preview the split, don't write anything
+		#[arg(long, default_value_t = false)]
+		dry_run: bool,
+	},
+	/// Commit, pull and push tasks_dir now (it must be a git repo)
+	Sync,
+	/// Due dates of open tasks as an iCalendar (.ics) file, for importing or
+	/// subscribing to (`swarm serve` also has it at /calendar.ics)
+	Ics {
+		/// Write to this file instead of stdout
+		#[arg(long, short = 'o')]
+		output: Option<String>,
 	},
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
 	let cli = Cli::parse();
+	// Prompt segments run on every shell prompt: answer from the cache before loading config
+	if let Some(Commands::Status {
+		prompt_segment: true,
+		..
+	}) = cli.command
+	{
+		print!("{}", prompt_segment_text());
+		return Ok(());
+	}
+	console::init_tracing(cli.log_level.as_deref());
+	if cli.offline || std::env::var("SWARM_OFFLINE").is_ok_and(|v| !v.is_empty() && v != "0") {
+		net::force_offline();
+	}
 	let mut cfg = config::load_or_init().context("failed to load config")?;
+	notify::set_command(&cfg.notifications.command);
+	net::probe_via(&cfg.updates.releases_url);
+	dates::init(&cfg.dates);
 
 	match cli.command {
-		Some(Commands::Status) => {
+		Some(Commands::Status {
+			session: Some(name), ..
+		}) => {
+			let detail = session_detail(&cfg, &name)?;
+			println!("{}", serde_json::to_string_pretty(&detail)?);
+			Ok(())
+		}
+		Some(Commands::Status { .. }) => {
 			let sessions = collect_sessions(&cfg)?;
+			let _ = write_fleet_snapshot(&sessions);
 			println!("{}", serde_json::to_string_pretty(&sessions)?);
 			Ok(())
 		}
+		Some(Commands::Statusline { menu }) => {
+			let snapshot = fleet_snapshot(&cfg, Duration::from_secs(15))?;
+			println!("{}", statusline_text(&snapshot));
+			if menu {
+				println!("---");
+				for (name, status) in &snapshot.sessions {
+					println!("{} {} | bash=swarm terminal=true", status_indicator(*status, "emoji").0, name);
+				}
+			}
+			Ok(())
+		}
+		Some(Commands::TmuxStatus { session }) => {
+			let snapshot = fleet_snapshot(&cfg, Duration::from_secs(15))?;
+			println!("{}", tmux_status_text(&snapshot, session.as_deref()));
+			Ok(())
+		}
+		Some(Commands::TmuxSwitch { to, client, from }) => tmux_switch(&to, client.as_deref(), from.as_deref()),
 		Some(Commands::Update) => {
-			check_and_install_update()?;
+			// reqwest's blocking client can't run on the async runtime thread
+			let updates = cfg.updates.clone();
+			std::thread::spawn(move || check_and_install_update(&updates))
+				.join()
+				.map_err(|_| anyhow::anyhow!("update thread panicked"))??;
 			Ok(())
 		}
 		Some(Commands::New {
@@ -431,172 +819,1741 @@ async fn main() -> Result<()> {
 			prompt,
 			task,
 			auto_accept,
-		}) => handle_new(&cfg, name, agent, repo, prompt, task, auto_accept, true),
+			variants,
+			model,
+			max_minutes,
+			max_cost,
+			on_limit,
+			within,
+			base,
+			on_conflict,
+			group,
+		}) => {
+			// The PTY fallback only lives as long as the swarm process that owns it
+			if !tmux::is_available() {
+				return Err(anyhow::anyhow!(
+					"tmux not found - `swarm new` needs tmux so the agent outlives this command. Install with: brew install tmux (or start agents from the swarm dashboard)"
+				));
+			}
+			let limits = SessionLimits {
+				max_minutes,
+				max_cost,
+				action: on_limit,
+			};
+			let has_limits = limits.max_minutes.is_some() || limits.max_cost.is_some() || limits.action.is_some();
+			let within = within.as_deref().map(parse_duration_arg).transpose()?;
+			let name = match (name, prompt.as_deref()) {
+				(Some(name), _) => name,
+				(None, Some(prompt)) => auto_session_name(&cfg, prompt),
+				(None, None) => return Err(anyhow::anyhow!("give the session a name, or a --prompt to name it from")),
+			};
+			match variants {
+				Some(count) if count > 1 => {
+					let names = launch_variants(
+						&cfg,
+						&name,
+						&agent,
+						&repo,
+						prompt,
+						task,
+						count,
+						auto_accept,
+						model,
+						base.as_deref(),
+						on_conflict.as_deref(),
+					)?;
+					for name in names {
+						if has_limits {
+							write_session_limits(&format!("{SWARM_PREFIX}{name}"), &limits)?;
+						}
+						if let Some(within) = within {
+							write_session_deadline(&format!("{SWARM_PREFIX}{name}"), Some(within))?;
+						}
+						if let Some(group) = &group {
+							fs::write(session_group_path(&format!("{SWARM_PREFIX}{name}"))?, group)?;
+						}
+						println!("Started {}{} (variant)", SWARM_PREFIX, name);
+					}
+					println!("Compare them in swarm: select one and press C");
+					Ok(())
+				}
+				_ => {
+					let session = session_name_for(&name);
+					handle_new(&cfg, name, agent, repo, prompt, task, auto_accept, model, base.as_deref(), true)?;
+					if has_limits {
+						write_session_limits(&session, &limits)?;
+					}
+					if let Some(within) = within {
+						write_session_deadline(&session, Some(within))?;
+					}
+					if let Some(group) = &group {
+						fs::write(session_group_path(&session)?, group)?;
+					}
+					Ok(())
+				}
+			}
+		}
+		Some(Commands::Rename { old, new }) => {
+			let new_name = rename_session(&cfg, &old, &new)?;
+			println!("Renamed {} → {}{}", old, SWARM_PREFIX, new_name);
+			Ok(())
+		}
+		Some(Commands::Clean { dry_run: _, apply }) => run_clean(&cfg, apply),
+		Some(Commands::Mute { duration, off }) => {
+			if off {
+				notify::unmute()?;
+				println!("Notifications on");
+			} else {
+				let duration = duration.as_deref().map(parse_duration_arg).transpose()?;
+				notify::mute(duration)?;
+				match duration {
+					Some(d) => println!("Muted for {}", format_duration_short(d)),
+					None => println!("Muted until `swarm mute --off`"),
+				}
+			}
+			Ok(())
+		}
+		Some(Commands::Fanout { task, group, agent }) => {
+			let path = PathBuf::from(config::expand_path(&task));
+			let task = load_task_entry(&path)
+				.ok_or_else(|| anyhow::anyhow!("{} is not an open task file", path.display()))?;
+			let (name, group) = start_fanout(&cfg, &task, group, agent)?;
+			println!(
+				"Started planner {SWARM_PREFIX}{name} in group {group}; its subtasks get agents when it's done (keep swarm open)"
+			);
+			Ok(())
+		}
+		Some(Commands::Handoff { session, agent, note }) => {
+			let sessions = collect_sessions(&cfg)?;
+			let name = session.trim_start_matches(SWARM_PREFIX);
+			let sel = sessions
+				.iter()
+				.find(|s| s.name == name)
+				.ok_or_else(|| anyhow::anyhow!("no session named {}", name))?;
+			let agent = agent.unwrap_or_else(|| sel.agent.clone());
+			let new_name = handoff_session(&cfg, sel, &agent, note.as_deref(), None)?;
+			println!("Handed off {} → {}{} ({})", sel.session_name, SWARM_PREFIX, new_name, agent);
+			Ok(())
+		}
+		Some(Commands::Wait {
+			session,
+			all,
+			until,
+			timeout,
+		}) => {
+			let timeout = timeout.as_deref().map(parse_duration_arg).transpose()?;
+			let code = wait_for_sessions(&cfg, session.as_deref(), all, &until, timeout)?;
+			std::process::exit(code);
+		}
+		Some(Commands::Events { follow }) => stream_events(&cfg, follow),
+		Some(Commands::RunAll {
+			paths,
+			no_queue,
+			dry_run,
+		}) => {
+			if !tmux::is_available() && !dry_run {
+				return Err(anyhow::anyhow!(
+					"tmux not found - `swarm run-all` needs tmux so agents outlive this command. Install with: brew install tmux"
+				));
+			}
+			run_all(&cfg, &paths, no_queue, dry_run)
+		}
+		Some(Commands::Pick) => run_pick(&cfg),
+		Some(Commands::Serve { host, port, token }) => {
+			let token = token
+				.or_else(|| std::env::var("SWARM_SERVE_TOKEN").ok())
+				.filter(|t| !t.is_empty());
+			let opts = serve::ServeOptions {
+				host,
+				port,
+				token,
+				refresh: Duration::from_millis(cfg.general.poll_interval_ms.max(1000)),
+			};
+			let snapshot_cfg = cfg.clone();
+			let preview_cfg = cfg.clone();
+			let answer_cfg = cfg.clone();
+			let calendar_cfg = cfg.clone();
+			// tiny_http and the tmux probes block; keep them off the async runtime thread
+			std::thread::spawn(move || {
+				serve::run(
+					opts,
+					move || web_snapshot(&snapshot_cfg),
+					move |name| web_preview(&preview_cfg, name),
+					move |name, approve| web_answer(&answer_cfg, name, approve),
+					move || task_calendar(&calendar_cfg),
+				)
+			})
+			.join()
+			.map_err(|_| anyhow::anyhow!("dashboard server panicked"))?
+		}
+		Some(Commands::Trust { repo, yes }) => trust_repo(&repo, yes),
+		Some(Commands::Fleet { publish }) => {
+			if publish {
+				if cfg.team.publish_to.is_empty() {
+					return Err(anyhow::anyhow!("set [team] publish_to in config first"));
+				}
+				let status = team_status(&cfg, &collect_sessions(&cfg)?);
+				let (target, token) = (cfg.team.publish_to.clone(), cfg.team.token.clone());
+				// reqwest's blocking client can't run on the async runtime thread
+				std::thread::spawn(move || team::publish(&target, &token, &status))
+					.join()
+					.map_err(|_| anyhow::anyhow!("publish thread panicked"))??;
+				println!("Published to {}", cfg.team.publish_to);
+				return Ok(());
+			}
+			print_fleet(&cfg)
+		}
+		Some(Commands::Task { action }) => match action {
+			TaskCommands::Add {
+				description,
+				due,
+				notify,
+				tags,
+				force,
+				suffix,
+			} => {
+				let on_existing = match (force, suffix) {
+					(true, _) => OnExisting::Overwrite,
+					(_, true) => OnExisting::Suffix,
+					_ => OnExisting::Fail,
+				};
+				let target = task_file_path(&cfg, task_title(&description));
+				if let Some(similar) = similar_task(&cfg, task_title(&description)).filter(|t| t.path != target) {
+					if !confirm_similar_task(&cfg, &similar)? {
+						return Ok(());
+					}
+				}
+				let due = parse_due_input(due.as_deref());
+				let tags = tags.split(',').map(str::trim).filter(|t| !t.is_empty()).collect::<Vec<_>>().join(", ");
+				let cwd = std::env::current_dir()?;
+				let path = write_task_file(
+					&cfg,
+					task_title(&description),
+					&description,
+					notify.as_deref(),
+					due,
+					&tags,
+					on_existing,
+					Some(&cwd),
+				)?;
+				println!("{}", path.display());
+				Ok(())
+			}
+			TaskCommands::Import { file, yes, dry_run } => import_tasks(&cfg, &file, yes, dry_run),
+			TaskCommands::Sync => {
+				let dir = PathBuf::from(&cfg.general.tasks_dir);
+				let result = task_sync::sync(&dir, &cfg.task_sync.remote)?;
+				println!("Tasks {}: {}", dir.display(), result);
+				Ok(())
+			}
+			TaskCommands::Ics { output } => {
+				let calendar = task_calendar(&cfg);
+				match output {
+					Some(path) => {
+						let path = PathBuf::from(config::expand_path(&path));
+						fs::write(&path, calendar).with_context(|| format!("failed to write {}", path.display()))?;
+						println!("Wrote {}", path.display());
+					}
+					None => print!("{calendar}"),
+				}
+				Ok(())
+			}
+		},
+		Some(Commands::History { task, json, limit }) => print_history(task.as_deref(), json, limit),
+		Some(Commands::Stats { weeks, csv }) => {
+			let runs = history::load();
+			let stats = history::stats(&runs, weeks, Local::now().date_naive());
+			match csv {
+				Some(table) => print!("{}", history::csv(&stats, &runs, &table)),
+				None => println!("{}", stats_lines(&stats).join("\n")),
+			}
+			Ok(())
+		}
+		Some(Commands::Replay {
+			target,
+			delay_ms,
+			step,
+		}) => {
+			let log_path = resolve_replay_log(&cfg, &target)?;
+			logs::replay(&log_path, Duration::from_millis(delay_ms), step)
+		}
 		None => run_tui(&mut cfg),
 	}
 }
 
+/// Max concurrent per-session probes (each runs a few tmux subprocesses)
+const COLLECT_WORKERS: usize = 8;
+
+#[tracing::instrument(level = "debug", skip_all, err)]
 fn collect_sessions(cfg: &Config) -> Result<Vec<AgentSession>> {
 	let sessions = list_sessions()?;
 	cleanup_orphans(cfg, &sessions);
-	let mut out = Vec::new();
-	for session in sessions {
-		let log_path = Path::new(&cfg.general.logs_dir).join(format!("{session}.log"));
-		let _ = ensure_pipe(&session, &log_path);
-
-		let lines = tail_lines(&log_path, 80).unwrap_or_default();
-		let last_output =
-			latest_output_time(&log_path).or_else(|| pane_last_used(&session).ok().flatten());
-		let age = last_output.and_then(|t| SystemTime::now().duration_since(t).ok());
-		let agent = agent_for_session(&session).unwrap_or_else(|_| "claude".to_string());
-		let detection = detection_for_agent(&agent);
-		let status = detect_status(&lines, &detection, age);
-		let task = task_info_for_session(&session)?;
-
-		let preview = tail_lines(&log_path, 12).unwrap_or_default();
-		let is_yolo = is_yolo_session(&session);
-		let worktree_path = get_worktree_path(&session);
-		out.push(AgentSession {
-			name: session.trim_start_matches(SWARM_PREFIX).to_string(),
-			session_name: session.clone(),
-			agent,
-			status,
-			last_output,
-			log_path,
-			preview,
-			task,
-			is_yolo,
-			worktree_path,
-		});
+	if sessions.len() <= 1 {
+		return sessions.iter().map(|s| collect_session(cfg, s)).collect();
 	}
-	Ok(out)
+	// Probe sessions in parallel, keeping tmux ls order
+	let chunk_size = sessions.len().div_ceil(COLLECT_WORKERS);
+	std::thread::scope(|scope| {
+		let handles: Vec<_> = sessions
+			.chunks(chunk_size)
+			.map(|chunk| {
+				scope.spawn(move || {
+					chunk
+						.iter()
+						.map(|s| collect_session(cfg, s))
+						.collect::<Vec<_>>()
+				})
+			})
+			.collect();
+		let mut out = Vec::with_capacity(sessions.len());
+		for handle in handles {
+			let results = handle
+				.join()
+				.map_err(|_| anyhow::anyhow!("session probe thread panicked"))?;
+			for result in results {
+				out.push(result?);
+			}
+		}
+		Ok(group_together(out))
+	})
 }
 
-fn cleanup_orphans(cfg: &Config, active_sessions: &[String]) {
-	let active: HashSet<String> = active_sessions.iter().cloned().collect();
-
-	if let Ok(entries) = fs::read_dir(&cfg.general.logs_dir) {
-		for entry in entries.flatten() {
-			let path = entry.path();
-			if !path.is_file() {
-				continue;
-			}
-			let name = entry.file_name().to_string_lossy().to_string();
-			if !(name.starts_with(SWARM_PREFIX) && name.ends_with(".log")) {
-				continue;
-			}
-			let session_name = name.trim_end_matches(".log");
-			if !active.contains(session_name) {
-				let _ = fs::remove_file(&path);
-			}
+/// Keep tmux order, but list a group's members together where its first member is
+fn group_together(sessions: Vec<AgentSession>) -> Vec<AgentSession> {
+	let mut out: Vec<AgentSession> = Vec::with_capacity(sessions.len());
+	let mut rest = sessions;
+	while !rest.is_empty() {
+		let first = rest.remove(0);
+		let group = first.group.clone();
+		out.push(first);
+		if let Some(group) = group {
+			let (members, others): (Vec<_>, Vec<_>) =
+				rest.into_iter().partition(|s| s.group.as_ref() == Some(&group));
+			out.extend(members);
+			rest = others;
 		}
 	}
+	out
+}
 
-	if let Ok(dir) = session_store_dir() {
-		if let Ok(entries) = fs::read_dir(&dir) {
-			for entry in entries.flatten() {
-				let name = entry.file_name().to_string_lossy().to_string();
-				if !active.contains(&name) {
-					// Note: We keep worktrees when sessions are cleaned up
-					// They can be manually cleaned with `git worktree remove`
-					let _ = fs::remove_dir_all(entry.path());
-				}
-			}
+/// The rows of a list that fit in `height` lines: from `first` (the previous
+/// frame's top row), scrolled just enough to keep `selected` in view and pulled
+/// back when the list ends early. Only these rows are built into ListItems, so
+/// a fleet of hundreds costs a frame no more than a screenful does.
+fn visible_rows(
+	len: usize,
+	selected: Option<usize>,
+	first: usize,
+	height: usize,
+	row_height: impl Fn(usize) -> usize,
+) -> std::ops::Range<usize> {
+	let mut first = first.min(len.saturating_sub(1));
+	if let Some(selected) = selected.filter(|&s| s < len) {
+		first = first.min(selected);
+		while first < selected && (first..=selected).map(&row_height).sum::<usize>() > height {
+			first += 1;
+		}
+	}
+	let mut used = 0;
+	let mut end = first;
+	while end < len && used + row_height(end) <= height {
+		used += row_height(end);
+		end += 1;
+	}
+	if end == len {
+		while first > 0 && used + row_height(first - 1) <= height {
+			first -= 1;
+			used += row_height(first);
 		}
 	}
+	// A row taller than the whole list still shows, cut off
+	first..end.max((first + 1).min(len))
 }
 
-fn latest_output_time(path: &Path) -> Option<SystemTime> {
-	fs::metadata(path).and_then(|m| m.modified()).ok()
+/// Which Agents list rows are hidden: members of a collapsed group other than
+/// its first, and sessions not matching the / filter. One pass with the filter
+/// parsed once, per redraw or key rather than per row.
+fn hidden_rows(sessions: &[AgentSession], collapsed: &HashSet<String>, filter: &str) -> Vec<bool> {
+	let filter = (!filter.is_empty()).then(|| parse_filter(filter));
+	let mut seen: HashSet<&str> = HashSet::new();
+	sessions
+		.iter()
+		.map(|session| {
+			let filtered_out = filter.as_ref().is_some_and(|f| !session_matches(session, f));
+			let folded = session.group.as_deref().is_some_and(|g| !seen.insert(g) && collapsed.contains(g));
+			filtered_out || folded
+		})
+		.collect()
 }
 
-fn handle_new(
-	cfg: &Config,
-	name: String,
-	agent: String,
-	repo: String,
-	prompt: Option<String>,
-	task: Option<String>,
-	auto_accept: bool,
-	announce: bool,
-) -> Result<()> {
-	// Truncate name to avoid "file name too long" errors (macOS limit is 255 bytes)
-	// Keep it under 100 chars to leave room for session prefix and other path components
-	let raw_name = name.trim_start_matches(SWARM_PREFIX);
-	let clean_name = if raw_name.len() > 100 {
-		raw_name.chars().take(100).collect::<String>()
+/// The nearest shown row after `idx` (or before it, going back)
+fn next_visible(hidden: &[bool], idx: usize, forward: bool) -> Option<usize> {
+	if forward {
+		(idx + 1..hidden.len()).find(|&i| !hidden[i])
 	} else {
-		raw_name.to_string()
-	};
-	let session = format!("{SWARM_PREFIX}{clean_name}");
-	let target_dir = resolve_repo_path(&repo)?;
-
-	if let Some(task_path) = &task {
-		let marker = session_task_path(&session)?;
-		fs::write(&marker, task_path)?;
-		// Also write .claude-task to repo root so Claude can find it after context compaction
-		let claude_task_marker = target_dir.join(".claude-task");
-		fs::write(&claude_task_marker, format!("{}\n", task_path))?;
+		(0..idx.min(hidden.len())).rev().find(|&i| !hidden[i])
 	}
+}
 
-	{
-		let agent_marker = session_agent_path(&session)?;
-		fs::write(&agent_marker, &agent)?;
-	}
+/// Which rows are the first of their group (and get its header row)
+fn group_heads(sessions: &[AgentSession]) -> Vec<bool> {
+	let mut seen: HashSet<&str> = HashSet::new();
+	sessions.iter().map(|s| s.group.as_deref().is_some_and(|g| seen.insert(g))).collect()
+}
 
-	// Mark YOLO mode sessions so we can show a warning indicator
-	if auto_accept {
-		let yolo_marker = session_yolo_path(&session)?;
-		fs::write(&yolo_marker, "1")?;
-	}
+/// The row to select instead of hidden `idx`: its group's header, else the
+/// nearest visible row (`idx` when nothing is visible)
+fn visible_selection(sessions: &[AgentSession], idx: usize, hidden: &[bool]) -> usize {
+	let visible = |i: &usize| hidden.get(*i) != Some(&true);
+	[group_head(sessions, idx)]
+		.into_iter()
+		.filter(visible)
+		.chain((idx..sessions.len()).filter(visible))
+		.chain((0..idx).rev().filter(visible))
+		.next()
+		.unwrap_or(idx)
+}
 
-	// Build the command with optional initial prompt
-	// Include worktree hint for implementation tasks
-	let worktree_note = "\n\nIMPORTANT: If this task involves writing code (not just research), ask the user: \"Do you want me to create a git worktree for isolation?\" If yes, call \\`/worktree\\` to set up an isolated workspace.";
-	let initial_prompt = prompt.clone().map(|p| {
-		format!("{}{}", p, worktree_note)
-	}).or_else(|| {
-		task.as_ref().map(|task_path| {
-			format!(
-				"Starting task. Read {} for context (include any Process Log). Summarize the task file before acting.{}",
-				task_path,
-				worktree_note
-			)
+/// Case-insensitive match on name, agent, status, group, task title and notes
+fn session_matches(session: &AgentSession, filter: &Filter) -> bool {
+	let Filter { terms, text } = filter;
+	let now = SystemTime::now();
+	let text_matches = [session.name.as_str(), session.agent.as_str(), session.status.label()]
+		.into_iter()
+		.chain(session.group.as_deref())
+		.chain(session.task.as_ref().map(|t| t.title.as_str()))
+		.chain(session.notes.iter().map(String::as_str))
+		.any(|field| field.to_lowercase().contains(text.as_str()));
+	text_matches
+		&& terms.iter().all(|term| match term {
+			FilterTerm::Status(want) => status_matches(session.status, want),
+			FilterTerm::Tag(want) => session.task.as_ref().is_some_and(|t| t.tags.iter().any(|tag| tag.eq_ignore_ascii_case(want))),
+			FilterTerm::Repo(want) => repo_matches(session, want),
+			FilterTerm::Agent(want) => session.agent.eq_ignore_ascii_case(want),
+			FilterTerm::Age(ord, limit) => session
+				.last_output
+				.and_then(|t| now.duration_since(t).ok())
+				.is_some_and(|age| age.cmp(limit) == *ord),
+			FilterTerm::Due(_) | FilterTerm::DueWithin(_) => due_matches(term, session.task.as_ref().and_then(|t| t.due)),
+			FilterTerm::HasAgent(has) => *has,
 		})
-	});
+}
 
-	// Write .claude/settings.local.json with allowed tools before starting Claude
-	if agent == "claude" && !auto_accept {
-		// Expand tasks_dir path (resolves ~ to home directory)
-		let tasks_dir = config::expand_path(&cfg.general.tasks_dir);
-		let mut allowed: Vec<String> = vec![
-			"Read(~/.swarm/tasks/**)".to_string(),
-			format!("Read({}/**)", tasks_dir),
-		];
-		allowed.extend(cfg.allowed_tools.get_all_tools());
+/// Indices of the tasks the active saved view shows (all of them without one),
+/// worked out once per refresh rather than per row drawn
+fn shown_tasks(tasks: &[TaskEntry], sessions: &[AgentSession], view: Option<&Filter>) -> Vec<usize> {
+	(0..tasks.len())
+		.filter(|&i| view.is_none_or(|filter| task_matches(&tasks[i], &find_sessions_for_task(sessions, &tasks[i].path), filter)))
+		.collect()
+}
 
-		// Expand additional directories (resolve ~ to home)
-		let additional_dirs: Vec<String> = cfg
-			.allowed_tools
-			.additional_directories
-			.iter()
-			.map(|d| config::expand_path(d))
-			.collect();
+/// The task to select instead of `idx` when the view hides it: the next one
+/// shown, else the one before; None when it hides them all
+fn visible_task(shown: &[usize], idx: usize) -> Option<usize> {
+	shown.iter().find(|&&i| i >= idx).or_else(|| shown.last()).copied()
+}
 
-		let settings_json = serde_json::json!({
-			"permissions": {
-				"allow": allowed,
-				"additionalDirectories": additional_dirs
+/// Whether a task passes a saved view's filter. Status, repo and agent terms
+/// also match through the agents working on it; age is since the file's last edit.
+fn task_matches(task: &TaskEntry, linked: &[&AgentSession], filter: &Filter) -> bool {
+	let Filter { terms, text } = filter;
+	let stem = task.path.file_stem().unwrap_or_default().to_string_lossy().to_lowercase();
+	(task.title.to_lowercase().contains(text.as_str()) || stem.contains(text.as_str()))
+		&& terms.iter().all(|term| match term {
+			FilterTerm::Status(want) => {
+				task.status.as_deref().is_some_and(|s| filter_key(s).starts_with(want.as_str()))
+					|| linked.iter().any(|s| status_matches(s.status, want))
 			}
-		});
+			FilterTerm::Tag(want) => task.tags.iter().any(|tag| tag.eq_ignore_ascii_case(want)),
+			FilterTerm::Repo(want) => linked.iter().any(|s| repo_matches(s, want)),
+			FilterTerm::Agent(want) => linked.iter().any(|s| s.agent.eq_ignore_ascii_case(want)),
+			FilterTerm::Age(ord, limit) => task
+				.modified
+				.and_then(|t| SystemTime::now().duration_since(t).ok())
+				.is_some_and(|age| age.cmp(limit) == *ord),
+			FilterTerm::Due(_) | FilterTerm::DueWithin(_) => due_matches(term, task.due),
+			FilterTerm::HasAgent(has) => linked.is_empty() != *has,
+		})
+}
 
-		let claude_dir = target_dir.join(".claude");
-		fs::create_dir_all(&claude_dir)?;
-		let settings_path = claude_dir.join("settings.local.json");
-		fs::write(&settings_path, serde_json::to_string_pretty(&settings_json)?)?;
-	}
+/// A parsed / filter or saved view: its structured terms, and the leftover
+/// words, which match as one phrase like the plain / filter always did
+#[derive(Debug, PartialEq)]
+struct Filter {
+	terms: Vec<FilterTerm>,
+	text: String,
+}
 
-	// Build Claude command
+/// One structured term of a / filter or saved view; other words are plain text
+#[derive(Debug, PartialEq)]
+enum FilterTerm {
+	Status(String),                     // status:needs_input (a prefix: status:needs works)
+	Tag(String),                        // tag:backend
+	Repo(String),                       // repo:billing, part of the path
+	Agent(String),                      // agent:codex
+	Age(std::cmp::Ordering, Duration),  // age>10m, age<1h
+	Due(String),                        // due:today, due:week, due:overdue, due:none
+	DueWithin(Duration),                // due<3d, overdue included
+	HasAgent(bool),                     // has:agent, no:agent
+}
+
+/// Split a filter into its terms and the leftover words
+fn parse_filter(filter: &str) -> Filter {
+	let mut terms = Vec::new();
+	let mut words = Vec::new();
+	for word in filter.split_whitespace() {
+		let lower = word.to_lowercase();
+		let term = if let Some(value) = lower.strip_prefix("status:") {
+			Some(FilterTerm::Status(filter_key(value)))
+		} else if let Some(value) = lower.strip_prefix("tag:") {
+			Some(FilterTerm::Tag(value.trim_start_matches('#').to_string()))
+		} else if let Some(value) = lower.strip_prefix("repo:") {
+			Some(FilterTerm::Repo(value.to_string()))
+		} else if let Some(value) = lower.strip_prefix("agent:") {
+			Some(FilterTerm::Agent(value.to_string()))
+		} else if let Some(value) = lower.strip_prefix("age>") {
+			parse_duration_arg(value).ok().map(|d| FilterTerm::Age(std::cmp::Ordering::Greater, d))
+		} else if let Some(value) = lower.strip_prefix("age<") {
+			parse_duration_arg(value).ok().map(|d| FilterTerm::Age(std::cmp::Ordering::Less, d))
+		} else if let Some(value) = lower.strip_prefix("due:").filter(|v| ["today", "week", "overdue", "none"].contains(v)) {
+			Some(FilterTerm::Due(value.to_string()))
+		} else if let Some(value) = lower.strip_prefix("due<") {
+			parse_duration_arg(value).ok().map(FilterTerm::DueWithin)
+		} else {
+			match lower.as_str() {
+				"has:agent" => Some(FilterTerm::HasAgent(true)),
+				"no:agent" => Some(FilterTerm::HasAgent(false)),
+				_ => None,
+			}
+		};
+		match term {
+			Some(term) => terms.push(term),
+			None => words.push(lower),
+		}
+	}
+	Filter {
+		terms,
+		text: words.join(" "),
+	}
+}
+
+/// repo:billing: part of the agent's worktree or working directory
+fn repo_matches(session: &AgentSession, want: &str) -> bool {
+	session
+		.worktree_path
+		.as_ref()
+		.or(session.repo.as_ref())
+		.is_some_and(|p| p.to_string_lossy().to_lowercase().contains(want))
+}
+
+/// "Needs input" and "needs-input" → "needs_input"
+fn filter_key(value: &str) -> String {
+	value.trim().to_lowercase().replace([' ', '-'], "_")
+}
+
+/// status:blocked is an alias for needs input
+fn status_matches(status: AgentStatus, want: &str) -> bool {
+	filter_key(status.label()).starts_with(want) || (want == "blocked" && status == AgentStatus::NeedsInput)
+}
+
+/// due:today / week (this week, per [dates] week_start) / overdue / none, or due<3d
+fn due_matches(term: &FilterTerm, due: Option<Due>) -> bool {
+	let now = Local::now().naive_local();
+	let today = now.date();
+	let overdue = |d: Due| d.at().map_or(d.date < today, |at| at < now);
+	match (term, due) {
+		(FilterTerm::Due(when), None) => when == "none",
+		(FilterTerm::Due(when), Some(due)) => match when.as_str() {
+			"today" => due.date == today,
+			"week" => {
+				let start = today - chrono::Duration::days(agenda_lead(today));
+				due.date >= start && due.date < start + chrono::Duration::days(7)
+			}
+			"overdue" => overdue(due),
+			_ => false,
+		},
+		(FilterTerm::DueWithin(within), Some(due)) => {
+			let limit = now + chrono::Duration::from_std(*within).unwrap_or_default();
+			due.at().map_or(due.date <= limit.date(), |at| at <= limit)
+		}
+		_ => false,
+	}
+}
+
+/// Priority of the session's task (`priority:` frontmatter), for notification styles
+fn session_priority(session: &AgentSession) -> Option<&str> {
+	session.task.as_ref().and_then(|t| t.priority.as_deref())
+}
+
+/// Index of the first member of `idx`'s group (`idx` itself when ungrouped)
+fn group_head(sessions: &[AgentSession], idx: usize) -> usize {
+	match sessions.get(idx).and_then(|s| s.group.as_ref()) {
+		Some(group) => sessions.iter().position(|s| s.group.as_ref() == Some(group)).unwrap_or(idx),
+		None => idx,
+	}
+}
+
+/// Members of `group`, in list order
+fn group_members<'a>(sessions: &'a [AgentSession], group: &str) -> Vec<&'a AgentSession> {
+	sessions.iter().filter(|s| s.group.as_deref() == Some(group)).collect()
+}
+
+/// "3 agents · 1 needs input · 2 running"
+fn group_summary(members: &[&AgentSession]) -> String {
+	let mut parts = vec![format!("{} agent{}", members.len(), if members.len() == 1 { "" } else { "s" })];
+	let mut statuses: Vec<AgentStatus> = members.iter().map(|s| s.status).collect();
+	statuses.sort_by_key(|s| s.attention_rank());
+	statuses.dedup();
+	for status in statuses {
+		let count = members.iter().filter(|s| s.status == status).count();
+		parts.push(format!("{count} {}", status.label()));
+	}
+	parts.join(" · ")
+}
+
+#[tracing::instrument(level = "trace", skip(cfg), err)]
+fn collect_session(cfg: &Config, session: &str) -> Result<AgentSession> {
+	let log_path = Path::new(&cfg.general.logs_dir).join(format!("{session}.log"));
+	let _ = ensure_pipe(session, &log_path);
+
+	let lines = tail_lines(&log_path, 80).unwrap_or_default();
+	let last_output =
+		latest_output_time(&log_path).or_else(|| pane_last_used(session).ok().flatten());
+	let age = last_output.and_then(|t| SystemTime::now().duration_since(t).ok());
+	let agent = agent_for_session(session).unwrap_or_else(|_| "claude".to_string());
+	let detection = detection_for_agent(&agent, &cfg.detection);
+	// A dead pane means the agent exited
+	let exit_code = tmux::pane_exit_status(session).ok().flatten();
+	let status = match exit_code {
+		Some(0) => AgentStatus::Done,
+		Some(_) => AgentStatus::Error,
+		None => {
+			// Prompts printed before our last input were already answered
+			let since_input = match input_offset(session, &log_path) {
+				Some(offset) => logs::tail_lines_from(&log_path, offset, 80).unwrap_or_default(),
+				None => lines.clone(),
+			};
+			detect_status(&since_input, &detection, age)
+		}
+	};
+	let mut activity = latest_activity(&lines);
+	let status = match (status, age) {
+		(AgentStatus::Idle, Some(age)) => match health_check(session, &detection, age) {
+			Some(AgentStatus::Error) => {
+				activity = Some("agent process gone (hung?)".to_string());
+				AgentStatus::Error
+			}
+			Some(checked) => checked,
+			None => status,
+		},
+		_ => status,
+	};
+	let progress = todo_progress(&lines);
+	let task = task_info_for_session(session)?;
+
+	let preview = tail_lines(&log_path, 12).unwrap_or_default();
+	let is_yolo = is_yolo_session(session);
+	let worktree_path = get_worktree_path(session);
+	let stage = get_stage_info(session, task.as_ref());
+	let variant = get_variant_info(session);
+	let group = session_group_path(session)
+		.ok()
+		.and_then(|p| fs::read_to_string(p).ok())
+		.map(|g| g.trim().to_string())
+		.filter(|g| !g.is_empty());
+	let model = session_model_path(session)
+		.ok()
+		.and_then(|p| fs::read_to_string(p).ok())
+		.map(|m| m.trim().to_string())
+		.filter(|m| !m.is_empty());
+	let notes = read_session_notes(session);
+	let pinned = is_pinned(session);
+	let interviewing = is_interviewing(session, task.as_ref());
+	let deadline = read_session_deadline(session);
+	let repo = tmux::session_path(session).ok().flatten().map(PathBuf::from);
+	Ok(AgentSession {
+		name: session.trim_start_matches(SWARM_PREFIX).to_string(),
+		session_name: session.to_string(),
+		agent,
+		status,
+		last_output,
+		log_path,
+		preview,
+		task,
+		is_yolo,
+		worktree_path,
+		exit_code,
+		stage,
+		variant,
+		group,
+		notes,
+		pinned,
+		model,
+		activity,
+		progress,
+		interviewing,
+		deadline,
+		repo,
+	})
+}
+
+fn write_fleet_snapshot(sessions: &[AgentSession]) -> Result<FleetSnapshot> {
+	let snapshot = FleetSnapshot {
+		updated_at: SystemTime::now()
+			.duration_since(SystemTime::UNIX_EPOCH)
+			.map(|d| d.as_secs())
+			.unwrap_or(0),
+		sessions: sessions.iter().map(|s| (s.name.clone(), s.status)).collect(),
+	};
+	fs::write(status_cache_path()?, serde_json::to_string(&snapshot)?)?;
+	Ok(snapshot)
+}
+
+/// Cached fleet state and its age, without touching tmux
+fn read_fleet_snapshot() -> Option<(FleetSnapshot, Duration)> {
+	let content = fs::read_to_string(status_cache_path().ok()?).ok()?;
+	let snapshot: FleetSnapshot = serde_json::from_str(&content).ok()?;
+	let now = SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.ok()?
+		.as_secs();
+	let age = Duration::from_secs(now.saturating_sub(snapshot.updated_at));
+	Some((snapshot, age))
+}
+
+/// Cached fleet state if fresher than max_age, otherwise collect and re-cache
+fn fleet_snapshot(cfg: &Config, max_age: Duration) -> Result<FleetSnapshot> {
+	if let Some((snapshot, age)) = read_fleet_snapshot() {
+		if age <= max_age {
+			return Ok(snapshot);
+		}
+	}
+	let sessions = collect_sessions(cfg)?;
+	write_fleet_snapshot(&sessions)
+}
+
+/// Blocked-agent count for shell prompts, e.g. "🔴2". Empty when nothing is blocked
+/// or the cache is stale (swarm not running), so prompts never show outdated state.
+fn prompt_segment_text() -> String {
+	match read_fleet_snapshot() {
+		Some((snapshot, age)) if age <= Duration::from_secs(300) => {
+			match snapshot.count(AgentStatus::NeedsInput) {
+				0 => String::new(),
+				n => format!("🔴{n}"),
+			}
+		}
+		_ => String::new(),
+	}
+}
+
+/// Compact summary like "🟢3 🔴1 ✓2" (zero counts omitted)
+fn statusline_text(snapshot: &FleetSnapshot) -> String {
+	let parts: Vec<String> = [
+		(AgentStatus::NeedsInput, "🔴"),
+		(AgentStatus::Error, "❌"),
+		(AgentStatus::Running, "🟢"),
+		(AgentStatus::Idle, "🟡"),
+		(AgentStatus::Done, "✓"),
+	]
+	.iter()
+	.filter_map(|(status, icon)| {
+		let n = snapshot.count(*status);
+		(n > 0).then(|| format!("{icon}{n}"))
+	})
+	.collect();
+	if parts.is_empty() {
+		"swarm".to_string()
+	} else {
+		parts.join(" ")
+	}
+}
+
+/// Records the tmux session the dashboard runs in, for Alt+0 in agent sessions
+fn dashboard_session_path() -> Result<PathBuf> {
+	Ok(config::base_dir()?.join("dashboard-session"))
+}
+
+/// Alt+. / Alt+, cycle through agent sessions in the order they were started;
+/// Alt+0 goes to the dashboard's tmux session, or detaches when the dashboard
+/// is waiting outside tmux for the attach to end
+fn tmux_switch(to: &str, client: Option<&str>, from: Option<&str>) -> Result<()> {
+	if to == "dashboard" {
+		let dashboard = dashboard_session_path()
+			.ok()
+			.and_then(|p| fs::read_to_string(p).ok())
+			.map(|s| s.trim().to_string())
+			.filter(|s| !s.is_empty() && tmux::has_session(s));
+		return match dashboard {
+			Some(session) => tmux::switch_client(client, &session),
+			None => tmux::detach_client(client),
+		};
+	}
+	let sessions = list_sessions()?;
+	if sessions.is_empty() {
+		return Ok(());
+	}
+	let len = sessions.len();
+	let current = from.and_then(|f| sessions.iter().position(|s| s == f));
+	let target = match (current, to == "next") {
+		(Some(i), true) => (i + 1) % len,
+		(Some(i), false) => (i + len - 1) % len,
+		(None, true) => 0,
+		(None, false) => len - 1,
+	};
+	if current == Some(target) {
+		return Ok(());
+	}
+	tmux::switch_client(client, &sessions[target])
+}
+
+/// "#[fg=red,bold]2 other agents need input#[default]" with tmux style codes,
+/// counting every agent but `current`. Empty when nothing needs attention.
+fn tmux_status_text(snapshot: &FleetSnapshot, current: Option<&str>) -> String {
+	let current = current.map(|s| s.trim_start_matches(SWARM_PREFIX));
+	// "other" only reads right from inside an agent's own session
+	let other = if snapshot.sessions.iter().any(|(name, _)| Some(name.as_str()) == current) { "other " } else { "" };
+	let count = |status: AgentStatus| {
+		snapshot
+			.sessions
+			.iter()
+			.filter(|(name, s)| *s == status && Some(name.as_str()) != current)
+			.count()
+	};
+	let mut parts = Vec::new();
+	match count(AgentStatus::NeedsInput) {
+		0 => {}
+		1 => parts.push(format!("#[fg=red,bold]1 {other}agent needs input#[default]")),
+		n => parts.push(format!("#[fg=red,bold]{n} {other}agents need input#[default]")),
+	}
+	match count(AgentStatus::Error) {
+		0 => {}
+		1 => parts.push(format!("#[fg=red]1 {other}agent failed#[default]")),
+		n => parts.push(format!("#[fg=red]{n} {other}agents failed#[default]")),
+	}
+	parts.join(" · ")
+}
+
+/// Open tasks with a due date as an .ics calendar
+fn task_calendar(cfg: &Config) -> String {
+	let events: Vec<ics::Event> = load_tasks(cfg)
+		.into_iter()
+		.filter_map(|t| {
+			Some(ics::Event {
+				uid: format!("{}@swarm", t.path.file_stem()?.to_string_lossy()),
+				description: format!("swarm task: {}", t.path.display()),
+				summary: t.title,
+				due: t.due?,
+			})
+		})
+		.collect();
+	let alarm = parse_duration_arg(&cfg.notifications.due_reminder).ok();
+	ics::calendar(&events, alarm)
+}
+
+/// Agents and tasks as JSON for the web dashboard
+fn web_snapshot(cfg: &Config) -> serde_json::Value {
+	let sessions = collect_sessions(cfg).unwrap_or_default();
+	let tasks = load_tasks(cfg);
+	let now = SystemTime::now();
+	serde_json::json!({
+		"updated_at": now.duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+		"sessions": sessions.iter().map(|s| serde_json::json!({
+			"name": s.name,
+			"status": s.status,
+			"task": s.task.as_ref().map(|t| &t.title),
+			"activity": s.activity,
+			"progress": s.progress,
+			"age_secs": s.last_output.and_then(|t| now.duration_since(t).ok()).map(|d| d.as_secs()),
+		})).collect::<Vec<_>>(),
+		"tasks": tasks.iter().map(|t| serde_json::json!({
+			"title": t.title,
+			"due": t.due.map(|d| d.to_string()),
+			"status": t.status,
+			"claimed_by": t.claimed_by,
+			"agents": find_sessions_for_task(&sessions, &t.path).len(),
+		})).collect::<Vec<_>>(),
+	})
+}
+
+/// Everything the Details pane knows about one session, as JSON for scripts
+fn session_detail(cfg: &Config, name: &str) -> Result<serde_json::Value> {
+	let session_name = format!("{SWARM_PREFIX}{}", name.trim_start_matches(SWARM_PREFIX));
+	if !list_sessions()?.contains(&session_name) {
+		return Err(anyhow::anyhow!("no such session: {name}"));
+	}
+	let session = collect_session(cfg, &session_name)?;
+	let work_dir = session_work_dir(&session);
+
+	let task = session.task.as_ref().map(|t| {
+		serde_json::json!({
+			"title": t.title,
+			"path": t.path,
+			"frontmatter": frontmatter_map(&t.path),
+		})
+	});
+	// The task's Process Log is the session timeline (agents append with /log)
+	let timeline: Vec<String> = session
+		.task
+		.as_ref()
+		.and_then(|t| parse_process_log(&t.path))
+		.unwrap_or_default()
+		.into_iter()
+		.map(|l| l.trim().to_string())
+		.filter(|l| !l.is_empty())
+		.collect();
+
+	let claude = match (&work_dir, session.agent.as_str()) {
+		(Some(dir), "claude") => {
+			let mut state = claude_session::ClaudeState::default();
+			claude_session::refresh(&mut state, dir);
+			(!state.path.as_os_str().is_empty()).then(|| {
+				serde_json::json!({
+					"transcript": state.path,
+					"turns": state.turns,
+					"last_tool": state.last_tool,
+					"model": state.model,
+					"todos": state.todos.iter().map(|t| serde_json::json!({
+						"content": t.content,
+						"status": t.status,
+					})).collect::<Vec<_>>(),
+					"usage": state.usage,
+					"cost_usd": (state.cost_estimate() * 100.0).round() / 100.0,
+				})
+			})
+		}
+		_ => None,
+	};
+
+	let log_path = Path::new(&cfg.general.logs_dir).join(format!("{session_name}.log"));
+	let preview = clean_preview(&tail_lines(&log_path, 40).unwrap_or_default());
+	let pr = work_dir.as_deref().and_then(pull_request_info);
+	let processes = tmux::pane_pid(&session_name).map(procs::snapshot).unwrap_or_default();
+
+	Ok(serde_json::json!({
+		"session": session,
+		"work_dir": work_dir,
+		"task": task,
+		"timeline": timeline,
+		"claude": claude,
+		"preview": preview,
+		"pr": pr,
+		"processes": processes,
+	}))
+}
+
+/// All `key: value` pairs of a task's frontmatter
+fn frontmatter_map(path: &Path) -> serde_json::Map<String, serde_json::Value> {
+	let mut map = serde_json::Map::new();
+	let Ok(content) = fs::read_to_string(path) else {
+		return map;
+	};
+	let mut lines = content.lines();
+	if lines.next() != Some("---") {
+		return map;
+	}
+	for line in lines.take_while(|l| l.trim() != "---") {
+		if let Some((key, value)) = line.split_once(':') {
+			let value = value.trim().trim_matches('"');
+			map.insert(key.trim().to_string(), serde_json::Value::String(value.to_string()));
+		}
+	}
+	map
+}
+
+/// The open PR for the branch checked out in `dir`, via the GitHub CLI
+fn pull_request_info(dir: &Path) -> Option<serde_json::Value> {
+	if net::offline() {
+		return None;
+	}
+	let output = Command::new("gh")
+		.args(["pr", "view", "--json", "number,title,url,state,isDraft,reviewDecision,headRefName"])
+		.current_dir(dir)
+		.output()
+		.ok()
+		.filter(|o| o.status.success())?;
+	serde_json::from_slice(&output.stdout).ok()
+}
+
+/// Recent plain-text output of a live session, for the web dashboard's preview
+fn web_preview(cfg: &Config, name: &str) -> Option<Vec<String>> {
+	let session = format!("{SWARM_PREFIX}{}", name.trim_start_matches(SWARM_PREFIX));
+	if !list_sessions().ok()?.contains(&session) {
+		return None;
+	}
+	let log_path = Path::new(&cfg.general.logs_dir).join(format!("{session}.log"));
+	let lines = tail_lines(&log_path, PREVIEW_DEPTH).unwrap_or_default();
+	Some(clean_preview(&lines))
+}
+
+/// Approve or deny a waiting agent's prompt from the dashboard or a notification button.
+/// Only answers sessions that are still waiting, so a stale tap can't type into a running agent.
+fn web_answer(cfg: &Config, name: &str, approve: bool) -> Result<String> {
+	let session_name = format!("{SWARM_PREFIX}{}", name.trim_start_matches(SWARM_PREFIX));
+	let session = collect_sessions(cfg)?
+		.into_iter()
+		.find(|s| s.session_name == session_name)
+		.ok_or_else(|| anyhow::anyhow!("no such session: {name}"))?;
+	if session.status != AgentStatus::NeedsInput {
+		return Err(anyhow::anyhow!("{} isn't waiting for input", session.name));
+	}
+	record_input(cfg, &session_name);
+	match (session.agent.as_str(), approve) {
+		// Claude's permission menu has "Yes" selected; Esc is "No, tell Claude what to do"
+		("claude", true) => send_special_key(&session_name, "Enter")?,
+		("claude", false) => send_special_key(&session_name, "Escape")?,
+		(_, true) => send_keys(&session_name, "y")?,
+		(_, false) => send_keys(&session_name, "n")?,
+	}
+	Ok(format!("{} {}", if approve { "Approved" } else { "Denied" }, session.name))
+}
+
+/// This machine's fleet as published for teammates
+fn team_status(cfg: &Config, sessions: &[AgentSession]) -> TeamStatus {
+	let member = if cfg.team.name.trim().is_empty() {
+		claim_identity(cfg)
+	} else {
+		cfg.team.name.trim().to_string()
+	};
+	TeamStatus {
+		member,
+		updated_at: SystemTime::now()
+			.duration_since(SystemTime::UNIX_EPOCH)
+			.map(|d| d.as_secs())
+			.unwrap_or(0),
+		sessions: sessions
+			.iter()
+			.map(|s| TeamSession {
+				name: s.name.clone(),
+				status: s.status,
+				task: s.task.as_ref().map(|t| t.title.clone()),
+				activity: s.activity.clone(),
+			})
+			.collect(),
+		tasks: load_tasks(cfg)
+			.into_iter()
+			.map(|t| TeamTask {
+				title: t.title,
+				due: t.due.map(|d| d.to_string()),
+				status: t.status,
+			})
+			.collect(),
+	}
+}
+
+/// Fetch every [team] member's status and print one block per person
+fn print_fleet(cfg: &Config) -> Result<()> {
+	if cfg.team.members.is_empty() {
+		println!("No teammates configured - add their publish_to locations to [team] members");
+		return Ok(());
+	}
+	let members = cfg.team.members.clone();
+	let token = cfg.team.token.clone();
+	let results: Vec<(String, Result<TeamStatus>)> = std::thread::spawn(move || {
+		members
+			.into_iter()
+			.map(|m| {
+				let status = team::fetch(&m, &token);
+				(m, status)
+			})
+			.collect()
+	})
+	.join()
+	.map_err(|_| anyhow::anyhow!("fleet fetch thread panicked"))?;
+
+	let now = SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH)
+		.map(|d| d.as_secs())
+		.unwrap_or(0);
+	for (source, result) in results {
+		let status = match result {
+			Ok(status) => status,
+			Err(e) => {
+				println!("{}: unavailable ({})\n", source, e);
+				continue;
+			}
+		};
+		let snapshot = FleetSnapshot {
+			updated_at: status.updated_at,
+			sessions: status.sessions.iter().map(|s| (s.name.clone(), s.status)).collect(),
+		};
+		let age = format_human_duration(Duration::from_secs(now.saturating_sub(status.updated_at)));
+		println!("{}  {}  (updated {})", status.member, statusline_text(&snapshot), age);
+		for s in &status.sessions {
+			let detail = s.activity.as_ref().or(s.task.as_ref()).map(|d| format!("  · {d}")).unwrap_or_default();
+			println!("  {} {}{}", status_indicator(s.status, "emoji").0, s.name, detail);
+		}
+		let open = status.tasks.len();
+		if open > 0 {
+			println!("  {} open task{}", open, if open == 1 { "" } else { "s" });
+		}
+		println!();
+	}
+	Ok(())
+}
+
+/// Expand run-all arguments (files, directories, globs) into open tasks, due-date order
+fn collect_run_all_tasks(paths: &[String]) -> Result<Vec<TaskEntry>> {
+	let mut tasks: Vec<TaskEntry> = Vec::new();
+	for arg in paths {
+		let expanded = config::expand_path(arg);
+		let matches: Vec<PathBuf> = if expanded.contains(['*', '?', '[']) {
+			glob::glob(&expanded)
+				.with_context(|| format!("invalid pattern: {arg}"))?
+				.filter_map(|p| p.ok())
+				.collect()
+		} else {
+			vec![PathBuf::from(&expanded)]
+		};
+		if matches.is_empty() {
+			eprintln!("No files match {}", arg);
+		}
+		for path in matches {
+			if path.is_dir() {
+				tasks.extend(load_task_dir(&path));
+			} else if !path.exists() {
+				eprintln!("Not found: {}", path.display());
+			} else {
+				tasks.extend(load_task_entry(&path));
+			}
+		}
+	}
+	sort_tasks(&mut tasks);
+	tasks.dedup_by(|a, b| a.path == b.path);
+	Ok(tasks)
+}
+
+/// Start an agent per task, up to general.max_agents running at once
+fn run_all(cfg: &Config, paths: &[String], no_queue: bool, dry_run: bool) -> Result<()> {
+	let tasks = collect_run_all_tasks(paths)?;
+	if tasks.is_empty() {
+		println!("No open tasks found");
+		return Ok(());
+	}
+	let sessions = collect_sessions(cfg)?;
+	let limit = cfg.general.max_agents;
+	let free_slots = |running: usize| if limit == 0 { usize::MAX } else { limit.saturating_sub(running) };
+
+	let mut rows: Vec<(String, String, String)> = Vec::new(); // task, session, result
+	let mut queue: Vec<TaskEntry> = Vec::new();
+	let mut running = sessions.len();
+	for task in tasks {
+		let existing = find_sessions_for_task(&sessions, &task.path);
+		if let Some(s) = existing.first() {
+			rows.push((task.title.clone(), s.name.clone(), "already running".to_string()));
+		} else if free_slots(running) == 0 {
+			rows.push((task.title.clone(), "-".to_string(), "queued".to_string()));
+			queue.push(task);
+		} else if dry_run {
+			rows.push((task.title.clone(), "-".to_string(), "would start".to_string()));
+			running += 1;
+		} else {
+			match start_from_task(cfg, &task) {
+				Ok(name) => {
+					rows.push((task.title.clone(), name, "started".to_string()));
+					running += 1;
+				}
+				Err(e) => rows.push((task.title.clone(), "-".to_string(), format!("failed: {e}"))),
+			}
+		}
+	}
+	print_run_all_table(&rows);
+
+	if queue.is_empty() || no_queue || dry_run {
+		if !queue.is_empty() {
+			println!("{} queued (max_agents = {}); run again when agents finish", queue.len(), limit);
+		}
+		return Ok(());
+	}
+	println!(
+		"{} queued (max_agents = {}); starting them as agents finish. Ctrl-C to stop queueing.",
+		queue.len(),
+		limit
+	);
+	let poll = Duration::from_millis(cfg.general.poll_interval_ms.max(1000));
+	let mut queue = queue.into_iter();
+	let mut next = queue.next();
+	while let Some(task) = next.take() {
+		let running = list_sessions()?.len();
+		if free_slots(running) == 0 {
+			next = Some(task);
+			std::thread::sleep(poll);
+			continue;
+		}
+		match start_from_task(cfg, &task) {
+			Ok(name) => println!("Started {}{}  ({})", SWARM_PREFIX, name, task.title),
+			Err(e) => println!("Failed to start {}: {}", task.title, e),
+		}
+		// Give the new session a moment to show up in tmux before counting again
+		std::thread::sleep(Duration::from_millis(500));
+		next = queue.next();
+	}
+	println!("All queued tasks started");
+	Ok(())
+}
+
+fn print_run_all_table(rows: &[(String, String, String)]) {
+	let width = |col: fn(&(String, String, String)) -> &String, header: &str| {
+		rows.iter()
+			.map(|r| text::width(col(r)))
+			.chain([header.len()])
+			.max()
+			.unwrap_or(0)
+			.min(50)
+	};
+	let task_w = width(|r| &r.0, "TASK");
+	let session_w = width(|r| &r.1, "SESSION");
+	// Pad by display width so emoji and CJK titles keep the columns aligned
+	let fit = |value: &str, w: usize| text::pad(&text::truncate(value, w), w);
+	println!("{:<task_w$}  {:<session_w$}  RESULT", "TASK", "SESSION");
+	for (task, session, result) in rows {
+		println!("{}  {}  {}", fit(task, task_w), fit(session, session_w), result);
+	}
+}
+
+/// "90s", "30m", "1h", "1h30m", "2d" or plain seconds
+/// --model: a plain model name, since it ends up in the agent's shell command
+fn parse_model_arg(input: &str) -> Result<String> {
+	config::validate_model(input.trim())?;
+	Ok(input.trim().to_string())
+}
+
+fn parse_duration_arg(input: &str) -> Result<Duration> {
+	let input = input.trim();
+	let mut rest = input;
+	let mut secs = 0;
+	loop {
+		let (digits, tail) = rest.split_at(rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len()));
+		let value: u64 = digits
+			.parse()
+			.with_context(|| format!("invalid duration: {input} (use e.g. 90s, 30m, 1h)"))?;
+		let (unit, tail) = tail.split_at(tail.find(|c: char| c.is_ascii_digit()).unwrap_or(tail.len()));
+		secs += match unit {
+			"" | "s" => value,
+			"m" => value * 60,
+			"h" => value * 3600,
+			"d" => value * 86_400,
+			_ => return Err(anyhow::anyhow!("invalid duration unit in {input} (use s, m, h or d)")),
+		};
+		if tail.is_empty() {
+			return Ok(Duration::from_secs(secs));
+		}
+		rest = tail;
+	}
+}
+
+/// Poll until the condition holds. Returns the process exit code:
+/// 0 condition met, 2 timed out, 3 an agent errored or vanished before it was met
+fn wait_for_sessions(
+	cfg: &Config,
+	session: Option<&str>,
+	all: bool,
+	until: &str,
+	timeout: Option<Duration>,
+) -> Result<i32> {
+	let targets: Vec<String> = if all {
+		list_sessions()?
+	} else {
+		let name = session.unwrap_or_default().trim_start_matches(SWARM_PREFIX);
+		let full = format!("{SWARM_PREFIX}{name}");
+		if !list_sessions()?.contains(&full) {
+			return Err(anyhow::anyhow!("no session named {}", name));
+		}
+		vec![full]
+	};
+	if targets.is_empty() {
+		println!("No agents running");
+		return Ok(0);
+	}
+	let started = Instant::now();
+	let poll = Duration::from_millis(cfg.general.poll_interval_ms.max(500));
+	loop {
+		let live = list_sessions()?;
+		let mut finished = 0;
+		for target in &targets {
+			let name = target.trim_start_matches(SWARM_PREFIX);
+			// /done and `d` end the tmux session, so a vanished agent counts as finished
+			let status = if live.contains(target) {
+				collect_session(cfg, target)?.status
+			} else {
+				AgentStatus::Done
+			};
+			match (until, status) {
+				("needs_input", AgentStatus::NeedsInput) => {
+					println!("{}: needs input", name);
+					return Ok(0);
+				}
+				(_, AgentStatus::Error) => {
+					println!("{}: exited with an error", name);
+					return Ok(3);
+				}
+				("needs_input", AgentStatus::Done) if !all => {
+					println!("{}: finished without asking for input", name);
+					return Ok(3);
+				}
+				(_, AgentStatus::Done) => finished += 1,
+				_ => {}
+			}
+		}
+		if finished == targets.len() {
+			if until == "done" {
+				match targets.as_slice() {
+					[only] => println!("{}: done", only.trim_start_matches(SWARM_PREFIX)),
+					_ => println!("All {} agents done", targets.len()),
+				}
+				return Ok(0);
+			}
+			println!("All agents finished without asking for input");
+			return Ok(3);
+		}
+		if timeout.is_some_and(|t| started.elapsed() >= t) {
+			println!("Timed out after {}s", started.elapsed().as_secs());
+			return Ok(2);
+		}
+		std::thread::sleep(poll);
+	}
+}
+
+/// Print fleet state, then (with `follow`) one JSON line per change until stdout closes
+fn stream_events(cfg: &Config, follow: bool) -> Result<()> {
+	use std::io::Write;
+	let mut out = stdout();
+	let mut emit = |mut event: serde_json::Value| -> bool {
+		event["ts"] = serde_json::Value::String(Local::now().to_rfc3339());
+		// A closed pipe (e.g. `| head`) ends the stream quietly
+		writeln!(out, "{event}").and_then(|_| out.flush()).is_ok()
+	};
+	let session_event = |kind: &str, s: &AgentSession| {
+		serde_json::json!({
+			"event": kind,
+			"session": s.name,
+			"status": s.status,
+			"task": s.task.as_ref().map(|t| &t.title),
+		})
+	};
+
+	let mut sessions = collect_sessions(cfg)?;
+	let mut tasks = load_tasks(cfg);
+	for s in &sessions {
+		if !emit(session_event("session", s)) {
+			return Ok(());
+		}
+	}
+	if !follow {
+		return Ok(());
+	}
+	let poll = Duration::from_millis(cfg.general.poll_interval_ms.max(500));
+	loop {
+		std::thread::sleep(poll);
+		let Ok(updated) = collect_sessions(cfg) else {
+			continue;
+		};
+		let mut events = Vec::new();
+		for s in &updated {
+			match sessions.iter().find(|old| old.session_name == s.session_name) {
+				None => events.push(session_event("session_created", s)),
+				Some(old) if old.status != s.status => {
+					let mut event = session_event("status_changed", s);
+					event["from"] = serde_json::json!(old.status);
+					events.push(event);
+				}
+				Some(_) => {}
+			}
+		}
+		for old in sessions.iter().filter(|old| !updated.iter().any(|s| s.session_name == old.session_name)) {
+			events.push(session_event("session_closed", old));
+		}
+		// Done tasks drop out of load_tasks
+		let open = load_tasks(cfg);
+		for task in tasks.iter().filter(|t| !open.iter().any(|o| o.path == t.path)) {
+			events.push(serde_json::json!({
+				"event": "task_done",
+				"task": task.title,
+				"path": task.path,
+			}));
+		}
+		for event in events {
+			if !emit(event) {
+				return Ok(());
+			}
+		}
+		sessions = updated;
+		tasks = open;
+	}
+}
+
+/// Resolve a replay target: an existing file path, or a session name mapped to its log
+fn resolve_replay_log(cfg: &Config, target: &str) -> Result<PathBuf> {
+	let as_path = PathBuf::from(config::expand_path(target));
+	if as_path.is_file() {
+		return Ok(as_path);
+	}
+	let session = format!("{SWARM_PREFIX}{}", target.trim_start_matches(SWARM_PREFIX));
+	let log_path = Path::new(&cfg.general.logs_dir).join(format!("{session}.log"));
+	if log_path.is_file() {
+		Ok(log_path)
+	} else {
+		Err(anyhow::anyhow!(
+			"no log found for {} (looked for {})",
+			target,
+			log_path.display()
+		))
+	}
+}
+
+/// Logs and session-store dirs left behind by tmux sessions that are gone
+/// (pinned sessions' are kept)
+fn find_orphans(cfg: &Config, active_sessions: &[String]) -> Vec<PathBuf> {
+	let active: HashSet<String> = active_sessions.iter().cloned().collect();
+	let mut orphans = Vec::new();
+
+	if let Ok(entries) = fs::read_dir(&cfg.general.logs_dir) {
+		for entry in entries.flatten() {
+			let path = entry.path();
+			if !path.is_file() {
+				continue;
+			}
+			let name = entry.file_name().to_string_lossy().to_string();
+			if !(name.starts_with(SWARM_PREFIX) && name.ends_with(".log")) {
+				continue;
+			}
+			let session_name = name.trim_end_matches(".log");
+			// A pinned session's log outlives its tmux session until it's closed with d
+			if !active.contains(session_name) && !is_pinned(session_name) {
+				orphans.push(path);
+			}
+		}
+	}
+
+	if let Ok(dir) = session_store_dir() {
+		if let Ok(entries) = fs::read_dir(&dir) {
+			for entry in entries.flatten() {
+				let name = entry.file_name().to_string_lossy().to_string();
+				if !active.contains(&name) && !is_pinned(&name) {
+					// Note: We keep worktrees when sessions are cleaned up
+					// They can be manually cleaned with `git worktree remove`
+					orphans.push(entry.path());
+				}
+			}
+		}
+	}
+	orphans
+}
+
+/// Move orphaned logs and session data into ~/.swarm/trash (see `swarm clean`)
+fn cleanup_orphans(cfg: &Config, active_sessions: &[String]) {
+	let orphans = find_orphans(cfg, active_sessions);
+	if !orphans.is_empty() {
+		let _ = move_to_trash(&orphans);
+	}
+	purge_trash(cfg);
+}
+
+/// ~/.swarm/trash/<timestamp>/ for this batch, logging each move to logs/cleanup.log
+fn move_to_trash(paths: &[PathBuf]) -> Result<PathBuf> {
+	let base = config::base_dir()?;
+	let trash = base.join("trash");
+	fs::create_dir_all(&trash)?;
+	// Status commands clean up too, so several runs can trash in the same second:
+	// the pid and a counter keep their batches apart
+	let batch_stamp = format!("{}-{}", Local::now().format("%Y%m%d-%H%M%S"), std::process::id());
+	let mut n = 0;
+	let batch = loop {
+		n += 1;
+		let batch = trash.join(format!("{batch_stamp}-{n}"));
+		match fs::create_dir(&batch) {
+			Ok(()) => break batch,
+			Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+			Err(e) => return Err(e.into()),
+		}
+	};
+	fs::create_dir_all(base.join("logs"))?;
+	let mut log = fs::OpenOptions::new().create(true).append(true).open(base.join("logs").join("cleanup.log"))?;
+	use std::io::Write;
+	let stamp = Local::now().format("%Y-%m-%d %H:%M:%S");
+	for path in paths {
+		let Some(name) = path.file_name() else {
+			continue;
+		};
+		let dest = batch.join(name);
+		// rename fails across filesystems (logs_dir on another disk); copy then
+		let moved = fs::rename(path, &dest).is_ok()
+			|| (copy_recursive(path, &dest).is_ok()
+				&& if path.is_dir() { fs::remove_dir_all(path) } else { fs::remove_file(path) }.is_ok());
+		if moved {
+			writeln!(log, "[{stamp}] moved {} → {}", path.display(), dest.display())?;
+		} else {
+			writeln!(log, "[{stamp}] couldn't move {}", path.display())?;
+		}
+	}
+	Ok(batch)
+}
+
+/// Trash batches older than general.trash_days
+fn expired_trash(cfg: &Config) -> Vec<PathBuf> {
+	let Ok(entries) = config::base_dir().and_then(|d| Ok(fs::read_dir(d.join("trash"))?)) else {
+		return Vec::new();
+	};
+	let cutoff = Local::now().naive_local() - chrono::Duration::days(cfg.general.trash_days as i64);
+	let mut expired: Vec<PathBuf> = entries
+		.flatten()
+		.filter(|e| {
+			// "20250314-093000-4242-1": the stamp, then the pid and counter
+			let name = e.file_name().to_string_lossy().into_owned();
+			name.get(..15)
+				.and_then(|stamp| chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%d-%H%M%S").ok())
+				.is_some_and(|t| t < cutoff)
+		})
+		.map(|e| e.path())
+		.collect();
+	expired.sort();
+	expired
+}
+
+/// Remove expired trash (throttled to once a day via the trash dir's marker)
+fn purge_trash(cfg: &Config) {
+	let Ok(trash) = config::base_dir().map(|d| d.join("trash")) else {
+		return;
+	};
+	let marker = trash.join(".last-purge");
+	let recent = fs::metadata(&marker)
+		.and_then(|m| m.modified())
+		.ok()
+		.and_then(|t| t.elapsed().ok())
+		.is_some_and(|age| age < Duration::from_secs(86_400));
+	if recent || !trash.is_dir() {
+		return;
+	}
+	for batch in expired_trash(cfg) {
+		let _ = fs::remove_dir_all(batch);
+	}
+	let _ = fs::write(marker, "");
+}
+
+/// `swarm clean`: list (or with apply, trash) orphaned session data and purge old trash
+fn run_clean(cfg: &Config, apply: bool) -> Result<()> {
+	let orphans = find_orphans(cfg, &list_sessions()?);
+	let expired = expired_trash(cfg);
+	if orphans.is_empty() && expired.is_empty() {
+		println!("Nothing to clean");
+		return Ok(());
+	}
+	let verb = if apply { "" } else { "would " };
+	for path in &orphans {
+		println!("{verb}move to trash: {}", path.display());
+	}
+	for path in &expired {
+		println!("{verb}delete expired trash (> {} days): {}", cfg.general.trash_days, path.display());
+	}
+	if !apply {
+		println!("Dry run - rerun with --apply to do it");
+		return Ok(());
+	}
+	if !orphans.is_empty() {
+		let batch = move_to_trash(&orphans)?;
+		println!("Moved {} item(s) to {} (log: ~/.swarm/logs/cleanup.log)", orphans.len(), batch.display());
+	}
+	for path in &expired {
+		fs::remove_dir_all(path)?;
+	}
+	Ok(())
+}
+
+/// How often a quiet session's health check is repeated
+const HEALTH_CHECK_EVERY: Duration = Duration::from_secs(30);
+
+/// For an agent quiet past its health_check_secs: a prompt on screen the log
+/// missed means NeedsInput, and no agent process left under the pane means
+/// Error. None while it looks alive (just thinking) or checks are off.
+fn health_check(session: &str, detection: &DetectionConfig, age: Duration) -> Option<AgentStatus> {
+	type Checks = std::collections::HashMap<String, (std::time::Instant, Option<AgentStatus>)>;
+	static CHECKS: once_cell::sync::Lazy<std::sync::Mutex<Checks>> = once_cell::sync::Lazy::new(Default::default);
+	if age < detection.health_check? {
+		return None;
+	}
+	if let Some((at, result)) = CHECKS.lock().unwrap_or_else(|e| e.into_inner()).get(session) {
+		if at.elapsed() < HEALTH_CHECK_EVERY {
+			return *result;
+		}
+	}
+	let screen = tmux::capture_tail(session, 40).unwrap_or_default();
+	let result = if detect_status(&screen, detection, None) == AgentStatus::NeedsInput {
+		Some(AgentStatus::NeedsInput)
+	} else if tmux::pane_pid(session).is_some_and(|pid| !procs::agent_alive(pid)) {
+		tracing::warn!(session, quiet_secs = age.as_secs(), "health check: no agent process under the pane");
+		Some(AgentStatus::Error)
+	} else {
+		None
+	};
+	CHECKS
+		.lock()
+		.unwrap_or_else(|e| e.into_inner())
+		.insert(session.to_string(), (std::time::Instant::now(), result));
+	result
+}
+
+fn latest_output_time(path: &Path) -> Option<SystemTime> {
+	fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// tmux session name for a requested agent name
+fn session_name_for(name: &str) -> String {
+	// Truncate name to avoid "file name too long" errors (macOS limit is 255 bytes)
+	// Keep it under 100 chars to leave room for session prefix and other path components
+	let raw_name = name.trim_start_matches(SWARM_PREFIX);
+	let clean_name = if raw_name.len() > 100 {
+		raw_name.chars().take(100).collect::<String>()
+	} else {
+		raw_name.to_string()
+	};
+	format!("{SWARM_PREFIX}{clean_name}")
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_new(
+	cfg: &Config,
+	name: String,
+	agent: String,
+	repo: String,
+	prompt: Option<String>,
+	task: Option<String>,
+	auto_accept: bool,
+	model: Option<String>,
+	base: Option<&str>,
+	announce: bool,
+) -> Result<()> {
+	let model = cfg.model_for(&agent, model.as_deref());
+	// It goes into the shell command; the new-agent dialog ends up here too
+	if let Some(model) = &model {
+		config::validate_model(model)?;
+	}
+	let session = session_name_for(&name);
+	let target_dir = resolve_repo_path(&repo)?;
+	let canonical = target_dir.canonicalize().unwrap_or_else(|_| target_dir.clone());
+	let main_repo = main_repo_dir(&canonical);
+	cfg.repos.check(&canonical, main_repo.as_deref(), auto_accept)?;
+
+	// A worktree without its own .swarm.toml (e.g. uncommitted) uses the main repo's
+	let project = match project::load(&target_dir)? {
+		Some(project) => Some(project),
+		None => main_repo.as_deref().map(project::load).transpose()?.flatten(),
+	};
+	if let Some(main_repo) = &main_repo {
+		let extra = project.as_ref().map(|p| &p.worktrees);
+		let copy: Vec<&String> = cfg.worktrees.copy.iter().chain(extra.into_iter().flat_map(|w| &w.copy)).collect();
+		let symlink: Vec<&String> = cfg.worktrees.symlink.iter().chain(extra.into_iter().flat_map(|w| &w.symlink)).collect();
+		seed_worktree(main_repo, &canonical, &copy, &symlink)?;
+	}
+	let create_ctx = hooks::HookContext {
+		event: "create",
+		session: session.clone(),
+		name: session.trim_start_matches(SWARM_PREFIX).to_string(),
+		agent: agent.clone(),
+		status: "starting".to_string(),
+		work_dir: Some(target_dir.clone()),
+		task: task.as_deref().map(|t| PathBuf::from(config::expand_path(t))),
+	};
+	hooks::run_blocking(&cfg.hooks.on_create, &create_ctx)?;
+	if let Some(project) = &project {
+		confirm_project_trust(project, main_repo.as_deref().unwrap_or(&canonical), announce)?;
+		hooks::run_blocking(&project.hooks.on_create, &create_ctx)?;
+		let failures = project::run_checks(project, &target_dir);
+		if !failures.is_empty() {
+			return Err(anyhow::anyhow!(
+				"{} check(s) failed in {}: {}",
+				project::FILE_NAME,
+				target_dir.display(),
+				failures.join(", ")
+			));
+		}
+	}
+
+	if let Some(task_path) = &task {
+		claim_task(cfg, Path::new(&config::expand_path(task_path)))?;
+		let marker = session_task_path(&session)?;
+		fs::write(&marker, task_path)?;
+		// Also write .claude-task to repo root so Claude can find it after context compaction
+		let claude_task_marker = target_dir.join(".claude-task");
+		fs::write(&claude_task_marker, format!("{}\n", task_path))?;
+	}
+
+	{
+		let agent_marker = session_agent_path(&session)?;
+		fs::write(&agent_marker, &agent)?;
+	}
+
+	if let Some(model) = &model {
+		fs::write(session_model_path(&session)?, model)?;
+	}
+
+	// Mark YOLO mode sessions so we can show a warning indicator
+	if auto_accept {
+		let yolo_marker = session_yolo_path(&session)?;
+		fs::write(&yolo_marker, "1")?;
+	}
+
+	// Build the command with optional initial prompt
+	// Include worktree hint for implementation tasks
+	// Only name the base when it's safe inside the shell command below
+	let base = worktree_base(cfg, project.as_ref(), main_repo.as_deref().unwrap_or(&canonical), base);
+	let base_note = if base.chars().all(|c| c.is_ascii_alphanumeric() || "/._-".contains(c)) {
+		format!(" branching from {base}")
+	} else {
+		String::new()
+	};
+	let worktree_note = format!("\n\nIMPORTANT: If this task involves writing code (not just research), ask the user: \"Do you want me to create a git worktree for isolation?\" If yes, call \\`/worktree\\` to set up an isolated workspace{base_note}.");
+	// A one-line task gets specced with the /interview flow before any work starts
+	let interview_task = task
+		.as_ref()
+		.filter(|t| prompt.is_none() && wants_interview(cfg, &agent, Path::new(&config::expand_path(t))));
+	if let Some(task_path) = interview_task {
+		mark_interviewing(&session, Path::new(&config::expand_path(task_path)))?;
+	}
+	let initial_prompt = prompt.clone().map(|p| {
+		format!("{}{}", p, worktree_note)
+	}).or_else(|| {
+		interview_task.map(|task_path| interview_prompt(task_path))
+	}).or_else(|| {
+		task.as_ref().map(|task_path| {
+			format!(
+				"Starting task. Read {} for context (include any Process Log). Summarize the task file before acting.{}",
+				task_path,
+				worktree_note
+			)
+		})
+	});
+
+	// Write .claude/settings.local.json with allowed tools before starting Claude
+	if agent == "claude" && !auto_accept {
+		// Expand tasks_dir path (resolves ~ to home directory)
+		let tasks_dir = config::expand_path(&cfg.general.tasks_dir);
+		let mut allowed: Vec<String> = vec![
+			"Read(~/.swarm/tasks/**)".to_string(),
+			format!("Read({}/**)", tasks_dir),
+		];
+		allowed.extend(cfg.allowed_tools.get_all_tools());
+
+		// Expand additional directories (resolve ~ to home)
+		let additional_dirs: Vec<String> = cfg
+			.allowed_tools
+			.additional_directories
+			.iter()
+			.map(|d| config::expand_path(d))
+			.collect();
+
+		let settings_json = serde_json::json!({
+			"permissions": {
+				"allow": allowed,
+				"additionalDirectories": additional_dirs
+			}
+		});
+
+		let claude_dir = target_dir.join(".claude");
+		fs::create_dir_all(&claude_dir)?;
+		let settings_path = claude_dir.join("settings.local.json");
+		fs::write(&settings_path, serde_json::to_string_pretty(&settings_json)?)?;
+	}
+
+	// Build Claude command
 	let command = if agent == "claude" {
 		let mut parts = vec!["claude".to_string()];
 		if auto_accept {
@@ -605,15 +2562,23 @@ fn handle_new(
 			parts.push("--permission-mode".to_string());
 			parts.push("acceptEdits".to_string());
 		}
+		if let Some(model) = &model {
+			parts.push("--model".to_string());
+			parts.push(model.clone());
+		}
 		// Add prompt
 		if let Some(p) = &initial_prompt {
 			parts.push(format!("\"{}\"", p.replace('"', "\\\"")));
 		}
 		parts.join(" ")
 	} else {
+		let codex = match &model {
+			Some(model) => format!("codex --model {}", model),
+			None => "codex".to_string(),
+		};
 		match (agent.as_str(), &initial_prompt) {
-			("codex", Some(p)) => format!("codex \"{}\"", p.replace('"', "\\\"")),
-			("codex", None) => "codex".to_string(),
+			("codex", Some(p)) => format!("{} \"{}\"", codex, p.replace('"', "\\\"")),
+			("codex", None) => codex,
 			(other, Some(p)) => format!("{} \"{}\"", other, p.replace('"', "\\\"")),
 			(other, None) => other.to_string(),
 		}
@@ -626,6 +2591,10 @@ fn handle_new(
 	} else {
 		start_session(&session, &target_dir, &command)?;
 	}
+	// Your own prompt is the first thing you asked it (shown in Details, recalled with ↑)
+	if let Some(p) = &prompt {
+		let _ = input_history::record(&session, p);
+	}
 
 	// Small delay to let tmux session initialize before setting up pipe
 	std::thread::sleep(std::time::Duration::from_millis(100));
@@ -633,7 +2602,11 @@ fn handle_new(
 	let log_path = Path::new(&cfg.general.logs_dir).join(format!("{session}.log"));
 	// Pipe setup is best-effort - session is already running
 	if let Err(e) = ensure_pipe(&session, &log_path) {
-		eprintln!("Warning: pipe setup failed for {}: {}", session, e);
+		if announce {
+			eprintln!("Warning: pipe setup failed for {}: {}", session, e);
+		} else {
+			console::error("pipe setup", format!("{session}: {e}"));
+		}
 	}
 
 	if announce {
@@ -662,6 +2635,135 @@ fn resolve_repo_path(input: &str) -> Result<PathBuf> {
 	Ok(path)
 }
 
+/// Refuse to run `.swarm.toml` commands that haven't been trusted for `repo`.
+/// With `ask` (`swarm new` on a terminal), list them and offer to trust them there.
+fn confirm_project_trust(project: &project::Project, repo: &Path, ask: bool) -> Result<()> {
+	use std::io::{IsTerminal, Write};
+	let untrusted = project::untrusted(project, repo);
+	if untrusted.is_empty() {
+		return Ok(());
+	}
+	if ask && std::io::stdin().is_terminal() {
+		print_project_commands(repo, &untrusted);
+		print!("Trust them and continue? [y/N] ");
+		std::io::stdout().flush()?;
+		let mut answer = String::new();
+		std::io::stdin().read_line(&mut answer)?;
+		if answer.trim().eq_ignore_ascii_case("y") {
+			return project::trust(repo, project);
+		}
+	}
+	Err(anyhow::anyhow!(
+		"{} in {} has commands you haven't trusted - review them with `swarm trust {}`",
+		project::FILE_NAME,
+		repo.display(),
+		repo.display()
+	))
+}
+
+fn print_project_commands(repo: &Path, commands: &[&str]) {
+	println!("{} in {} runs:", project::FILE_NAME, repo.display());
+	for command in commands {
+		println!("  {command}");
+	}
+}
+
+/// `swarm trust`: show the repo's `.swarm.toml` commands and task template and allow them
+fn trust_repo(repo: &str, yes: bool) -> Result<()> {
+	let dir = resolve_repo_path(repo)?;
+	let canonical = dir.canonicalize().unwrap_or_else(|_| dir.clone());
+	let main_repo = main_repo_dir(&canonical);
+	// Same lookup as a launch: the worktree's own file, else the main repo's
+	let project = match project::load(&dir)? {
+		Some(project) => project,
+		None => main_repo
+			.as_deref()
+			.map(project::load)
+			.transpose()?
+			.flatten()
+			.ok_or_else(|| anyhow::anyhow!("no {} in {}", project::FILE_NAME, dir.display()))?,
+	};
+	let repo = main_repo.as_deref().unwrap_or(&canonical);
+	let commands = project.commands();
+	if commands.is_empty() && project.task_template.is_none() {
+		println!("{} in {} has no commands or task template to trust", project::FILE_NAME, repo.display());
+		return Ok(());
+	}
+	if !commands.is_empty() {
+		print_project_commands(repo, &commands);
+	}
+	if let Some(template) = &project.task_template {
+		println!("Task template: {template}");
+	}
+	if !yes {
+		use std::io::Write;
+		print!("Trust these? [y/N] ");
+		std::io::stdout().flush()?;
+		let mut answer = String::new();
+		std::io::stdin().read_line(&mut answer)?;
+		if !answer.trim().eq_ignore_ascii_case("y") {
+			println!("Not trusted");
+			return Ok(());
+		}
+	}
+	project::trust(repo, &project)?;
+	println!("Trusted {} until its {} changes", repo.display(), project::FILE_NAME);
+	Ok(())
+}
+
+/// Run the global and repo hooks for `event` ("done" or "kill") on `session`
+fn run_hook(cfg: &Config, event: &'static str, session: &AgentSession) {
+	let pick = |hooks: &config::Hooks| match event {
+		"done" => hooks.on_done.clone(),
+		"kill" => hooks.on_kill.clone(),
+		_ => String::new(),
+	};
+	let work_dir = session_work_dir(session);
+	// Repo hooks only once trusted (`swarm trust`); these run in the background, so just log it
+	let repo_hooks = work_dir.as_deref().and_then(|dir| {
+		let project = project::load(dir).ok().flatten()?;
+		let canonical = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+		let repo = main_repo_dir(&canonical).unwrap_or(canonical);
+		if !project::untrusted(&project, &repo).is_empty() {
+			tracing::warn!(session = %session.session_name, repo = %repo.display(), "skipping untrusted .swarm.toml hooks");
+			return None;
+		}
+		Some(project.hooks)
+	});
+	let ctx = hooks::HookContext {
+		event,
+		session: session.session_name.clone(),
+		name: session.name.clone(),
+		agent: session.agent.clone(),
+		status: session.status.label().replace(' ', "_"),
+		work_dir,
+		task: session.task.as_ref().map(|t| t.path.clone()),
+	};
+	// One after the other, in a single background run
+	let commands: Vec<String> = std::iter::once(pick(&cfg.hooks))
+		.chain(repo_hooks.as_ref().map(pick))
+		.filter(|c| !c.trim().is_empty())
+		.collect();
+	if !commands.is_empty() {
+		hooks::run(&commands.join("\n"), ctx);
+	}
+}
+
+/// The repository a worktree was created from (None outside git or in the main checkout)
+fn main_repo_dir(dir: &Path) -> Option<PathBuf> {
+	let output = Command::new("git")
+		.args(["rev-parse", "--path-format=absolute", "--git-common-dir"])
+		.current_dir(dir)
+		.output()
+		.ok()?;
+	if !output.status.success() {
+		return None;
+	}
+	let common = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+	let repo = common.parent()?.to_path_buf();
+	(repo != dir).then_some(repo)
+}
+
 fn task_info_for_session(session: &str) -> Result<Option<TaskInfo>> {
 	if let Some(info) = task_info_from_session_store(session)? {
 		return Ok(Some(info));
@@ -677,54 +2779,305 @@ fn task_info_for_session(session: &str) -> Result<Option<TaskInfo>> {
 	Ok(read_task_info_from_marker(&marker))
 }
 
-fn agent_for_session(session: &str) -> Result<String> {
-	if let Ok(marker) = session_agent_path(session) {
-		if let Ok(val) = fs::read_to_string(&marker) {
-			let trimmed = val.trim();
-			if !trimmed.is_empty() {
-				return Ok(trimmed.to_string());
-			}
+fn agent_for_session(session: &str) -> Result<String> {
+	if let Ok(marker) = session_agent_path(session) {
+		if let Ok(val) = fs::read_to_string(&marker) {
+			let trimmed = val.trim();
+			if !trimmed.is_empty() {
+				return Ok(trimmed.to_string());
+			}
+		}
+	}
+	Ok("claude".to_string())
+}
+
+fn task_info_from_session_store(session: &str) -> Result<Option<TaskInfo>> {
+	let marker = session_task_path(session)?;
+	if !marker.exists() {
+		return Ok(None);
+	}
+	Ok(read_task_info_from_marker(&marker))
+}
+
+fn session_task_path(session: &str) -> Result<PathBuf> {
+	let dir = session_store_dir()?.join(session);
+	fs::create_dir_all(&dir)?;
+	Ok(dir.join("task"))
+}
+
+fn session_agent_path(session: &str) -> Result<PathBuf> {
+	let dir = session_store_dir()?.join(session);
+	fs::create_dir_all(&dir)?;
+	Ok(dir.join("agent"))
+}
+
+fn session_yolo_path(session: &str) -> Result<PathBuf> {
+	let dir = session_store_dir()?.join(session);
+	fs::create_dir_all(&dir)?;
+	Ok(dir.join("yolo"))
+}
+
+fn session_worktree_path(session: &str) -> Result<PathBuf> {
+	let dir = session_store_dir()?.join(session);
+	fs::create_dir_all(&dir)?;
+	Ok(dir.join("worktree"))
+}
+
+fn session_stage_path(session: &str) -> Result<PathBuf> {
+	let dir = session_store_dir()?.join(session);
+	fs::create_dir_all(&dir)?;
+	Ok(dir.join("stage"))
+}
+
+/// Present while a session interviews on its task; holds the task's status at start
+fn session_interview_path(session: &str) -> Result<PathBuf> {
+	let dir = session_store_dir()?.join(session);
+	fs::create_dir_all(&dir)?;
+	Ok(dir.join("interview"))
+}
+
+/// Whether starting `agent` on `task` should begin with an interview
+fn wants_interview(cfg: &Config, agent: &str, task: &Path) -> bool {
+	agent == "claude" && cfg.general.auto_interview && is_one_line_task(task)
+}
+
+fn mark_interviewing(session: &str, task: &Path) -> Result<()> {
+	fs::write(session_interview_path(session)?, parse_status(task).unwrap_or_default())?;
+	Ok(())
+}
+
+/// Whether the session is still interviewing: its task's frontmatter status
+/// hasn't changed since the interview started
+fn is_interviewing(session: &str, task: Option<&TaskInfo>) -> bool {
+	let Ok(marker) = session_interview_path(session) else {
+		return false;
+	};
+	let Ok(started) = fs::read_to_string(&marker) else {
+		return false;
+	};
+	let status = task.and_then(|t| parse_status(&t.path)).unwrap_or_default();
+	if status != started.trim() {
+		let _ = fs::remove_file(&marker);
+		return false;
+	}
+	true
+}
+
+fn is_yolo_session(session: &str) -> bool {
+	session_yolo_path(session)
+		.map(|p| p.exists())
+		.unwrap_or(false)
+}
+
+fn session_input_path(session: &str) -> Result<PathBuf> {
+	let dir = session_store_dir()?.join(session);
+	fs::create_dir_all(&dir)?;
+	Ok(dir.join("input_offset"))
+}
+
+/// Log length when input was last sent from swarm, so status detection skips
+/// prompts that were already answered. Ignored once the log is truncated.
+fn input_offset(session: &str, log_path: &Path) -> Option<u64> {
+	let offset: u64 = fs::read_to_string(session_input_path(session).ok()?).ok()?.trim().parse().ok()?;
+	let len = fs::metadata(log_path).ok()?.len();
+	(offset <= len).then_some(offset)
+}
+
+fn record_input(cfg: &Config, session: &str) {
+	let log_path = Path::new(&cfg.general.logs_dir).join(format!("{session}.log"));
+	let len = fs::metadata(log_path).map(|m| m.len()).unwrap_or(0);
+	if let Ok(path) = session_input_path(session) {
+		let _ = fs::write(path, len.to_string());
+	}
+}
+
+/// Time spent needing input: "<total secs>" plus " <unix start>" while blocked
+fn session_blocked_path(session: &str) -> Result<PathBuf> {
+	let dir = session_store_dir()?.join(session);
+	fs::create_dir_all(&dir)?;
+	Ok(dir.join("blocked"))
+}
+
+fn read_blocked(session: &str) -> (u64, Option<i64>) {
+	let content = session_blocked_path(session)
+		.ok()
+		.and_then(|p| fs::read_to_string(p).ok())
+		.unwrap_or_default();
+	let mut parts = content.split_whitespace();
+	let total = parts.next().and_then(|t| t.parse().ok()).unwrap_or(0);
+	(total, parts.next().and_then(|t| t.parse().ok()))
+}
+
+/// Start or stop the blocked clock when a session enters or leaves NeedsInput
+fn record_blocked(session: &str, blocked: bool) {
+	let (mut total, since) = read_blocked(session);
+	let now = Local::now().timestamp();
+	if let Some(since) = since {
+		total += (now - since).max(0) as u64;
+	}
+	let content = if blocked { format!("{total} {now}") } else { total.to_string() };
+	if let Ok(path) = session_blocked_path(session) {
+		let _ = fs::write(path, content);
+	}
+}
+
+/// Seconds blocked so far, including a wait still in progress
+fn blocked_secs(session: &str) -> u64 {
+	let (total, since) = read_blocked(session);
+	total + since.map(|s| (Local::now().timestamp() - s).max(0) as u64).unwrap_or(0)
+}
+
+fn session_limits_path(session: &str) -> Result<PathBuf> {
+	let dir = session_store_dir()?.join(session);
+	fs::create_dir_all(&dir)?;
+	Ok(dir.join("limits.toml"))
+}
+
+fn write_session_limits(session: &str, limits: &SessionLimits) -> Result<()> {
+	fs::write(session_limits_path(session)?, toml::to_string(limits)?)?;
+	Ok(())
+}
+
+/// The session's own limits with [limits] filling the gaps (0 = no limit)
+fn effective_limits(cfg: &Config, session: &AgentSession) -> SessionLimits {
+	let own: SessionLimits = session_limits_path(&session.session_name)
+		.ok()
+		.and_then(|p| fs::read_to_string(p).ok())
+		.and_then(|s| toml::from_str(&s).ok())
+		.unwrap_or_default();
+	let default_action = if session.is_yolo { &cfg.limits.yolo_action } else { &cfg.limits.action };
+	SessionLimits {
+		max_minutes: own.max_minutes.or(Some(cfg.limits.max_minutes)).filter(|m| *m > 0),
+		max_cost: own.max_cost.or(Some(cfg.limits.max_cost)).filter(|c| *c > 0.0),
+		action: own.action.or_else(|| Some(default_action.clone())),
+	}
+}
+
+/// Which limit a session has passed, if any: ("ran 45m (limit 30m)", action)
+fn limit_breach(cfg: &Config, session: &AgentSession, cost: Option<f64>) -> Option<(String, String)> {
+	let limits = effective_limits(cfg, session);
+	let action = limits.action.unwrap_or_else(|| "pause".to_string());
+	if let Some(max) = limits.max_minutes {
+		let minutes = tmux::session_created(&session.session_name)
+			.and_then(|t| SystemTime::now().duration_since(t).ok())
+			.map(|d| d.as_secs() / 60)
+			.unwrap_or(0);
+		if minutes >= max {
+			return Some((format!("ran {minutes}m (limit {max}m)"), action));
+		}
+	}
+	if let (Some(max), Some(cost)) = (limits.max_cost, cost) {
+		if cost >= max {
+			return Some((format!("spent ~${cost:.2} (limit ${max:.2})"), action));
+		}
+	}
+	None
+}
+
+/// Soft deadline: "<budget secs> <unix start>", the start being when the session began
+fn session_deadline_path(session: &str) -> Result<PathBuf> {
+	let dir = session_store_dir()?.join(session);
+	fs::create_dir_all(&dir)?;
+	Ok(dir.join("deadline"))
+}
+
+/// Set (or with None clear) how long the session should take in total
+fn write_session_deadline(session: &str, budget: Option<Duration>) -> Result<()> {
+	let path = session_deadline_path(session)?;
+	let Some(budget) = budget else {
+		if path.exists() {
+			fs::remove_file(path)?;
 		}
-	}
-	Ok("claude".to_string())
+		return Ok(());
+	};
+	let started = tmux::session_created(session).unwrap_or_else(SystemTime::now);
+	let started = started.duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+	fs::write(path, format!("{} {started}", budget.as_secs()))?;
+	Ok(())
 }
 
-fn task_info_from_session_store(session: &str) -> Result<Option<TaskInfo>> {
-	let marker = session_task_path(session)?;
-	if !marker.exists() {
-		return Ok(None);
-	}
-	Ok(read_task_info_from_marker(&marker))
+/// (runtime so far, budget) for a session with a soft deadline
+fn read_session_deadline(session: &str) -> Option<(Duration, Duration)> {
+	let content = fs::read_to_string(session_deadline_path(session).ok()?).ok()?;
+	let mut parts = content.split_whitespace().map(|p| p.parse::<u64>().ok());
+	let (budget, started) = (parts.next()??, parts.next()??);
+	let elapsed = SystemTime::now()
+		.duration_since(SystemTime::UNIX_EPOCH + Duration::from_secs(started))
+		.unwrap_or_default();
+	Some((elapsed, Duration::from_secs(budget)))
 }
 
-fn session_task_path(session: &str) -> Result<PathBuf> {
+fn session_model_path(session: &str) -> Result<PathBuf> {
 	let dir = session_store_dir()?.join(session);
 	fs::create_dir_all(&dir)?;
-	Ok(dir.join("task"))
+	Ok(dir.join("model"))
 }
 
-fn session_agent_path(session: &str) -> Result<PathBuf> {
+fn session_group_path(session: &str) -> Result<PathBuf> {
 	let dir = session_store_dir()?.join(session);
 	fs::create_dir_all(&dir)?;
-	Ok(dir.join("agent"))
+	Ok(dir.join("group"))
 }
 
-fn session_yolo_path(session: &str) -> Result<PathBuf> {
+/// Freeform notes on a session, one per line
+fn session_notes_path(session: &str) -> Result<PathBuf> {
 	let dir = session_store_dir()?.join(session);
 	fs::create_dir_all(&dir)?;
-	Ok(dir.join("yolo"))
+	Ok(dir.join("notes"))
 }
 
-fn session_worktree_path(session: &str) -> Result<PathBuf> {
+fn read_session_notes(session: &str) -> Vec<String> {
+	session_notes_path(session)
+		.ok()
+		.and_then(|p| fs::read_to_string(p).ok())
+		.map(|content| content.lines().map(str::trim).filter(|l| !l.is_empty()).map(str::to_string).collect())
+		.unwrap_or_default()
+}
+
+fn write_session_notes(session: &str, notes: &[String]) -> Result<()> {
+	let path = session_notes_path(session)?;
+	if notes.is_empty() {
+		let _ = fs::remove_file(path);
+	} else {
+		fs::write(path, notes.join("\n") + "\n")?;
+	}
+	Ok(())
+}
+
+/// Present when the session is pinned (P)
+fn session_pin_path(session: &str) -> Result<PathBuf> {
 	let dir = session_store_dir()?.join(session);
 	fs::create_dir_all(&dir)?;
-	Ok(dir.join("worktree"))
+	Ok(dir.join("pinned"))
 }
 
-fn is_yolo_session(session: &str) -> bool {
-	session_yolo_path(session)
-		.map(|p| p.exists())
-		.unwrap_or(false)
+/// Doesn't create the session's store dir, so orphan cleanup can ask too
+fn is_pinned(session: &str) -> bool {
+	session_store_dir().is_ok_and(|dir| dir.join(session).join("pinned").exists())
+}
+
+/// Marks a fan-out planner; holds the task file it's splitting up
+fn session_fanout_path(session: &str) -> Result<PathBuf> {
+	let dir = session_store_dir()?.join(session);
+	fs::create_dir_all(&dir)?;
+	Ok(dir.join("fanout"))
+}
+
+fn session_variant_path(session: &str) -> Result<PathBuf> {
+	let dir = session_store_dir()?.join(session);
+	fs::create_dir_all(&dir)?;
+	Ok(dir.join("variant"))
+}
+
+/// Variant marker: "<group> <index> <total>"
+fn get_variant_info(session: &str) -> Option<VariantInfo> {
+	let content = fs::read_to_string(session_variant_path(session).ok()?).ok()?;
+	let mut parts = content.split_whitespace();
+	Some(VariantInfo {
+		group: parts.next()?.to_string(),
+		index: parts.next()?.parse().ok()?,
+		total: parts.next()?.parse().ok()?,
+	})
 }
 
 fn get_worktree_path(session: &str) -> Option<PathBuf> {
@@ -734,6 +3087,21 @@ fn get_worktree_path(session: &str) -> Option<PathBuf> {
 		.map(|s| PathBuf::from(s.trim()))
 }
 
+/// Pipeline stage for a session, from its stage marker and the task's `stages:` frontmatter
+fn get_stage_info(session: &str, task: Option<&TaskInfo>) -> Option<StageInfo> {
+	let index = session_stage_path(session)
+		.ok()
+		.and_then(|p| fs::read_to_string(&p).ok())
+		.and_then(|s| s.trim().parse::<usize>().ok())?;
+	let stages = pipeline::parse_stages(&task?.path);
+	let stage = stages.get(index)?;
+	Some(StageInfo {
+		index,
+		total: stages.len(),
+		name: stage.name.clone(),
+	})
+}
+
 fn read_task_info_from_marker(marker: &Path) -> Option<TaskInfo> {
 	let target_path = fs::read_to_string(marker)
 		.ok()
@@ -742,17 +3110,30 @@ fn read_task_info_from_marker(marker: &Path) -> Option<TaskInfo> {
 	Some(build_task_info(PathBuf::from(target_path)))
 }
 
-/// Find existing session for a task (by matching task path)
+/// Find existing sessions for a task (by matching task path)
+fn find_sessions_for_task<'a>(
+	sessions: &'a [AgentSession],
+	task_path: &Path,
+) -> Vec<&'a AgentSession> {
+	sessions
+		.iter()
+		.filter(|s| {
+			s.task
+				.as_ref()
+				.map(|t| t.path == task_path)
+				.unwrap_or(false)
+		})
+		.collect()
+}
+
+/// Pick the linked session that most needs attention (e.g. the one waiting for input)
 fn find_session_for_task<'a>(
 	sessions: &'a [AgentSession],
 	task_path: &Path,
 ) -> Option<&'a AgentSession> {
-	sessions.iter().find(|s| {
-		s.task
-			.as_ref()
-			.map(|t| t.path == task_path)
-			.unwrap_or(false)
-	})
+	find_sessions_for_task(sessions, task_path)
+		.into_iter()
+		.min_by_key(|s| s.status.attention_rank())
 }
 
 fn build_task_info(task_path: PathBuf) -> TaskInfo {
@@ -764,14 +3145,23 @@ fn build_task_info(task_path: PathBuf) -> TaskInfo {
 				.to_string_lossy()
 				.into_owned()
 		});
+		let priority = parse_frontmatter(&task_path, "priority").map(|p| p.to_uppercase());
+		let tags = parse_tags(&task_path);
+		let due = parse_due(&task_path);
 		TaskInfo {
 			path: task_path,
 			title,
+			priority,
+			tags,
+			due,
 		}
 	} else {
 		TaskInfo {
 			path: task_path,
 			title: "Missing task file".to_string(),
+			priority: None,
+			tags: Vec::new(),
+			due: None,
 		}
 	}
 }
@@ -786,7 +3176,7 @@ fn extract_title(path: &Path) -> Option<String> {
 	None
 }
 
-fn parse_due(path: &Path) -> Option<NaiveDate> {
+fn parse_due(path: &Path) -> Option<Due> {
 	let content = fs::read_to_string(path).ok()?;
 	let mut lines = content.lines();
 	if lines.next()? != "---" {
@@ -798,9 +3188,8 @@ fn parse_due(path: &Path) -> Option<NaiveDate> {
 		}
 		let trimmed = line.trim();
 		if let Some(rest) = trimmed.strip_prefix("due:") {
-			let val = rest.trim().trim_matches('"').trim();
-			if let Ok(date) = NaiveDate::parse_from_str(val, "%Y-%m-%d") {
-				return Some(date);
+			if let Some(due) = Due::parse(rest.trim().trim_matches('"')) {
+				return Some(due);
 			}
 		}
 	}
@@ -825,6 +3214,155 @@ fn parse_status(path: &Path) -> Option<String> {
 	None
 }
 
+/// `tags: [backend, urgent]` (or `tags: backend, urgent`) from the frontmatter
+fn parse_tags(path: &Path) -> Vec<String> {
+	parse_frontmatter(path, "tags")
+		.unwrap_or_default()
+		.trim_matches(['[', ']'])
+		.split(',')
+		.map(|t| t.trim().trim_matches(['"', '\'', '#']).to_string())
+		.filter(|t| !t.is_empty())
+		.collect()
+}
+
+/// Value of a `key:` line in the task's frontmatter
+fn parse_frontmatter(path: &Path, key: &str) -> Option<String> {
+	let content = fs::read_to_string(path).ok()?;
+	let mut lines = content.lines();
+	if lines.next()? != "---" {
+		return None;
+	}
+	let prefix = format!("{key}:");
+	for line in lines {
+		let trimmed = line.trim();
+		if trimmed == "---" {
+			break;
+		}
+		if let Some(rest) = trimmed.strip_prefix(&prefix) {
+			let value = rest.trim().trim_matches('"');
+			return (!value.is_empty()).then(|| value.to_string());
+		}
+	}
+	None
+}
+
+/// Set (or with None, remove) a frontmatter key. Written via a temp file and rename
+/// so a syncing tasks_dir never sees a half-written task.
+fn set_frontmatter(path: &Path, key: &str, value: Option<&str>) -> Result<()> {
+	let content = fs::read_to_string(path)?;
+	let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+	let prefix = format!("{key}:");
+	let close = if lines.first().map(|l| l.trim()) == Some("---") {
+		lines.iter().skip(1).position(|l| l.trim() == "---").map(|i| i + 1)
+	} else {
+		None
+	};
+	match close {
+		Some(close) => {
+			let existing = (1..close).find(|&i| lines[i].trim_start().starts_with(&prefix));
+			match (existing, value) {
+				(Some(i), Some(v)) => lines[i] = format!("{key}: {v}"),
+				(Some(i), None) => {
+					lines.remove(i);
+				}
+				(None, Some(v)) => lines.insert(close, format!("{key}: {v}")),
+				(None, None) => return Ok(()),
+			}
+		}
+		None => {
+			let Some(v) = value else {
+				return Ok(());
+			};
+			lines.splice(0..0, ["---".to_string(), format!("{key}: {v}"), "---".to_string()]);
+		}
+	}
+	let mut updated = lines.join("\n");
+	if content.ends_with('\n') {
+		updated.push('\n');
+	}
+	let tmp = path.with_extension("md.swarm-tmp");
+	fs::write(&tmp, updated)?;
+	fs::rename(&tmp, path)?;
+	Ok(())
+}
+
+/// Claims older than this are treated as abandoned (crashed machine, forgotten agent)
+const CLAIM_STALE_HOURS: i64 = 24;
+
+/// Name written into claimed_by: (config claim_name, else user@hostname)
+fn claim_identity(cfg: &Config) -> String {
+	static DEFAULT: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+	if !cfg.general.claim_name.trim().is_empty() {
+		return cfg.general.claim_name.trim().to_string();
+	}
+	DEFAULT
+		.get_or_init(|| {
+			let user = std::env::var("USER").unwrap_or_else(|_| "someone".to_string());
+			let host = Command::new("hostname")
+				.arg("-s")
+				.output()
+				.ok()
+				.and_then(|o| String::from_utf8(o.stdout).ok())
+				.map(|h| h.trim().to_string())
+				.filter(|h| !h.is_empty())
+				.unwrap_or_else(|| "localhost".to_string());
+			format!("{user}@{host}")
+		})
+		.clone()
+}
+
+/// Someone else's live claim on a task, if any
+fn foreign_claim(cfg: &Config, path: &Path) -> Option<String> {
+	let owner = parse_frontmatter(path, "claimed_by")?;
+	if owner == claim_identity(cfg) {
+		return None;
+	}
+	let stale = parse_frontmatter(path, "claimed_at")
+		.and_then(|at| chrono::DateTime::parse_from_rfc3339(&at).ok())
+		.map(|at| Local::now().signed_duration_since(at) > chrono::Duration::hours(CLAIM_STALE_HOURS))
+		.unwrap_or(false);
+	(!stale).then_some(owner)
+}
+
+/// Refuse tasks a teammate has claimed; otherwise claim it (when claim_tasks is on)
+fn claim_task(cfg: &Config, path: &Path) -> Result<()> {
+	if let Some(owner) = foreign_claim(cfg, path) {
+		return Err(anyhow::anyhow!(
+			"task is claimed by {} - remove claimed_by: from {} to take it over",
+			owner,
+			path.display()
+		));
+	}
+	if !cfg.general.claim_tasks {
+		return Ok(());
+	}
+	let me = claim_identity(cfg);
+	set_frontmatter(path, "claimed_by", Some(&me))?;
+	set_frontmatter(path, "claimed_at", Some(&Local::now().to_rfc3339()))?;
+	// Two machines can claim at once on a synced drive; the last write wins, so re-check
+	if parse_frontmatter(path, "claimed_by").as_deref() != Some(me.as_str()) {
+		return Err(anyhow::anyhow!("lost the claim on {} to another machine", path.display()));
+	}
+	Ok(())
+}
+
+/// Drop our claim once no agent of ours is working on the task
+fn release_task_claim(cfg: &Config, path: &Path) {
+	if parse_frontmatter(path, "claimed_by").as_deref() != Some(claim_identity(cfg).as_str()) {
+		return;
+	}
+	let still_running = list_sessions().unwrap_or_default().iter().any(|s| {
+		task_info_for_session(s)
+			.ok()
+			.flatten()
+			.is_some_and(|t| t.path == path)
+	});
+	if !still_running {
+		let _ = set_frontmatter(path, "claimed_by", None);
+		let _ = set_frontmatter(path, "claimed_at", None);
+	}
+}
+
 fn parse_summary(path: &Path) -> Option<String> {
 	let content = fs::read_to_string(path).ok()?;
 	let mut lines = content.lines();
@@ -843,68 +3381,464 @@ fn parse_summary(path: &Path) -> Option<String> {
 	None
 }
 
-fn format_due(date: NaiveDate) -> String {
-	let today = Local::now().date_naive();
-	let days = date.signed_duration_since(today).num_days();
+/// Lines of the task's "## Process Log" section (up to the next `## ` heading)
+fn parse_process_log(path: &Path) -> Option<Vec<String>> {
+	let content = fs::read_to_string(path).ok()?;
+	let mut lines = content.lines();
+	lines.by_ref().find(|l| l.trim().eq_ignore_ascii_case("## Process Log"))?;
+	Some(
+		lines
+			.take_while(|l| !l.starts_with("## "))
+			.map(|l| l.to_string())
+			.collect(),
+	)
+}
+
+/// Whether a task is just its title: nothing but headings and blank lines
+/// outside the frontmatter, "When done" and "Process Log"
+fn is_one_line_task(path: &Path) -> bool {
+	let Ok(content) = fs::read_to_string(path) else {
+		return false;
+	};
+	let body = content
+		.strip_prefix("---\n")
+		.and_then(|c| c.split_once("\n---"))
+		.map_or(content.as_str(), |(_, rest)| rest);
+	let mut skipping = false;
+	!body.lines().map(str::trim).any(|line| {
+		if let Some(section) = line.strip_prefix("## ") {
+			skipping = ["when done", "process log"].contains(&section.trim().to_lowercase().as_str());
+			return false;
+		}
+		!skipping && !line.is_empty() && !line.starts_with('#')
+	})
+}
+
+/// First message for an auto-interview: the /interview command, pointed at the
+/// task, ending with the status change that tells swarm the interview is over.
+/// Escaped for the double-quoted agent command line.
+fn interview_prompt(task_path: &str) -> String {
+	let steps = HOOK_INTERVIEW.replace('`', "\\`").replace('$', "\\$");
+	format!(
+		"This task is only a one-liner, so interview me about it before doing anything else. The task file is {task_path}.\n\n{steps}\nWhen the spec is written, change status: in the task's frontmatter to ready and stop; I'll start the implementation."
+	)
+}
+
+/// Style a process log line, highlighting a leading date/time stamp
+fn process_log_line(line: &str) -> Line<'static> {
+	static STAMP: once_cell::sync::Lazy<regex::Regex> = once_cell::sync::Lazy::new(|| {
+		regex::Regex::new(r"^(\s*(?:[-*]|#+)?\s*)(\[?\d{4}-\d{2}-\d{2}(?:[ T]\d{1,2}:\d{2}(?::\d{2})?)?\]?|\[?\d{1,2}:\d{2}\]?)(.*)$").unwrap()
+	});
+	if let Some(caps) = STAMP.captures(line) {
+		Line::from(vec![
+			Span::raw(caps[1].to_string()),
+			Span::styled(caps[2].to_string(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+			Span::raw(caps[3].to_string()),
+		])
+	} else if line.trim_start().starts_with('#') {
+		Line::from(Span::styled(line.to_string(), Style::default().add_modifier(Modifier::BOLD)))
+	} else {
+		Line::from(line.to_string())
+	}
+}
+
+fn format_due(due: Due) -> String {
+	let now = Local::now().naive_local();
+	// Within a day of a timed deadline, count down (or up) instead
+	if let Some(at) = due.at() {
+		let left = at - now;
+		if left.num_seconds() >= 0 && left.num_hours() < 24 {
+			// Rounded up, so 59m30s left reads "due in 1h" rather than "59m"
+			return format!("due in {}", format_duration_short(left.to_std().unwrap_or_default() + Duration::from_secs(59)));
+		}
+		if left.num_seconds() < 0 && left.num_hours() > -24 {
+			return format!("overdue {}", format_duration_short((-left).to_std().unwrap_or_default()));
+		}
+	}
+	let time = due.time.map(|t| format!(" {}", dates::time(t))).unwrap_or_default();
+	let days = due.date.signed_duration_since(now.date()).num_days();
 	match days {
-		0 => "due today".to_string(),
-		1 => "due tomorrow".to_string(),
-		d if d > 1 && d <= 7 => format!("due in {}d", d),
+		0 => format!("due today{time}"),
+		1 => format!("due tomorrow{time}"),
+		d if (2..=7).contains(&d) => format!("due in {}d", d),
 		-1 => "due yesterday".to_string(),
-		d if d < -1 && d >= -7 => format!("due {}d ago", -d),
-		_ => format!("due {}", date.format("%b %-d")),
+		d if (-7..=-2).contains(&d) => format!("due {}d ago", -d),
+		_ => format!("due {}{time}", dates::short_date(due.date)),
+	}
+}
+
+/// Days the agenda lays out: this week and next
+const AGENDA_DAYS: i64 = 14;
+
+/// Days from the start of this week (per [dates] week_start) to `today`
+fn agenda_lead(today: NaiveDate) -> i64 {
+	today.weekday().days_since(dates::week_start()) as i64
+}
+
+/// Indexes into `tasks` due on `day`, earliest first; today also lists overdue tasks
+fn agenda_tasks(tasks: &[TaskEntry], day: NaiveDate, today: NaiveDate) -> Vec<usize> {
+	let mut due: Vec<usize> = tasks
+		.iter()
+		.enumerate()
+		.filter(|(_, t)| t.due.is_some_and(|d| d.date == day || (day == today && d.date < today)))
+		.map(|(i, _)| i)
+		.collect();
+	due.sort_by_key(|&i| tasks[i].due);
+	due
+}
+
+/// Point the tasks view's selection at the agenda's task `idx` on the day
+/// `offset` days from today (none on an empty day); returns the clamped index
+fn sync_agenda_selection(tasks: &[TaskEntry], offset: i64, idx: usize, tasks_state: &mut ListState) -> usize {
+	let today = Local::now().date_naive();
+	let due = agenda_tasks(tasks, today + chrono::Duration::days(offset), today);
+	let idx = idx.min(due.len().saturating_sub(1));
+	tasks_state.select(due.get(idx).copied());
+	idx
+}
+
+fn is_overdue(task: &TaskEntry) -> bool {
+	let now = Local::now().naive_local();
+	task.due
+		.map(|d| match d.at() {
+			Some(at) => at < now,
+			None => d.date < now.date(),
+		})
+		.unwrap_or(false)
+}
+
+/// Remind about timed tasks `[notifications] due_reminder` before they're due,
+/// once per task and deadline. Returns the status line for the last one sent.
+fn send_due_reminders(cfg: &Config, tasks: &[TaskEntry], sessions: &[AgentSession]) -> Option<String> {
+	let lead = parse_duration_arg(&cfg.notifications.due_reminder).ok()?;
+	let lead = chrono::Duration::from_std(lead).ok()?;
+	let marker = config::base_dir().ok()?.join(".due-reminders");
+	// "<task path>\t<due>" per reminder already sent
+	let sent = fs::read_to_string(&marker).unwrap_or_default();
+	let now = Local::now().naive_local();
+	let mut status = None;
+	let mut keep: Vec<String> = Vec::new();
+	for task in tasks {
+		let (Some(due), Some(at)) = (task.due, task.due.and_then(|d| d.at())) else {
+			continue;
+		};
+		let key = format!("{}\t{}", task.path.display(), due);
+		if sent.lines().any(|l| l == key) {
+			keep.push(key);
+			continue;
+		}
+		if now < at - lead || now >= at {
+			continue;
+		}
+		let left = format_duration_short((at - now).to_std().unwrap_or_default() + Duration::from_secs(59));
+		let message = match find_session_for_task(sessions, &task.path) {
+			Some(s) => format!("{} is due in {} - agent {} is {}", task.title, left, s.name, s.status.label()),
+			None => format!("{} is due in {} - no agent on it yet", task.title, left),
+		};
+		notify::notify_due(&message, &cfg.notifications.sound_needs_input);
+		status = Some(format!("⏰ {message}"));
+		keep.push(key);
+	}
+	// Only remember reminders for deadlines that still exist
+	if status.is_some() || keep.len() != sent.lines().count() {
+		let _ = fs::write(&marker, keep.join("\n"));
+	}
+	status
+}
+
+/// Send the morning digest (overdue + due today) once per day after digest_time
+fn maybe_send_daily_digest(cfg: &Config, tasks: &[TaskEntry], sessions: &[AgentSession]) {
+	let Ok(digest_time) = chrono::NaiveTime::parse_from_str(cfg.notifications.digest_time.trim(), "%H:%M") else {
+		return; // Empty or invalid time disables the digest
+	};
+	let now = Local::now();
+	if now.time() < digest_time {
+		return;
+	}
+	let Ok(marker) = config::base_dir().map(|d| d.join(".last-digest")) else {
+		return;
+	};
+	let today = now.date_naive().format("%Y-%m-%d").to_string();
+	if fs::read_to_string(&marker).map(|s| s.trim() == today).unwrap_or(false) {
+		return;
+	}
+	let _ = fs::write(&marker, &today);
+
+	let describe = |t: &TaskEntry| match find_session_for_task(sessions, &t.path) {
+		Some(s) => format!("{} ({})", t.title, s.name),
+		None => t.title.clone(),
+	};
+	let overdue: Vec<String> = tasks.iter().filter(|t| is_overdue(t)).map(describe).collect();
+	let due_today: Vec<String> = tasks
+		.iter()
+		.filter(|t| t.due.is_some_and(|d| d.date == now.date_naive()))
+		.map(describe)
+		.collect();
+	if overdue.is_empty() && due_today.is_empty() {
+		return;
+	}
+	let mut parts = Vec::new();
+	if !overdue.is_empty() {
+		parts.push(format!("{} overdue: {}", overdue.len(), overdue.join(", ")));
 	}
+	if !due_today.is_empty() {
+		parts.push(format!("{} due today: {}", due_today.len(), due_today.join(", ")));
+	}
+	notify::notify_digest(&parts.join(" · "), &cfg.notifications.sound_needs_input);
 }
 
 fn load_tasks(cfg: &Config) -> Vec<TaskEntry> {
-	let dir = PathBuf::from(&cfg.general.tasks_dir);
+	let mut tasks = load_task_dir(Path::new(&cfg.general.tasks_dir));
+	sort_tasks(&mut tasks);
+	tasks
+}
+
+/// Open tasks directly in `dir` (subdirectories such as archive/ are skipped),
+/// minus files its .swarmignore hides and snoozed tasks
+fn load_task_dir(dir: &Path) -> Vec<TaskEntry> {
+	let ignores = task_ignores(dir);
+	let now = Local::now().naive_local();
 	let mut tasks = Vec::new();
-	if let Ok(entries) = fs::read_dir(&dir) {
+	if let Ok(entries) = fs::read_dir(dir) {
 		for entry in entries.flatten() {
-			let path = entry.path();
-			if path.is_dir() {
-				if path.file_name().map(|n| n == "archive").unwrap_or(false) {
-					continue;
-				}
-				continue;
+			if !task_ignored(&ignores, &entry.file_name().to_string_lossy())
+				&& task_snoozed_until(&entry.path()).is_none_or(|until| until <= now)
+			{
+				tasks.extend(load_task_entry(&entry.path()));
 			}
-			if let Some(ext) = path.extension() {
-				if ext == "md" {
-					if path.file_stem().map(|s| s == "README").unwrap_or(false) {
-						continue;
-					}
-					let status = parse_status(&path);
-					if let Some(s) = status.as_deref() {
-						if s == "done" || s == "completed" {
-							continue;
-						}
-					}
-					// Prefer summary over title for display
-					let title = parse_summary(&path)
-						.or_else(|| extract_title(&path))
-						.unwrap_or_else(|| {
-							path.file_stem()
-								.unwrap_or_default()
-								.to_string_lossy()
-								.into_owned()
-						});
-					let due = parse_due(&path);
-					tasks.push(TaskEntry { title, path: path.clone(), due, status });
-				}
+		}
+	}
+	tasks
+}
+
+/// When a snoozed task comes back: its `snoozed_until:` (a date alone means 9:00)
+fn task_snoozed_until(path: &Path) -> Option<chrono::NaiveDateTime> {
+	let until = Due::parse(&parse_frontmatter(path, "snoozed_until")?)?;
+	Some(until.at().unwrap_or_else(|| until.date.and_time(SNOOZE_MORNING)))
+}
+
+/// Snoozed tasks in tasks_dir with when each comes back, soonest first
+fn snoozed_tasks(cfg: &Config) -> Vec<(PathBuf, chrono::NaiveDateTime)> {
+	let dir = Path::new(&cfg.general.tasks_dir);
+	let ignores = task_ignores(dir);
+	let mut snoozed: Vec<_> = fs::read_dir(dir)
+		.into_iter()
+		.flatten()
+		.flatten()
+		.filter(|e| !task_ignored(&ignores, &e.file_name().to_string_lossy()))
+		.filter_map(|e| Some((e.path(), task_snoozed_until(&e.path())?)))
+		.collect();
+	snoozed.sort_by_key(|(_, until)| *until);
+	snoozed
+}
+
+/// Tasks whose snooze ran out: snoozed_until: is removed so each comes back
+/// (and is announced) once, even if swarm wasn't running at the time
+fn wake_snoozed_tasks(cfg: &Config) -> Vec<TaskEntry> {
+	let now = Local::now().naive_local();
+	snoozed_tasks(cfg)
+		.into_iter()
+		.filter(|(_, until)| *until <= now)
+		.filter(|(path, _)| set_frontmatter(path, "snoozed_until", None).is_ok())
+		.filter_map(|(path, _)| load_task_entry(&path))
+		.collect()
+}
+
+/// "tomorrow" (and a date without a time) snoozes until this time
+const SNOOZE_MORNING: chrono::NaiveTime = chrono::NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+
+/// "tonight" snoozes until this time
+const SNOOZE_EVENING: chrono::NaiveTime = chrono::NaiveTime::from_hms_opt(20, 0, 0).unwrap();
+
+/// Choices Tab cycles through in the snooze prompt
+const SNOOZE_PRESETS: &[&str] = &["1h", "tonight", "tomorrow"];
+
+/// When a snooze typed as "1h", "tonight", "tomorrow", "17:30" (the next 17:30)
+/// or "2025-03-14 09:00" ends
+fn parse_snooze(input: &str) -> Result<chrono::NaiveDateTime> {
+	let input = input.trim().to_lowercase();
+	let now = Local::now().naive_local();
+	let today = now.date();
+	// A time of day that's already gone today means tomorrow
+	let next = |time: chrono::NaiveTime| {
+		let at = today.and_time(time);
+		if at > now {
+			at
+		} else {
+			at + chrono::Duration::days(1)
+		}
+	};
+	let until = match input.as_str() {
+		"tonight" => next(SNOOZE_EVENING),
+		"tomorrow" => (today + chrono::Days::new(1)).and_time(SNOOZE_MORNING),
+		_ => {
+			if let Ok(time) = chrono::NaiveTime::parse_from_str(&input, "%H:%M") {
+				next(time)
+			} else if let Some(until) = Due::parse(&input) {
+				until.at().unwrap_or_else(|| until.date.and_time(SNOOZE_MORNING))
+			} else {
+				let duration = parse_duration_arg(&input)
+					.map_err(|_| anyhow::anyhow!("try 1h, tonight, tomorrow, 17:30 or 2025-03-14 09:00"))?;
+				now + chrono::Duration::from_std(duration)?
 			}
 		}
+	};
+	if until <= now {
+		return Err(anyhow::anyhow!("{} has already passed", until.format("%Y-%m-%d %H:%M")));
+	}
+	Ok(until)
+}
+
+/// "20:00", "tomorrow 09:00", "Mar 14 09:00"
+fn describe_snooze(until: chrono::NaiveDateTime) -> String {
+	let days = until.date().signed_duration_since(Local::now().date_naive()).num_days();
+	match days {
+		0 => dates::time(until.time()),
+		1 => format!("tomorrow {}", dates::time(until.time())),
+		_ => format!("{} {}", dates::short_date(until.date()), dates::time(until.time())),
+	}
+}
+
+/// Rules from `dir/.swarmignore`: a glob per line matched against file names
+/// ("_template*.md", "*conflicted copy*"), # comments, ! to bring a file back
+fn task_ignores(dir: &Path) -> Vec<(bool, glob::Pattern)> {
+	let Ok(content) = fs::read_to_string(dir.join(".swarmignore")) else {
+		return Vec::new();
+	};
+	content
+		.lines()
+		.map(str::trim)
+		.filter(|line| !line.is_empty() && !line.starts_with('#'))
+		.filter_map(|line| {
+			let (keep, pattern) = match line.strip_prefix('!') {
+				Some(rest) => (true, rest),
+				None => (false, line),
+			};
+			let pattern = pattern.trim_start_matches('/').trim_end_matches('/');
+			glob::Pattern::new(pattern).ok().map(|p| (keep, p))
+		})
+		.collect()
+}
+
+/// Whether .swarmignore hides `name`; like .gitignore, the last matching rule wins
+fn task_ignored(ignores: &[(bool, glob::Pattern)], name: &str) -> bool {
+	ignores
+		.iter()
+		.rev()
+		.find(|(_, pattern)| pattern.matches(name))
+		.is_some_and(|(keep, _)| !keep)
+}
+
+/// A task file as a TaskEntry; None for non-markdown, README and done tasks
+fn load_task_entry(path: &Path) -> Option<TaskEntry> {
+	read_task_entry(path).filter(|t| !matches!(t.status.as_deref(), Some("done" | "completed")))
+}
+
+/// Any task file as a TaskEntry, done or not; None for non-markdown and README
+fn read_task_entry(path: &Path) -> Option<TaskEntry> {
+	if path.is_dir() || path.extension()? != "md" {
+		return None;
+	}
+	if path.file_stem().map(|s| s == "README").unwrap_or(false) {
+		return None;
+	}
+	let status = parse_status(path);
+	// Prefer summary over title for display
+	let title = parse_summary(path)
+		.or_else(|| extract_title(path))
+		.unwrap_or_else(|| {
+			path.file_stem()
+				.unwrap_or_default()
+				.to_string_lossy()
+				.into_owned()
+		});
+	let due = parse_due(path);
+	let claimed_by = parse_frontmatter(path, "claimed_by");
+	let tags = parse_tags(path);
+	let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+	Some(TaskEntry {
+		title,
+		path: path.to_path_buf(),
+		due,
+		status,
+		claimed_by,
+		tags,
+		modified,
+	})
+}
+
+/// How close a new task's title must be to an existing one to warn about it
+const SIMILAR_TASK_THRESHOLD: f64 = 0.7;
+
+/// The open, done or archived task most like `title`, if one is close enough
+fn similar_task(cfg: &Config, title: &str) -> Option<TaskEntry> {
+	let dir = Path::new(&cfg.general.tasks_dir);
+	let ignores = task_ignores(dir);
+	[dir.to_path_buf(), dir.join("archive")]
+		.iter()
+		.filter_map(|d| fs::read_dir(d).ok())
+		.flatten()
+		.flatten()
+		.filter(|e| !task_ignored(&ignores, &e.file_name().to_string_lossy()))
+		.filter_map(|e| read_task_entry(&e.path()))
+		.map(|t| {
+			let stem = t.path.file_stem().unwrap_or_default().to_string_lossy();
+			(text::similarity(title, &t.title).max(text::similarity(title, &stem)), t)
+		})
+		.filter(|(score, _)| *score >= SIMILAR_TASK_THRESHOLD)
+		.max_by(|a, b| a.0.total_cmp(&b.0))
+		.map(|(_, t)| t)
+}
+
+/// "fix-login-redirect (due Fri)", or "(archived)" / "(done)" for closed tasks
+fn describe_similar(task: &TaskEntry) -> String {
+	let state = if task.path.parent().is_some_and(|p| p.ends_with("archive")) {
+		"archived".to_string()
+	} else if matches!(task.status.as_deref(), Some("done" | "completed")) {
+		"done".to_string()
+	} else {
+		task.due.map(format_due).unwrap_or_else(|| "no due date".to_string())
+	};
+	format!("{} ({state})", task.path.file_stem().unwrap_or_default().to_string_lossy())
+}
+
+/// `swarm task add` found a near-duplicate: warn, and on a terminal ask first.
+/// False = don't create it.
+fn confirm_similar_task(cfg: &Config, similar: &TaskEntry) -> Result<bool> {
+	use std::io::{IsTerminal, Write};
+	eprintln!("⚠ Similar task exists: {}\n  {}", describe_similar(similar), similar.path.display());
+	if !std::io::stdin().is_terminal() {
+		return Ok(true);
+	}
+	eprint!("Create anyway? [y/N, o opens it] ");
+	std::io::stderr().flush()?;
+	let mut answer = String::new();
+	std::io::stdin().read_line(&mut answer)?;
+	match answer.trim().to_lowercase().as_str() {
+		"y" | "yes" => Ok(true),
+		"o" => {
+			obsidian::open(cfg, &similar.path);
+			println!("{}", similar.path.display());
+			Ok(false)
+		}
+		_ => {
+			eprintln!("Nothing written");
+			Ok(false)
+		}
 	}
+}
+
+fn sort_tasks(tasks: &mut [TaskEntry]) {
 	tasks.sort_by(|a, b| match (a.due, b.due) {
 		(Some(da), Some(db)) => da.cmp(&db),
 		(Some(_), None) => std::cmp::Ordering::Less,
 		(None, Some(_)) => std::cmp::Ordering::Greater,
 		(None, None) => a.title.to_lowercase().cmp(&b.title.to_lowercase()),
 	});
-	tasks
 }
 
 fn load_daily_logs(cfg: &Config) -> Vec<DailyEntry> {
-	let dir = PathBuf::from(&cfg.general.daily_dir);
+	let dir = obsidian::daily_dir(cfg);
 	let mut logs = Vec::new();
 	if let Ok(entries) = fs::read_dir(&dir) {
 		for entry in entries.flatten() {
@@ -919,28 +3853,24 @@ fn load_daily_logs(cfg: &Config) -> Vec<DailyEntry> {
 			} else {
 				continue;
 			}
-			// Parse date from filename (YYYY-MM-DD.md)
+			// Parse date from filename (YYYY-MM-DD.md, or the vault's daily note format)
 			if let Some(stem) = path.file_stem() {
 				let name = stem.to_string_lossy();
-				if let Ok(date) = chrono::NaiveDate::parse_from_str(&name, "%Y-%m-%d") {
-					// Get first non-empty, non-heading line as preview
+				if let Ok(date) = chrono::NaiveDate::parse_from_str(&name, obsidian::daily_format(cfg)) {
+					// Get first non-empty, non-heading line after any frontmatter as preview
 					let preview = fs::read_to_string(&path)
 						.ok()
 						.and_then(|content| {
-							content
-								.lines()
+							let body = content
+								.strip_prefix("---\n")
+								.and_then(|rest| rest.split_once("\n---\n"))
+								.map_or(content.as_str(), |(_, body)| body);
+							body.lines()
 								.find(|l| {
 									let trimmed = l.trim();
 									!trimmed.is_empty() && !trimmed.starts_with('#')
 								})
-								.map(|s| {
-									let s = s.trim().trim_start_matches("- ");
-									if s.len() > 50 {
-										format!("{}...", &s[..47])
-									} else {
-										s.to_string()
-									}
-								})
+								.map(|s| text::truncate(&text::sanitize(s.trim().trim_start_matches("- ")), 50))
 						})
 						.unwrap_or_default();
 					logs.push(DailyEntry { date, path: path.clone(), preview });
@@ -949,7 +3879,7 @@ fn load_daily_logs(cfg: &Config) -> Vec<DailyEntry> {
 		}
 	}
 	// Sort newest first
-	logs.sort_by(|a, b| b.date.cmp(&a.date));
+	logs.sort_by_key(|d| std::cmp::Reverse(d.date));
 	logs
 }
 
@@ -982,10 +3912,16 @@ fn task_preview(task: &TaskEntry, max_lines: usize) -> String {
 fn run_tui(cfg: &mut Config) -> Result<()> {
 	// Always install/update tmux config for easier keybindings
 	let _ = install_tmux_conf();
+	// Inside tmux, Alt+0 in an agent session switches back here
+	let dashboard_session = tmux::current_session();
+	if let (Some(session), Ok(path)) = (&dashboard_session, dashboard_session_path()) {
+		let _ = fs::write(path, session);
+	}
 
+	install_panic_hook();
 	enable_raw_mode()?;
 	let mut stdout_handle = stdout();
-	execute!(stdout_handle, EnterAlternateScreen)?;
+	execute!(stdout_handle, EnterAlternateScreen, EnableFocusChange, EnableBracketedPaste)?;
 	let backend = ratatui::backend::CrosstermBackend::new(stdout_handle);
 	let mut terminal = ratatui::Terminal::new(backend)?;
 
@@ -999,25 +3935,99 @@ fn run_tui(cfg: &mut Config) -> Result<()> {
 	let daily_logs = load_daily_logs(cfg);
 	let mut daily_state = ListState::default();
 	daily_state.select(Some(0));
-	let mut showing_tasks = false;
+	// Without tmux, agents run on swarm's own PTYs (no attach); start in the tasks view
+	let tmux_missing = !tmux::is_available();
+	let mut showing_tasks = tmux_missing;
 	let mut showing_daily = false;
 	let mut show_help = false;
+	let mut show_console = false;
+	// Process log viewer for a task file (v key), re-read every frame so it follows the agent
+	let mut process_log_view: Option<TaskInfo> = None;
+	// Fleet summary printed on quit
+	let tui_started = Instant::now();
+	let mut attached_total = Duration::ZERO;
+	let mut completed_this_run: Vec<String> = Vec::new();
+	// Set when returning from an attach; the next refresh reports what changed meanwhile
+	let mut attach_return: Option<AttachSnapshot> = None;
+	// Sessions already stopped (or warned) for passing --max-minutes/--max-cost
+	let mut limits_hit: HashSet<String> = HashSet::new();
+	// Sessions already notified for running past their soft deadline (--within, T)
+	let mut deadlines_hit: HashSet<String> = HashSet::new();
+	// Terminal focus (from focus events); while unfocused everything polls at blur_poll_ms
+	let mut focused = true;
 	// First-run hooks install prompt
 	let mut show_hooks_prompt = !cfg.general.hooks_installed;
 	// Always install/update hooks on startup (they're small, ensures latest version)
 	let _ = install_hooks();
 	// Auto-update on startup (checks once per day, shows changelog if we updated last run)
-	let (just_updated_version, changelog_notes) = auto_update_on_startup()
+	let (just_updated_version, changelog_notes) = auto_update_on_startup(&cfg.updates)
 		.map(|(v, n)| (Some(v), n))
 		.unwrap_or((None, None));
 	// Show changelog modal if we have release notes from an update
 	let mut show_changelog: Option<(String, String)> = just_updated_version
 		.as_ref()
 		.and_then(|v| changelog_notes.map(|n| (v.clone(), n)));
+	// Independent timers: session status, preview capture, task reload
 	let mut last_refresh = Instant::now();
+	let mut last_preview = Instant::now();
+	let mut last_tasks_reload = Instant::now();
+	// Processes under each session's pane (CPU, memory, runaways) for the Details pane
+	let mut proc_monitor = procs::Monitor::default();
+	let mut session_procs: std::collections::HashMap<String, Vec<procs::Proc>> = std::collections::HashMap::new();
+	let mut last_procs: Option<Instant> = None;
+	// Background git sync of tasks_dir; the first run happens right away
+	let task_sync_enabled =
+		cfg.task_sync.enabled && task_sync::is_repo(Path::new(&cfg.general.tasks_dir));
+	let mut last_task_sync: Option<Instant> = None;
+	let mut task_sync_rx: Option<std::sync::mpsc::Receiver<Result<String>>> = None;
+	// Team mode: publish fleet status for teammates' `swarm fleet`
+	let mut last_team_publish: Option<Instant> = None;
+	let mut team_publish_rx: Option<std::sync::mpsc::Receiver<Result<()>>> = None;
+	// Agents touching the same files in a repo, by session name (checked in the background)
+	let mut conflicts: std::collections::HashMap<String, Vec<conflicts::Conflict>> =
+		std::collections::HashMap::new();
+	let mut last_conflict_check: Option<Instant> = None;
+	let mut conflicts_rx: Option<
+		std::sync::mpsc::Receiver<std::collections::HashMap<String, Vec<conflicts::Conflict>>>,
+	> = None;
 	let mut status_message: Option<(String, Instant)> = None;
 	let mut send_input_mode = false;
 	let mut send_input_buf = String::new();
+	// Suggested answers for the prompt on screen; Left/Right picks one into the buffer
+	let mut replies: Vec<QuickReply> = Vec::new();
+	let mut reply_idx: Option<usize> = None;
+	// Up/Down recall of earlier messages; the typed draft is kept while browsing
+	let mut input_recall: Vec<String> = Vec::new();
+	let mut recall_idx: Option<usize> = None;
+	let mut recall_draft = String::new();
+	// `y` waits for what to copy: p preview, a attach command, t task path
+	let mut copy_mode = false;
+	// Groups (swarm new --group): G opens the group menu; collapsed groups show one row
+	let mut group_mode = false;
+	let mut collapsed_groups: HashSet<String> = HashSet::new();
+	let mut confirm_group_kill: Option<String> = None;
+	let mut broadcast_group: Option<String> = None;
+	// Agents list filter (/) and the note editor (N)
+	let mut agent_filter = String::new();
+	let mut filter_mode = false;
+	// Saved view ([[views]]) cycled with f: filters the agents and tasks lists. The
+	// tasks list keeps the view parsed and the shown task indices from the last redraw.
+	let mut active_view: Option<usize> = None;
+	let mut task_filter: Option<Filter> = None;
+	let mut shown: Vec<usize> = (0..tasks.len()).collect();
+	let mut notes_mode = false;
+	let mut notes_buf = String::new();
+	// Soft deadline prompt (T)
+	let mut deadline_mode = false;
+	let mut deadline_buf = String::new();
+	// Snooze prompt for the selected task (z in the tasks view)
+	let mut snooze_mode = false;
+	let mut snooze_buf = String::new();
+	let mut snoozed_count = snoozed_tasks(cfg).len();
+	// Fan-out subtasks waiting for a free slot (general.max_agents): task, group, planner's dir
+	let mut fanout_queue: Vec<(TaskEntry, String, Option<PathBuf>)> = Vec::new();
+	// YOLO sessions need a second Enter before input is sent (no permission gate there)
+	let mut yolo_send_armed = false;
 	// Confirmation mode for killing sessions (d key)
 	let mut confirm_kill_mode = false;
 	let mut pending_kill_session: Option<String> = None;
@@ -1026,7 +4036,46 @@ fn run_tui(cfg: &mut Config) -> Result<()> {
 	let mut new_agent_buf = String::new();
 	let mut new_agent_due = String::from("tomorrow"); // pre-filled, can be deleted
 	let mut new_agent_notify = String::from("no one"); // pre-filled, can be deleted
-	let mut new_agent_field = 0; // 0 = description, 1 = notify, 2 = due
+	let mut new_agent_model = String::new(); // blank = [models] default for the agent
+	let mut new_agent_field = 0; // 0 = description, 1 = notify, 2 = due, 3 = model
+	let mut new_agent_collision: Option<PathBuf> = None; // existing task file with the same slug
+	let mut new_agent_similar: Option<TaskEntry> = None; // open or archived task with a similar title
+	// Agenda layout of the tasks view (A): selected day (days from today) and task on it
+	let mut agenda_view = false;
+	let mut agenda_offset: i64 = 0;
+	let mut agenda_idx = 0;
+	// Handoff to a fresh agent (H key)
+	let mut handoff_mode = false;
+	// Side-by-side compare of two sessions (C marks the first, C on another opens it)
+	let mut compare_mark: Option<String> = None;
+	let mut compare_view: Option<(String, String)> = None;
+	let mut compare_diff = false;
+	let mut compare_diffs: (Vec<String>, Vec<String>) = (Vec::new(), Vec::new());
+	// Land modal (L): session name, steps, and updates from the background run
+	let mut land_view: Option<(String, Vec<land::Step>)> = None;
+	// History view (O): archived runs, the search typed after /, and the run opened with Enter
+	let mut history_view: Option<Vec<history::Run>> = None;
+	let mut history_idx: usize = 0;
+	let mut history_query = String::new();
+	let mut history_search = false;
+	let mut history_detail = false;
+	let mut history_scroll: u16 = 0;
+	let mut history_stats = false;
+	// Process picker (K): the selected session and the processes under its pane
+	let mut procs_view: Option<(String, Vec<procs::Proc>)> = None;
+	let mut procs_idx: usize = 0;
+	let mut procs_kill_armed = false;
+	// Command runner (:): the prompt, then the session, command and directory of the run on show
+	let mut run_mode = false;
+	let mut run_buf = String::new();
+	let mut run_view: Option<(String, String, PathBuf)> = None;
+	let mut land_rx: Option<std::sync::mpsc::Receiver<land::Update>> = None;
+	let mut land_cancel: Option<land::Cancel> = None;
+	let mut rename_mode = false;
+	let mut rename_buf = String::new();
+	let mut handoff_agent_buf = String::new();
+	let mut handoff_note_buf = String::new();
+	let mut handoff_field = 0; // 0 = agent, 1 = note
 	let pipe_status: std::collections::HashMap<String, String> =
 		std::collections::HashMap::new();
 	// Track previous status for each session to detect state changes for notifications
@@ -1035,8 +4084,22 @@ fn run_tui(cfg: &mut Config) -> Result<()> {
 		.iter()
 		.map(|s| (s.session_name.clone(), s.status))
 		.collect();
+	// Blocked clocks left running (or not started) while swarm was closed
+	for session in &sessions {
+		let blocked = session.status == AgentStatus::NeedsInput;
+		if read_blocked(&session.session_name).1.is_some() != blocked {
+			record_blocked(&session.session_name, blocked);
+		}
+	}
 	// Cache preview to avoid calling tmux capture-pane on every render frame
-	let mut cached_preview: Option<(String, Vec<String>)> = None; // (session_name, lines)
+	// Per session, re-captured only when the session has new output
+	// Parsed Claude transcripts (todos, last tool, turns) for the Details pane
+	let mut claude_states: std::collections::HashMap<String, claude_session::ClaudeState> =
+		std::collections::HashMap::new();
+	let mut preview_cache: std::collections::HashMap<String, PreviewCache> =
+		std::collections::HashMap::new();
+	let mut needs_redraw = true;
+	let mut last_draw = Instant::now();
 	// Status indicator style - can cycle with 's' key
 	let styles = ["unicode", "emoji", "text"];
 	let mut style_idx = styles
@@ -1054,397 +4117,1419 @@ fn run_tui(cfg: &mut Config) -> Result<()> {
 			.unwrap_or(false)
 		{
 			status_message = None;
+			needs_redraw = true;
 		}
 
-		terminal.draw(|f| {
-			let size = f.area();
+		// Only redraw when something changed (input, refresh, new preview), plus a
+		// periodic redraw for time-based UI (process log viewer, relative times)
+		if needs_redraw || (focused && last_draw.elapsed() >= Duration::from_secs(1)) {
+			if needs_redraw && showing_tasks {
+				shown = shown_tasks(&tasks, &sessions, task_filter.as_ref());
+			}
+			needs_redraw = false;
+			last_draw = Instant::now();
+			terminal.draw(|f| {
+				let size = f.area();
+
+				// Footer area (always at bottom); a status message or the input line needs a bordered 3 rows
+				let footer_height: u16 = if active_status.is_some() || send_input_mode {
+					3
+				} else {
+					2
+				};
+				let vertical = Layout::default()
+					.direction(Direction::Vertical)
+					.constraints(
+						[
+							Constraint::Min(3),
+							Constraint::Length(if show_console { 8 } else { 0 }),
+							Constraint::Length(footer_height + tmux_missing as u16),
+						]
+						.as_ref(),
+					)
+					.split(size);
 
-			// Footer area (always at bottom)
-			let vertical = Layout::default()
-				.direction(Direction::Vertical)
-				.constraints([Constraint::Min(3), Constraint::Length(2)].as_ref())
-				.split(size);
-
-			// Standard split chunks for all views
-			let split_chunks = Layout::default()
-				.direction(Direction::Horizontal)
-				.constraints([Constraint::Percentage(45), Constraint::Percentage(55)].as_ref())
-				.split(vertical[0]);
-
-			if showing_daily {
-				let chunks = &split_chunks;
-				// Daily logs view
-				let items: Vec<ListItem> = daily_logs
-					.iter()
-					.map(|d| {
-						let date_str = d.date.format("%Y-%m-%d").to_string();
-						let preview = if d.preview.is_empty() {
-							String::new()
-						} else {
-							format!(" · {}", d.preview)
-						};
-						ListItem::new(Line::from(Span::styled(
-							format!("• {}{}", date_str, preview),
-							Style::default(),
-						)))
-					})
-					.collect();
-				let list_title = "Daily Logs (o=open)".to_string();
-				let list = List::new(items)
-					.block(Block::default().borders(Borders::ALL).title(list_title))
-					.highlight_symbol("▶ ")
-					.highlight_style(
-						Style::default()
-							.add_modifier(Modifier::BOLD | Modifier::REVERSED)
-							.fg(Color::White),
+				// Standard split chunks for all views
+				let split_chunks = Layout::default()
+					.direction(Direction::Horizontal)
+					.constraints([Constraint::Percentage(45), Constraint::Percentage(55)].as_ref())
+					.split(vertical[0]);
+
+				if showing_daily {
+					let chunks = &split_chunks;
+					// Daily logs view
+					let items: Vec<ListItem> = daily_logs
+						.iter()
+						.map(|d| {
+							let date_str = d.date.format("%Y-%m-%d").to_string();
+							let preview = if d.preview.is_empty() {
+								String::new()
+							} else {
+								format!(" · {}", d.preview)
+							};
+							ListItem::new(Line::from(Span::styled(
+								format!("• {}{}", date_str, preview),
+								Style::default(),
+							)))
+						})
+						.collect();
+					let list_title = "Daily Logs (o=open)".to_string();
+					let list = List::new(items)
+						.block(Block::default().borders(Borders::ALL).title(list_title))
+						.highlight_symbol("▶ ")
+						.highlight_style(
+							Style::default()
+								.add_modifier(Modifier::BOLD | Modifier::REVERSED)
+								.fg(Color::White),
+						);
+					f.render_stateful_widget(list, chunks[0], &mut daily_state);
+
+					let preview_text = if let Some(sel) = daily_state
+						.selected()
+						.and_then(|idx| daily_logs.get(idx))
+					{
+						daily_preview(sel, 100)
+					} else if daily_logs.is_empty() {
+						String::from("No daily logs found\n\nRun /done at end of sessions to log work")
+					} else {
+						String::from("No log selected")
+					};
+					let preview = Paragraph::new(preview_text)
+						.block(Block::default().borders(Borders::ALL).title("Daily Log"))
+						.wrap(Wrap { trim: true });
+					f.render_widget(preview, chunks[1]);
+				} else if showing_tasks {
+					let chunks = &split_chunks;
+					// Build a set of task paths that have active sessions
+					let current_style = styles[style_idx];
+					let claim_me = claim_identity(cfg);
+					// Rows are positions in `shown`, not task indices
+					let selected_row = tasks_state.selected().and_then(|s| shown.iter().position(|&i| i == s));
+					let rows = visible_rows(
+						shown.len(),
+						selected_row,
+						tasks_state.offset(),
+						chunks[0].height.saturating_sub(2) as usize,
+						|row| 1 + find_sessions_for_task(&sessions, &tasks[shown[row]].path).len(),
 					);
-				f.render_stateful_widget(list, chunks[0], &mut daily_state);
-
-				let preview_text = if let Some(sel) = daily_state
-					.selected()
-					.and_then(|idx| daily_logs.get(idx))
-				{
-					daily_preview(sel, 100)
-				} else if daily_logs.is_empty() {
-					String::from("No daily logs found\n\nRun /done at end of sessions to log work")
+					*tasks_state.offset_mut() = rows.start;
+					let mut tasks_window =
+						ListState::default().with_selected(selected_row.filter(|r| rows.contains(r)).map(|r| r - rows.start));
+					let items: Vec<ListItem> = shown[rows.clone()]
+						.iter()
+						.map(|&idx| {
+							let t = &tasks[idx];
+							let due = t
+								.due
+								.map(|d| format!(" · {}", format_due(d)))
+								.unwrap_or_default();
+							let status_tag = t
+								.status
+								.as_ref()
+								.map(|s| format!("[{}] ", s))
+								.unwrap_or_default();
+							let linked = find_sessions_for_task(&sessions, &t.path);
+							let style = if is_overdue(t) {
+								Style::default().fg(Color::Red)
+							} else if !linked.is_empty() {
+								Style::default().fg(Color::Green)
+							} else {
+								Style::default()
+							};
+							// Show ● indicator (colored by aggregate status) if task has active sessions
+							let mut lines = vec![match AgentStatus::aggregate(linked.iter().map(|s| s.status)) {
+								Some(agg) => Line::from(vec![
+									Span::styled("● ", status_indicator(agg, "unicode").1),
+									Span::styled(format!("{}{}{}", status_tag, t.title, due), style),
+									Span::styled(format!(" · {}", agg.label()), Style::default().fg(Color::DarkGray)),
+								]),
+								None => Line::from(Span::styled(
+									format!("• {}{}{}", status_tag, t.title, due),
+									style,
+								)),
+							}];
+							if let Some(owner) = t.claimed_by.as_ref().filter(|o| **o != claim_me) {
+								lines[0].spans.push(Span::styled(
+									format!("  🔒 {}", owner),
+									Style::default().fg(Color::Yellow),
+								));
+							}
+							// Group linked sessions under the task
+							for s in &linked {
+								let (indicator, indicator_style) = status_indicator(s.status, current_style);
+								lines.push(Line::from(vec![
+									Span::styled("    └ ", Style::default().fg(Color::DarkGray)),
+									Span::styled(indicator, indicator_style),
+									Span::raw(format!(" {}", s.name)),
+								]));
+							}
+							ListItem::new(Text::from(lines))
+						})
+						.collect();
+					let overdue_count = tasks.iter().filter(|t| is_overdue(t)).count();
+					let mut list_title = if overdue_count > 0 {
+						format!("Tasks ({} overdue, enter=start)", overdue_count)
+					} else {
+						"Tasks (enter=start)".to_string()
+					};
+					if snoozed_count > 0 {
+						list_title = format!("{list_title} │ 💤 {snoozed_count} snoozed");
+					}
+					if let Some(view) = active_view.and_then(|i| cfg.views.get(i)) {
+						list_title = format!("{list_title} │ view: {} ({} shown)", view.name, shown.len());
+					}
+					let list = List::new(items)
+						.block(Block::default().borders(Borders::ALL).title(list_title))
+						.highlight_symbol("▶ ")
+						.highlight_style(
+							Style::default()
+								.add_modifier(Modifier::BOLD | Modifier::REVERSED)
+								.fg(Color::White),
+						);
+					if agenda_view {
+						let today = Local::now().date_naive();
+						let day = today + chrono::Duration::days(agenda_offset);
+						let rows = Layout::default()
+							.direction(Direction::Vertical)
+							.constraints([Constraint::Length(4), Constraint::Length(4), Constraint::Min(3)].as_ref())
+							.split(chunks[0]);
+						// This week and next, one cell per day with how much is due; days
+						// already past are dimmed, their tasks count as overdue on today
+						let lead = agenda_lead(today);
+						for week in 0..2 {
+							let cells = Layout::default()
+								.direction(Direction::Horizontal)
+								.constraints([Constraint::Ratio(1, 7); 7].as_ref())
+								.split(rows[week]);
+							for (col, cell) in cells.iter().enumerate() {
+								let offset = week as i64 * 7 + col as i64 - lead;
+								let date = today + chrono::Duration::days(offset);
+								let due = if offset < 0 { Vec::new() } else { agenda_tasks(&tasks, date, today) };
+								let overdue = due.iter().filter(|&&i| is_overdue(&tasks[i])).count();
+								let mut border = Style::default().fg(Color::DarkGray);
+								if offset == agenda_offset {
+									border = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+								}
+								let title_style = if offset == 0 {
+									Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+								} else if offset < 0 {
+									Style::default().fg(Color::DarkGray)
+								} else {
+									Style::default()
+								};
+								let load = cell.width.saturating_sub(2) as usize;
+								let lines = vec![
+									Line::from(match due.len() {
+										_ if offset < 0 => Span::raw(""),
+										0 => Span::styled("-", Style::default().fg(Color::DarkGray)),
+										n if overdue > 0 => Span::styled(format!("{n} ({overdue}!)"), Style::default().fg(Color::Red)),
+										n => Span::raw(n.to_string()),
+									}),
+									Line::from(Span::styled("■".repeat(due.len().min(load)), Style::default().fg(Color::Green))),
+								];
+								let block = Block::default()
+									.borders(Borders::ALL)
+									.border_style(border)
+									.title(Span::styled(date.format("%a %-d").to_string(), title_style));
+								f.render_widget(Paragraph::new(Text::from(lines)).block(block), *cell);
+							}
+						}
+						let due = agenda_tasks(&tasks, day, today);
+						let items: Vec<ListItem> = due
+							.iter()
+							.map(|&i| {
+								let t = &tasks[i];
+								let when = match t.due {
+									Some(d) if d.date < today => "overdue".to_string(),
+									Some(Due { time: Some(time), .. }) => dates::time(time),
+									_ => "-".to_string(),
+								};
+								let mut spans = vec![
+									Span::styled(format!("{when:>7}  "), Style::default().fg(if is_overdue(t) { Color::Red } else { Color::DarkGray })),
+									Span::raw(t.title.clone()),
+								];
+								if let Some(s) = find_session_for_task(&sessions, &t.path) {
+									let (indicator, indicator_style) = status_indicator(s.status, current_style);
+									spans.push(Span::raw("  "));
+									spans.push(Span::styled(indicator, indicator_style));
+									spans.push(Span::styled(format!(" {}", s.name), Style::default().fg(Color::DarkGray)));
+								}
+								ListItem::new(Line::from(spans))
+							})
+							.collect();
+						let undated = tasks.iter().filter(|t| t.due.is_none()).count();
+						let mut day_state = ListState::default();
+						day_state.select(Some(agenda_idx.min(due.len().saturating_sub(1))).filter(|_| !due.is_empty()));
+						let list = List::new(items)
+							.block(Block::default().borders(Borders::ALL).title(format!(
+								"{} · {} due{}",
+								dates::long_date(day),
+								due.len(),
+								if undated > 0 { format!(" · {undated} undated") } else { String::new() }
+							)))
+							.highlight_symbol("▶ ")
+							.highlight_style(Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED).fg(Color::White));
+						f.render_stateful_widget(list, rows[2], &mut day_state);
+					} else {
+						f.render_stateful_widget(list, chunks[0], &mut tasks_window);
+					}
+
+					let preview_text = if let Some(sel) = tasks_state
+						.selected()
+						.and_then(|idx| tasks.get(idx))
+					{
+						task_preview(sel, 100)
+					} else if tasks.is_empty() {
+						String::from("No tasks")
+					} else {
+						String::from("No task selected")
+					};
+					let preview = Paragraph::new(preview_text)
+						.block(Block::default().borders(Borders::ALL).title("Task Preview"))
+						.wrap(Wrap { trim: true });
+					f.render_widget(preview, chunks[1]);
 				} else {
-					String::from("No log selected")
-				};
-				let preview = Paragraph::new(preview_text)
-					.block(Block::default().borders(Borders::ALL).title("Daily Log"))
-					.wrap(Wrap { trim: true });
-				f.render_widget(preview, chunks[1]);
-			} else if showing_tasks {
-				let chunks = &split_chunks;
-				// Build a set of task paths that have active sessions
-				let active_task_paths: HashSet<PathBuf> = sessions
-					.iter()
-					.filter_map(|s| s.task.as_ref().map(|t| t.path.clone()))
-					.collect();
-
-				let items: Vec<ListItem> = tasks
-					.iter()
-					.map(|t| {
-						let due = t
-							.due
-							.map(|d| format!(" · {}", format_due(d)))
-							.unwrap_or_default();
-						let status_tag = t
-							.status
-							.as_ref()
-							.map(|s| format!("[{}] ", s))
-							.unwrap_or_default();
-						// Show ● indicator if task has an active session
-						let active_indicator = if active_task_paths.contains(&t.path) {
-							"● "
+					// AGENTS VIEW - handle all layout types
+					let current_style = styles[style_idx];
+					let needs_input_count = sessions.iter().filter(|s| s.status == AgentStatus::NeedsInput).count();
+
+					// Helper to get preview lines for a session
+					let get_preview_lines = |s: &AgentSession| -> Vec<Line> {
+						let preview_lines = if Some(&s.session_name) == sessions.get(selected).map(|sel| &sel.session_name) {
+							preview_cache.get(&s.session_name).map(|c| c.lines.clone()).unwrap_or_else(|| s.preview.clone())
 						} else {
-							"• "
+							s.preview.clone()
 						};
-						let style = if active_task_paths.contains(&t.path) {
-							Style::default().fg(Color::Green)
+						let cleaned = clean_preview(&preview_lines);
+						let mut styled_lines: Vec<Line> = Vec::new();
+						let combined = cleaned.join("\n");
+						if let Ok(text) = combined.as_bytes().into_text() {
+							styled_lines.extend(text.lines);
 						} else {
-							Style::default()
-						};
-						ListItem::new(Line::from(Span::styled(
-							format!("{}{}{}{}", active_indicator, status_tag, t.title, due),
-							style,
-						)))
-					})
-					.collect();
-				let list_title = "Tasks (enter=start)".to_string();
-				let list = List::new(items)
-					.block(Block::default().borders(Borders::ALL).title(list_title))
-					.highlight_symbol("▶ ")
-					.highlight_style(
-						Style::default()
-							.add_modifier(Modifier::BOLD | Modifier::REVERSED)
-							.fg(Color::White),
+							for line in &cleaned { styled_lines.push(Line::from(line.clone())); }
+						}
+						styled_lines
+					};
+
+					// SPLIT: Traditional left/right panels
+					let chunks = Layout::default()
+						.direction(Direction::Horizontal)
+						.constraints([Constraint::Percentage(35), Constraint::Percentage(65)].as_ref())
+						.split(vertical[0]);
+
+					let hidden = hidden_rows(&sessions, &collapsed_groups, &agent_filter);
+					let heads = group_heads(&sessions);
+					let rows = visible_rows(
+						sessions.len(),
+						list_state.selected(),
+						list_state.offset(),
+						chunks[0].height.saturating_sub(2) as usize,
+						|idx| {
+							if hidden[idx] {
+								0
+							} else {
+								match sessions[idx].group.as_ref().filter(|_| heads[idx]) {
+									Some(group) if collapsed_groups.contains(group) => 1,
+									Some(_) => 2,
+									None => 1,
+								}
+							}
+						},
 					);
-				f.render_stateful_widget(list, chunks[0], &mut tasks_state);
-
-				let preview_text = if let Some(sel) = tasks_state
-					.selected()
-					.and_then(|idx| tasks.get(idx))
-				{
-					task_preview(sel, 100)
-				} else if tasks.is_empty() {
-					String::from("No tasks")
+					*list_state.offset_mut() = rows.start;
+					let mut agents_window = ListState::default()
+						.with_selected(list_state.selected().filter(|s| rows.contains(s)).map(|s| s - rows.start));
+					let items: Vec<ListItem> = sessions.iter().enumerate().skip(rows.start).take(rows.len()).map(|(idx, s)| {
+						// Groups get a header row above their first member; collapsed, that's all they show
+						if hidden[idx] {
+							return ListItem::new(Text::default());
+						}
+						let group_header = s.group.as_ref().filter(|_| heads[idx]).map(|group| {
+							let members = group_members(&sessions, group);
+							let collapsed = collapsed_groups.contains(group);
+							let status = AgentStatus::aggregate(members.iter().map(|m| m.status)).unwrap_or(AgentStatus::Unknown);
+							let (status_text, status_style) = status_indicator(status, current_style);
+							(collapsed, Line::from(vec![
+								Span::raw(if collapsed { "▸ " } else { "▾ " }),
+								Span::styled(status_text, status_style),
+								Span::raw(" "),
+								Span::styled(group.clone(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+								Span::styled(format!(" · {}", group_summary(&members)), Style::default().fg(Color::DarkGray)),
+							]))
+						});
+						if let Some((true, header)) = group_header {
+							return ListItem::new(header);
+						}
+						let (status_text, status_style) = status_indicator(s.status, current_style);
+						let age = s.last_output.and_then(|t| SystemTime::now().duration_since(t).ok()).map(format_human_duration).unwrap_or_else(|| "–".to_string());
+						let mut spans: Vec<Span> = Vec::new();
+						if idx < 9 { spans.push(Span::styled(format!("{} ", idx + 1), Style::default().fg(Color::DarkGray))); }
+						else { spans.push(Span::raw("  ")); }
+						spans.push(Span::styled(status_text, status_style));
+						spans.push(Span::raw(" "));
+						if s.is_yolo { spans.push(Span::styled("⚠️ ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))); }
+						if s.pinned { spans.push(Span::raw("📌 ")); }
+						if s.interviewing { spans.push(Span::styled("[interview] ", Style::default().fg(Color::LightYellow))); }
+						if let Some(priority) = session_priority(s) { spans.push(Span::styled(format!("[{priority}] "), Style::default().fg(Color::LightRed))); }
+						if s.worktree_path.is_some() { spans.push(Span::styled("[wt] ", Style::default().fg(Color::Cyan))); }
+						if conflicts.contains_key(&s.session_name) { spans.push(Span::styled("[conflict] ", Style::default().fg(Color::Red))); }
+						if session_procs.get(&s.session_name).is_some_and(|p| p.iter().any(procs::Proc::is_runaway)) { spans.push(Span::styled("[runaway] ", Style::default().fg(Color::Red))); }
+						if let Some((elapsed, budget)) = s.deadline {
+							// Yellow in the last fifth of the budget, red once past it
+							let color = if elapsed >= budget { Color::Red } else if elapsed * 5 >= budget * 4 { Color::Yellow } else { Color::DarkGray };
+							spans.push(Span::styled(format!("⏱ {}/{} ", format_duration_hm(elapsed), format_duration_hm(budget)), Style::default().fg(color)));
+						}
+						if let Some(stage) = &s.stage {
+							spans.push(Span::styled(
+								format!("[{}/{} {}] ", stage.index + 1, stage.total, stage.name),
+								Style::default().fg(Color::Magenta),
+							));
+						}
+						if let Some(variant) = &s.variant {
+							spans.push(Span::styled(
+								format!("[v{}/{}] ", variant.index, variant.total),
+								Style::default().fg(Color::LightBlue),
+							));
+						}
+						spans.push(Span::raw(&s.name));
+						if let Some(note) = s.notes.first() {
+							spans.push(Span::styled(format!(" 📝 {}", text::truncate(&text::sanitize(note), 30)), Style::default().fg(Color::Yellow)));
+						}
+						if let Some((done, total)) = s.progress {
+							spans.push(Span::styled(
+								format!(" {} {}/{}", progress_bar(done, total, 5), done, total),
+								Style::default().fg(Color::Green),
+							));
+						}
+						spans.push(Span::styled(format!(" · {}", age), Style::default().fg(Color::DarkGray)));
+						if let Some(task) = &s.task {
+							spans.push(Span::raw(" · "));
+							spans.push(Span::raw(&task.title));
+							let linked = find_sessions_for_task(&sessions, &task.path).len();
+							if linked > 1 { spans.push(Span::styled(format!(" ({} agents)", linked), Style::default().fg(Color::Cyan))); }
+						}
+						// Live tool action beats the raw last line, except when the agent is asking something
+						let activity = s.activity.as_ref().filter(|_| s.status != AgentStatus::NeedsInput);
+						if let Some(action) = activity {
+							spans.push(Span::styled("  · ", Style::default().fg(Color::DarkGray)));
+							spans.push(Span::styled(text::truncate(&text::sanitize(action), 80), Style::default().fg(Color::Blue)));
+						} else if let Some(snippet) = mini_log_preview(&s.preview) {
+							spans.push(Span::styled("  · ", Style::default().fg(Color::DarkGray)));
+							spans.push(Span::styled(snippet, Style::default().fg(Color::DarkGray)));
+						}
+						// Priorities with flash = true blink while the agent needs attention (redrawn each second)
+						let mut row = Line::from(spans);
+						if matches!(s.status, AgentStatus::NeedsInput | AgentStatus::Error)
+							&& cfg.notifications.flash(session_priority(s))
+							&& Local::now().timestamp() % 2 == 0
+						{
+							row = row.style(Style::default().bg(Color::Red).fg(Color::White));
+						}
+						match group_header {
+							Some((_, header)) => ListItem::new(Text::from(vec![header, row])),
+							None => ListItem::new(row),
+						}
+					}).collect();
+
+					let mut agents_title = if needs_input_count > 0 { format!("Agents ({} need input)", needs_input_count) } else { "Agents".to_string() };
+					let overdue_count = tasks.iter().filter(|t| is_overdue(t)).count();
+					if overdue_count > 0 { agents_title = format!("{} │ {} overdue", agents_title, overdue_count); }
+					if filter_mode || !agent_filter.is_empty() {
+						let filter = parse_filter(&agent_filter);
+						let shown = sessions.iter().filter(|s| session_matches(s, &filter)).count();
+						match active_view.and_then(|i| cfg.views.get(i)).filter(|v| !filter_mode && v.filter == agent_filter) {
+							Some(view) => agents_title = format!("{} │ view: {} ({} shown)", agents_title, view.name, shown),
+							None => agents_title = format!("{} │ /{}{} ({} shown)", agents_title, agent_filter, if filter_mode { "█" } else { "" }, shown),
+						}
+					}
+					// Time left rounds up, so a fresh 1h mute reads "1h" rather than "59m"
+					match notify::mute_state() {
+						Some(Some(left)) => agents_title = format!("{} │ 🔕 muted {}", agents_title, format_duration_short(left + Duration::from_secs(59))),
+						Some(None) => agents_title = format!("{} │ 🔕 muted", agents_title),
+						None => {}
+					}
+					let unseen_errors = console::unseen();
+					if unseen_errors > 0 && !show_console {
+						agents_title = format!("{} │ ⚠ {} error{} (E)", agents_title, unseen_errors, if unseen_errors == 1 { "" } else { "s" });
+					}
+					if net::known_offline() { agents_title = format!("{} │ ✈ offline", agents_title); }
+					if show_changelog.is_none() { if let Some(ref version) = just_updated_version { agents_title = format!("{} │ ✨ Updated to {}!", agents_title, version); } }
+
+					let list = List::new(items)
+						.block(Block::default().borders(Borders::ALL).title(agents_title))
+						.highlight_symbol("▶ ")
+						.highlight_style(Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED).fg(Color::White));
+					f.render_stateful_widget(list, chunks[0], &mut agents_window);
+
+					let right_panes = Layout::default()
+						.direction(Direction::Vertical)
+						.constraints([Constraint::Min(10), Constraint::Length(10)].as_ref())
+						.split(chunks[1]);
+
+					let (preview_lines_styled, details_text, is_yolo_selected, needs_input_selected) =
+						if let Some(sel) = sessions.get(selected) {
+							let lines = get_preview_lines(sel);
+							let mut styled = if sel.is_yolo {
+								vec![Line::from(Span::styled("⚠️ YOLO MODE", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)))]
+							} else { Vec::new() };
+							styled.extend(lines);
+							let mut details = agent_details(sel);
+							if let Some(procs) = session_procs.get(&sel.session_name) {
+								details = format!("{}\n{}", process_details(procs), details);
+							}
+							if let Some(state) = claude_states.get(&sel.session_name) {
+								details = format!("{}\n{}", claude_details(state), details);
+							}
+							if let Some(task) = &sel.task {
+								let others: Vec<String> = find_sessions_for_task(&sessions, &task.path)
+									.iter()
+									.filter(|s| s.session_name != sel.session_name)
+									.map(|s| format!("{} ({})", s.name, s.status.label()))
+									.collect();
+								if !others.is_empty() { details.push_str(&format!("\nAlso on this task: {}", others.join(", "))); }
+							}
+							if let Some(pipe_msg) = pipe_status.get(&sel.session_name) { details.push_str(&format!("\nPipe: {pipe_msg}")); }
+							for conflict in conflicts.get(&sel.session_name).into_iter().flatten() {
+								details.push_str(&format!("\n⚠ {}", conflicts::describe(conflict)));
+							}
+							if let Some(asked) = last_instruction(&sel.session_name, claude_states.get(&sel.session_name)) {
+								details = format!("{asked}\n{details}");
+							}
+							if !sel.notes.is_empty() {
+								details = format!("Notes: {}\n{}", sel.notes.join(" · "), details);
+							}
+							(styled, details, sel.is_yolo, sel.status == AgentStatus::NeedsInput)
+						} else if sessions.is_empty() {
+							(vec![Line::from(""), Line::from(Span::styled("No agents yet.", Style::default().add_modifier(Modifier::BOLD))), Line::from(""), Line::from("Press n to create")], String::from(""), false, false)
+						} else { (vec![Line::from("No session selected")], String::from(""), false, false) };
+
+					let preview_block = if is_yolo_selected {
+						Block::default().borders(Borders::ALL).title("⚠️ Preview (YOLO)").border_style(Style::default().fg(Color::Red)).title_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+					} else if needs_input_selected {
+						Block::default().borders(Borders::ALL).title("Preview (Enter to reply)").title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+					} else { Block::default().borders(Borders::ALL).title("Preview") };
+					let preview = Paragraph::new(Text::from(preview_lines_styled)).block(preview_block).wrap(Wrap { trim: true });
+					let height = right_panes[0].height.saturating_sub(2) as usize;
+					let line_count = preview.line_count(right_panes[0].width.saturating_sub(2));
+					let scroll = line_count.saturating_sub(height);
+					f.render_widget(preview.scroll((scroll as u16, 0)), right_panes[0]);
+					f.render_widget(Paragraph::new(details_text).block(Block::default().borders(Borders::ALL).title("Details")).wrap(Wrap { trim: true }), right_panes[1]);
+				}
+
+				let mut footer_lines = vec![if compare_view.is_some() {
+					format!(
+						"Compare: Tab {} | 1/2 keep that one (kill the other) | Esc close",
+						if compare_diff { "output" } else { "diffs" }
+					)
+				} else if showing_daily {
+					"Esc:back  ↑/↓:nav  o:open".to_string()
+				} else if showing_tasks && agenda_view {
+					"Agenda: ←/→ day | [/] week | . today | ↑/↓ task | enter start | N new session | o open | A/Esc list".to_string()
+				} else if showing_tasks {
+					tasks_footer_text(size.width)
+				} else if send_input_mode && !replies.is_empty() {
+					"Input: type message or ←/→ pick a reply, ↑/↓ history, Enter send, Esc cancel".to_string()
+				} else if send_input_mode {
+					"Input: type message, ↑/↓ history, Enter send, Esc cancel".to_string()
+				} else {
+					agents_footer_text(size.width)
+				}];
+				if send_input_mode {
+					footer_lines.push(format!("> {}", send_input_buf.replace('\n', " ⏎ ")));
+				}
+				if let Some(msg) = &active_status {
+					footer_lines.push(format!("Status: {msg}"));
+				}
+				let footer_text = footer_lines.join("  |  ");
+				let footer_block = if active_status.is_some() || send_input_mode {
+					Block::default().borders(Borders::ALL)
 				} else {
-					String::from("No task selected")
+					Block::default()
 				};
-				let preview = Paragraph::new(preview_text)
-					.block(Block::default().borders(Borders::ALL).title("Task Preview"))
+				let footer = Paragraph::new(footer_text)
+					.block(footer_block)
 					.wrap(Wrap { trim: true });
-				f.render_widget(preview, chunks[1]);
-			} else {
-				// AGENTS VIEW - handle all layout types
-				let current_style = styles[style_idx];
-				let needs_input_count = sessions.iter().filter(|s| s.status == AgentStatus::NeedsInput).count();
-
-				// Helper to get preview lines for a session
-				let get_preview_lines = |s: &AgentSession| -> Vec<Line> {
-					let preview_lines = if Some(&s.session_name) == sessions.get(selected).map(|sel| &sel.session_name) {
-						cached_preview.as_ref().filter(|(name, _)| name == &s.session_name).map(|(_, lines)| lines.clone()).unwrap_or_else(|| s.preview.clone())
+				let mut footer_area = Rect {
+					x: vertical[2].x,
+					y: vertical[2].y,
+					width: vertical[2].width,
+					height: footer_height,
+				};
+				if tmux_missing {
+					let banner = Paragraph::new(
+						"⚠ tmux not found - agents run inside swarm and stop when you quit (no attach). Install: brew install tmux",
+					)
+					.style(Style::default().fg(Color::Black).bg(Color::Yellow));
+					f.render_widget(banner, Rect { height: 1, ..footer_area });
+					footer_area.y += 1;
+				}
+				f.render_widget(footer, footer_area);
+
+				if show_console {
+					// Newest at the bottom, like a terminal
+					console::mark_seen();
+					let entries = console::entries();
+					let height = vertical[1].height.saturating_sub(2) as usize;
+					let lines: Vec<Line> = if entries.is_empty() {
+						vec![Line::from(Span::styled(" No errors this session", Style::default().fg(Color::DarkGray)))]
 					} else {
-						s.preview.clone()
+						entries[entries.len().saturating_sub(height)..]
+							.iter()
+							.map(|e| {
+								Line::from(vec![
+									Span::styled(format!(" {} ", dates::time_secs(e.at.time())), Style::default().fg(Color::DarkGray)),
+									Span::styled(format!("{}: ", e.context), Style::default().fg(Color::Red)),
+									Span::raw(e.message.replace('\n', " ")),
+								])
+							})
+							.collect()
 					};
-					let cleaned = clean_preview(&preview_lines);
-					let mut styled_lines: Vec<Line> = Vec::new();
-					let combined = cleaned.join("\n");
-					if let Ok(text) = combined.as_bytes().into_text() {
-						styled_lines.extend(text.lines.into_iter());
-					} else {
-						for line in &cleaned { styled_lines.push(Line::from(line.clone())); }
+					let panel = Paragraph::new(Text::from(lines)).block(
+						Block::default()
+							.borders(Borders::ALL)
+							.title(format!("Console · {} error(s), full log in ~/.swarm/swarm.log (E close)", entries.len()))
+							.border_style(Style::default().fg(Color::DarkGray)),
+					);
+					f.render_widget(panel, vertical[1]);
+				}
+
+				if let Some((left, right)) = &compare_view {
+					let area = vertical[0];
+					f.render_widget(ratatui::widgets::Clear, area);
+					let halves = Layout::default()
+						.direction(Direction::Horizontal)
+						.constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+						.split(area);
+					for (idx, name) in [left, right].into_iter().enumerate() {
+						let Some(s) = sessions.iter().find(|s| &s.session_name == name) else {
+							continue;
+						};
+						let lines: Vec<Line> = if compare_diff {
+							let diff = if idx == 0 { &compare_diffs.0 } else { &compare_diffs.1 };
+							diff.iter().map(|l| Line::from(l.clone())).collect()
+						} else {
+							let raw = preview_cache
+								.get(&s.session_name)
+								.map(|c| c.lines.clone())
+								.unwrap_or_else(|| s.preview.clone());
+							let cleaned = clean_preview(&raw);
+							match cleaned.join("\n").as_bytes().into_text() {
+								Ok(text) => text.lines,
+								Err(_) => cleaned.into_iter().map(Line::from).collect(),
+							}
+						};
+						let (indicator, indicator_style) = status_indicator(s.status, styles[style_idx]);
+						let title = Line::from(vec![
+							Span::raw(format!(" {} ", idx + 1)),
+							Span::styled(indicator, indicator_style),
+							Span::raw(format!(" {} · {} ", s.name, s.agent)),
+						]);
+						let pane = Paragraph::new(Text::from(lines))
+							.block(Block::default().borders(Borders::ALL).title(title))
+							.wrap(Wrap { trim: false });
+						let height = halves[idx].height.saturating_sub(2) as usize;
+						let line_count = pane.line_count(halves[idx].width.saturating_sub(2));
+						let scroll = if compare_diff { 0 } else { line_count.saturating_sub(height) };
+						f.render_widget(pane.scroll((scroll as u16, 0)), halves[idx]);
 					}
-					styled_lines
-				};
+				}
 
-				// SPLIT: Traditional left/right panels
-				let chunks = Layout::default()
-					.direction(Direction::Horizontal)
-					.constraints([Constraint::Percentage(35), Constraint::Percentage(65)].as_ref())
-					.split(vertical[0]);
+				if let Some((session, command, dir)) = &run_view {
+					let area = centered_rect(80, 60, size);
+					f.render_widget(ratatui::widgets::Clear, area);
+					let (output, status) = session_run_paths(session).unwrap_or_default();
+					let mut lines: Vec<Line> = tail_lines(&output, 500)
+						.unwrap_or_default()
+						.iter()
+						.map(|l| Line::from(text::sanitize(l)))
+						.collect();
+					if lines.is_empty() {
+						lines.push(Line::from(Span::styled("(no output yet)", Style::default().fg(Color::DarkGray))));
+					}
+					let (state, color) = match fs::read_to_string(&status).ok().and_then(|s| s.trim().parse::<i32>().ok()) {
+						None => ("running…".to_string(), Color::Yellow),
+						Some(0) => ("exit 0".to_string(), Color::Green),
+						Some(code) => (format!("exit {code}"), Color::Red),
+					};
+					let log = Paragraph::new(Text::from(lines))
+						.block(
+							Block::default()
+								.borders(Borders::ALL)
+								.title(format!(
+									"$ {} · {} · {state} (r rerun · : edit · Esc close)",
+									text::truncate(command, 40),
+									dir.display()
+								))
+								.border_style(Style::default().fg(color)),
+						)
+						.wrap(Wrap { trim: false });
+					// Anchor to the bottom so the latest output is visible
+					let height = area.height.saturating_sub(2) as usize;
+					let line_count = log.line_count(area.width.saturating_sub(2));
+					let scroll = line_count.saturating_sub(height);
+					f.render_widget(log.scroll((scroll as u16, 0)), area);
+				}
 
-				let items: Vec<ListItem> = sessions.iter().enumerate().map(|(idx, s)| {
-					let (status_text, status_style) = status_indicator(s.status, current_style);
-					let age = s.last_output.and_then(|t| SystemTime::now().duration_since(t).ok()).map(format_human_duration).unwrap_or_else(|| "–".to_string());
-					let mut spans: Vec<Span> = Vec::new();
-					if idx < 9 { spans.push(Span::styled(format!("{} ", idx + 1), Style::default().fg(Color::DarkGray))); }
-					else { spans.push(Span::raw("  ")); }
-					spans.push(Span::styled(status_text, status_style));
-					spans.push(Span::raw(" "));
-					if s.is_yolo { spans.push(Span::styled("⚠️ ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))); }
-					if s.worktree_path.is_some() { spans.push(Span::styled("[wt] ", Style::default().fg(Color::Cyan))); }
-					spans.push(Span::raw(&s.name));
-					spans.push(Span::styled(format!(" · {}", age), Style::default().fg(Color::DarkGray)));
-					if let Some(task) = &s.task { spans.push(Span::raw(" · ")); spans.push(Span::raw(&task.title)); }
-					if let Some(snippet) = mini_log_preview(&s.preview) {
-						spans.push(Span::styled("  · ", Style::default().fg(Color::DarkGray)));
-						spans.push(Span::styled(snippet, Style::default().fg(Color::DarkGray)));
-					}
-					ListItem::new(Line::from(spans))
-				}).collect();
-
-				let mut agents_title = if needs_input_count > 0 { format!("Agents ({} need input)", needs_input_count) } else { "Agents".to_string() };
-				if show_changelog.is_none() { if let Some(ref version) = just_updated_version { agents_title = format!("{} │ ✨ Updated to {}!", agents_title, version); } }
-
-				let list = List::new(items)
-					.block(Block::default().borders(Borders::ALL).title(agents_title))
-					.highlight_symbol("▶ ")
-					.highlight_style(Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED).fg(Color::White));
-				f.render_stateful_widget(list, chunks[0], &mut list_state);
-
-				let right_panes = Layout::default()
-					.direction(Direction::Vertical)
-					.constraints([Constraint::Min(10), Constraint::Length(8)].as_ref())
-					.split(chunks[1]);
-
-				let (preview_lines_styled, details_text, is_yolo_selected, needs_input_selected) =
-					if let Some(sel) = sessions.get(selected) {
-						let lines = get_preview_lines(sel);
-						let mut styled = if sel.is_yolo {
-							vec![Line::from(Span::styled("⚠️ YOLO MODE", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)))]
-						} else { Vec::new() };
-						styled.extend(lines);
-						let mut details = agent_details(sel);
-						if let Some(pipe_msg) = pipe_status.get(&sel.session_name) { details.push_str(&format!("\nPipe: {pipe_msg}")); }
-						(styled, details, sel.is_yolo, sel.status == AgentStatus::NeedsInput)
-					} else if sessions.is_empty() {
-						(vec![Line::from(""), Line::from(Span::styled("No agents yet.", Style::default().add_modifier(Modifier::BOLD))), Line::from(""), Line::from("Press n to create")], String::from(""), false, false)
-					} else { (vec![Line::from("No session selected")], String::from(""), false, false) };
-
-				let preview_block = if is_yolo_selected {
-					Block::default().borders(Borders::ALL).title("⚠️ Preview (YOLO)").border_style(Style::default().fg(Color::Red)).title_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
-				} else if needs_input_selected {
-					Block::default().borders(Borders::ALL).title("Preview (Enter to reply)").title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
-				} else { Block::default().borders(Borders::ALL).title("Preview") };
-				let preview = Paragraph::new(Text::from(preview_lines_styled)).block(preview_block).wrap(Wrap { trim: true });
-				let height = right_panes[0].height.saturating_sub(2) as usize;
-				let line_count = preview.line_count(right_panes[0].width.saturating_sub(2));
-				let scroll = line_count.saturating_sub(height);
-				f.render_widget(preview.scroll((scroll as u16, 0)), right_panes[0]);
-				f.render_widget(Paragraph::new(details_text).block(Block::default().borders(Borders::ALL).title("Details")).wrap(Wrap { trim: true }), right_panes[1]);
-			}
-
-			let footer_height: u16 = if active_status.is_some() || send_input_mode {
-				3
-			} else {
-				2
-			};
-			let mut footer_lines = vec![if showing_daily {
-				"Esc:back  ↑/↓:nav  o:open".to_string()
-			} else if showing_tasks {
-				tasks_footer_text(size.width)
-			} else if send_input_mode {
-				"Input: type message, Enter send, Esc cancel".to_string()
-			} else {
-				agents_footer_text(size.width)
-			}];
-			if send_input_mode {
-				footer_lines.push(format!("> {}", send_input_buf));
-			}
-			if let Some(msg) = &active_status {
-				footer_lines.push(format!("Status: {msg}"));
-			}
-			let footer_text = footer_lines.join("  |  ");
-			let footer_block = if active_status.is_some() || send_input_mode {
-				Block::default().borders(Borders::ALL)
-			} else {
-				Block::default()
-			};
-			let footer = Paragraph::new(footer_text)
-				.block(footer_block)
-				.wrap(Wrap { trim: true });
-			let footer_area = Rect {
-				x: vertical[1].x,
-				y: vertical[1].y,
-				width: vertical[1].width,
-				height: footer_height,
-			};
-			f.render_widget(footer, footer_area);
-
-			if show_help {
-				let area = centered_rect(70, 80, size);
-				let clear = ratatui::widgets::Clear;
-				f.render_widget(clear, area);
-				let overlay = Paragraph::new(help_text())
-					.block(Block::default().borders(Borders::ALL).title("Help"))
-					.wrap(Wrap { trim: true });
-				f.render_widget(overlay, area);
-			}
+				if let Some(ref task) = process_log_view {
+					let area = centered_rect(80, 80, size);
+					let clear = ratatui::widgets::Clear;
+					f.render_widget(clear, area);
+					let lines: Vec<Line> = match parse_process_log(&task.path) {
+						Some(entries) if entries.iter().any(|l| !l.trim().is_empty()) => {
+							entries.iter().map(|l| process_log_line(l)).collect()
+						}
+						Some(_) => vec![Line::from("(Process Log is empty - agents append here with /log)")],
+						None => vec![Line::from("(No \"## Process Log\" section in this task file)")],
+					};
+					let log = Paragraph::new(Text::from(lines))
+						.block(
+							Block::default()
+								.borders(Borders::ALL)
+								.title(format!("Process Log · {} (v/Esc close)", task.title))
+								.border_style(Style::default().fg(Color::Cyan)),
+						)
+						.wrap(Wrap { trim: false });
+					// Anchor to the bottom so the newest entries are visible
+					let height = area.height.saturating_sub(2) as usize;
+					let line_count = log.line_count(area.width.saturating_sub(2));
+					let scroll = line_count.saturating_sub(height);
+					f.render_widget(log.scroll((scroll as u16, 0)), area);
+				}
 
-			// Changelog modal (shown after update)
-			if let Some((ref version, ref notes)) = show_changelog {
-				let area = centered_rect(70, 80, size);
-				let clear = ratatui::widgets::Clear;
-				f.render_widget(clear, area);
-				let body = format!("{}\n\n─────────────────────────────────────\n         Press any key to continue", notes);
-				let overlay = Paragraph::new(body)
-					.block(Block::default().borders(Borders::ALL).title(format!("✨ Updated to {}", version)))
-					.wrap(Wrap { trim: true });
-				f.render_widget(overlay, area);
-			}
+				if let Some((name, steps)) = &land_view {
+					let area = centered_rect(70, 50, size);
+					f.render_widget(ratatui::widgets::Clear, area);
+					let mut lines: Vec<Line> = steps
+						.iter()
+						.map(|step| {
+							let (mark, color) = match step.state {
+								land::StepState::Pending => ("·", Color::DarkGray),
+								land::StepState::Running => ("…", Color::Yellow),
+								land::StepState::Ok => ("✓", Color::Green),
+								land::StepState::Failed => ("✗", Color::Red),
+							};
+							let mut spans = vec![
+								Span::styled(format!(" {mark} "), Style::default().fg(color)),
+								Span::raw(step.name.clone()),
+							];
+							if !step.detail.is_empty() {
+								spans.push(Span::styled(format!("  {}", step.detail), Style::default().fg(Color::DarkGray)));
+							}
+							Line::from(spans)
+						})
+						.collect();
+					lines.push(Line::from(""));
+					lines.push(Line::from(if land_rx.is_some() { " Working... Esc to cancel" } else { " Esc to close" }));
+					let modal = Paragraph::new(Text::from(lines))
+						.block(
+							Block::default()
+								.borders(Borders::ALL)
+								.title(format!("Land · {name}"))
+								.border_style(Style::default().fg(Color::Cyan)),
+						)
+						.wrap(Wrap { trim: false });
+					f.render_widget(modal, area);
+				}
 
-			if send_input_mode {
-				let area = centered_rect(70, 30, size);
-				let clear = ratatui::widgets::Clear;
-				f.render_widget(clear, area);
-				let instructions = "Send input (Enter to send, Esc to cancel)";
-				let body = format!("{}\n\n> {}", instructions, send_input_buf);
-				let overlay = Paragraph::new(body)
-					.block(Block::default().borders(Borders::ALL).title("Send Input"))
-					.wrap(Wrap { trim: true });
-				f.render_widget(overlay, area);
-			}
-
-			if confirm_kill_mode {
-				let area = centered_rect(60, 40, size);
-				let clear = ratatui::widgets::Clear;
-				f.render_widget(clear, area);
-				let session_name = pending_kill_session
-					.as_deref()
-					.unwrap_or("unknown");
-				let body = format!(
-					r#"⚠️  Are you sure you want to kill this session?
-
-Session: {}
-
-Did you run /done in Claude first?
-(Saves learnings, updates daily log, marks task complete)
-
-  [y]   Yes, kill it
-  [Esc] No, go back"#,
-					session_name
-				);
-				let overlay = Paragraph::new(body)
-					.block(
+				if let Some(runs) = &history_view {
+					let area = centered_rect(90, 85, size);
+					f.render_widget(ratatui::widgets::Clear, area);
+					let shown: Vec<&history::Run> = runs.iter().filter(|r| r.matches(&history_query)).collect();
+					let dim = Style::default().fg(Color::DarkGray);
+					let (title, lines, scroll) = match shown.get(history_idx).filter(|_| history_detail) {
+						_ if history_stats => {
+							let stats = history::stats(runs, 8, Local::now().date_naive());
+							let lines = stats_lines(&stats).into_iter().map(|l| Line::from(format!(" {l}"))).collect();
+							("Stats · last 8 weeks (CSV: swarm stats --csv weeks|agents|runs; s/Esc back)".to_string(), lines, 0)
+						}
+						Some(run) => {
+							let at = |secs: i64| {
+								let at = chrono::DateTime::from_timestamp(secs, 0).unwrap_or_default().with_timezone(&Local);
+								format!("{} {}", at.format("%Y-%m-%d"), dates::time(at.time()))
+							};
+							let field = |name: &str, value: String| {
+								Line::from(vec![Span::styled(format!(" {name:<9}"), dim), Span::raw(value)])
+							};
+							let mut lines = vec![
+								field("Agent", format!("{}{}", run.agent, run.model.as_ref().map(|m| format!(" ({m})")).unwrap_or_default())),
+								field("Task", run.task.clone().unwrap_or_else(|| "-".to_string())),
+								field("Outcome", run.outcome.clone()),
+								field(
+									"Ran",
+									format!(
+										"{} → {} ({})",
+										run.started.map(at).unwrap_or_else(|| "?".to_string()),
+										at(run.ended),
+										run.duration().map(format_duration_short).unwrap_or_else(|| "-".to_string())
+									),
+								),
+							];
+							if let Some(branch) = &run.branch {
+								lines.push(field("Branch", branch.clone()));
+							}
+							if let Some(group) = &run.group {
+								lines.push(field("Group", group.clone()));
+							}
+							for (heading, body) in [("Timeline", &run.timeline), ("Changes", &run.diff), ("Last output", &run.log_tail)] {
+								lines.push(Line::from(""));
+								lines.push(Line::from(Span::styled(format!(" {heading}"), Style::default().add_modifier(Modifier::BOLD))));
+								if body.is_empty() {
+									lines.push(Line::from(Span::styled("  (none)", dim)));
+								}
+								lines.extend(body.iter().map(|l| Line::from(format!("  {l}"))));
+							}
+							(format!("History · {} (↑/↓ scroll, Esc back)", run.name), lines, history_scroll)
+						}
+						None => {
+							let mut lines = vec![Line::from(Span::styled(
+								if history_search || !history_query.is_empty() {
+									format!(" Search: {}{}", history_query, if history_search { "█" } else { "" })
+								} else {
+									" / search by task · Enter details · s stats".to_string()
+								},
+								dim,
+							))];
+							if shown.is_empty() {
+								lines.push(Line::from(Span::styled(" No runs (sessions are archived when marked done)", dim)));
+							}
+							// Keep the selected run on screen
+							let height = area.height.saturating_sub(3) as usize;
+							let first = history_idx.saturating_sub(height.saturating_sub(1));
+							for (idx, run) in shown.iter().enumerate().skip(first).take(height) {
+								let style = if idx == history_idx {
+									Style::default().add_modifier(Modifier::REVERSED)
+								} else if run.outcome == "done" {
+									Style::default()
+								} else {
+									Style::default().fg(Color::Yellow)
+								};
+								lines.push(Line::from(Span::styled(format!(" {}", history_row(run)), style)));
+							}
+							(format!("History · {} run(s) (O/Esc close)", shown.len()), lines, 0)
+						}
+					};
+					let modal = Paragraph::new(Text::from(lines))
+						.block(
+							Block::default()
+								.borders(Borders::ALL)
+								.title(title)
+								.border_style(Style::default().fg(Color::Cyan)),
+						)
+						.wrap(Wrap { trim: false })
+						.scroll((scroll, 0));
+					f.render_widget(modal, area);
+				}
+
+				if let Some((session, list)) = &procs_view {
+					let area = centered_rect(80, 60, size);
+					f.render_widget(ratatui::widgets::Clear, area);
+					let dim = Style::default().fg(Color::DarkGray);
+					let agent_pid = procs::agent(list).map(|p| p.pid);
+					let mut lines = vec![Line::from(Span::styled(
+						if procs_kill_armed {
+							" x again sends SIGKILL · any other key cancels".to_string()
+						} else {
+							" ↑/↓ choose · i SIGINT · x SIGKILL · r refresh · Esc close".to_string()
+						},
+						if procs_kill_armed { Style::default().fg(Color::Red) } else { dim },
+					))];
+					if list.is_empty() {
+						lines.push(Line::from(Span::styled(" No processes under this pane", dim)));
+					}
+					let height = area.height.saturating_sub(3) as usize;
+					let first = procs_idx.saturating_sub(height.saturating_sub(1));
+					for (idx, p) in list.iter().enumerate().skip(first).take(height) {
+						let command = if p.command.is_empty() { &p.name } else { &p.command };
+						let mut row = format!(
+							" {}{} ({})  {:.0}%  {}  {}",
+							"  ".repeat(p.depth),
+							p.name,
+							p.pid,
+							p.cpu,
+							procs::format_memory(p.memory),
+							text::truncate(&text::sanitize(command), 60)
+						);
+						if Some(p.pid) == agent_pid {
+							row.push_str("  (agent)");
+						}
+						if !p.alive {
+							row.push_str("  (stopped/zombie)");
+						}
+						let style = if idx == procs_idx {
+							Style::default().add_modifier(Modifier::REVERSED)
+						} else if p.is_runaway() {
+							Style::default().fg(Color::Red)
+						} else if p.is_shell() || Some(p.pid) == agent_pid {
+							dim
+						} else {
+							Style::default()
+						};
+						lines.push(Line::from(Span::styled(row, style)));
+					}
+					let name = session.trim_start_matches(SWARM_PREFIX);
+					let modal = Paragraph::new(Text::from(lines)).block(
 						Block::default()
 							.borders(Borders::ALL)
-							.title("⚠️ Confirm Kill Session")
-							.border_style(Style::default().fg(Color::Yellow))
-							.title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-					)
-					.wrap(Wrap { trim: true });
-				f.render_widget(overlay, area);
-			}
-
-		if new_agent_mode {
-				let area = centered_rect(65, 50, size);
-				let clear = ratatui::widgets::Clear;
-				f.render_widget(clear, area);
-				let cursors = [
-					if new_agent_field == 0 { "█" } else { "" },
-					if new_agent_field == 1 { "█" } else { "" },
-					if new_agent_field == 2 { "█" } else { "" },
-				];
-				let due_display = &new_agent_due;
-				let body = format!(
-					r#"What are you working on?
-> {}{}
-
-Who should be notified when done?
-> {}{}
-
-Due date (MM-DD or leave blank for tomorrow)
-> {}{}
-
-Tab to switch fields, Enter to start, Esc to cancel"#,
-					new_agent_buf, cursors[0],
-					new_agent_notify, cursors[1],
-					due_display, cursors[2],
-				);
-				let overlay = Paragraph::new(body)
-					.block(
+							.title(format!("Processes · {name} (K/Esc close)"))
+							.border_style(Style::default().fg(Color::Cyan)),
+					);
+					f.render_widget(modal, area);
+				}
+
+				if show_help {
+					let area = centered_rect(70, 80, size);
+					let clear = ratatui::widgets::Clear;
+					f.render_widget(clear, area);
+					let overlay = Paragraph::new(help_text())
+						.block(Block::default().borders(Borders::ALL).title("Help"))
+						.wrap(Wrap { trim: true });
+					f.render_widget(overlay, area);
+				}
+
+				// Changelog modal (shown after update)
+				if let Some((ref version, ref notes)) = show_changelog {
+					let area = centered_rect(70, 80, size);
+					let clear = ratatui::widgets::Clear;
+					f.render_widget(clear, area);
+					let body = format!("{}\n\n─────────────────────────────────────\n         Press any key to continue", notes);
+					let overlay = Paragraph::new(body)
+						.block(Block::default().borders(Borders::ALL).title(format!("✨ Updated to {}", version)))
+						.wrap(Wrap { trim: true });
+					f.render_widget(overlay, area);
+				}
+
+				if send_input_mode {
+					let area = centered_rect(70, if send_input_buf.contains('\n') { 60 } else { 30 }, size);
+					let clear = ratatui::widgets::Clear;
+					f.render_widget(clear, area);
+					let broadcast_to = broadcast_group.as_ref().map(|g| (g, group_members(&sessions, g)));
+					let target_is_yolo = match &broadcast_to {
+						Some((_, members)) => members.iter().any(|s| s.is_yolo),
+						None => sessions.get(selected).map(|s| s.is_yolo).unwrap_or(false),
+					};
+					let instructions = if yolo_send_armed {
+						"⚠️  YOLO session: this runs with NO permission prompts.\nPress Enter again to send, Esc to cancel"
+					} else if target_is_yolo {
+						"⚠️  YOLO session (Enter to review, Esc to cancel)"
+					} else {
+						"Send input (Enter to send, Alt+Enter new line, Ctrl+E $EDITOR, Esc to cancel)"
+					};
+					let mut body = vec![Line::from(instructions.to_string())];
+					if !replies.is_empty() {
+						let mut spans = vec![Span::styled("Replies (←/→): ", Style::default().fg(Color::DarkGray))];
+						for (i, reply) in replies.iter().enumerate() {
+							let style = if reply_idx == Some(i) {
+								Style::default().fg(Color::Black).bg(Color::Cyan)
+							} else {
+								Style::default().fg(Color::Cyan)
+							};
+							spans.push(Span::styled(format!(" {} ", reply.label), style));
+							spans.push(Span::raw(" "));
+						}
+						body.push(Line::from(""));
+						body.push(Line::from(spans));
+					}
+					body.push(Line::from(""));
+					for (i, line) in send_input_buf.split('\n').enumerate() {
+						body.push(Line::from(format!("{} {}", if i == 0 { ">" } else { " " }, line)));
+					}
+					let title = match &broadcast_to {
+						Some((group, members)) => format!("Broadcast · {group} ({} agents)", members.len()),
+						None => "Send Input".to_string(),
+					};
+					let block = if target_is_yolo {
 						Block::default()
 							.borders(Borders::ALL)
-							.title("New Agent")
-							.border_style(Style::default().fg(Color::Cyan))
-							.title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-					)
-					.wrap(Wrap { trim: true });
-				f.render_widget(overlay, area);
-			}
+							.title(format!("⚠️ {title} (YOLO)"))
+							.border_style(Style::default().fg(Color::Red))
+							.title_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+					} else {
+						Block::default().borders(Borders::ALL).title(title)
+					};
+					let overlay = Paragraph::new(body)
+						.block(block)
+						.wrap(Wrap { trim: true });
+					f.render_widget(overlay, area);
+				}
 
-			// First-run hooks install prompt
-			if show_hooks_prompt {
-				let area = centered_rect(60, 50, size);
-				let clear = ratatui::widgets::Clear;
-				f.render_widget(clear, area);
-				let body = r#"Welcome to swarm!
+				if let Some(group) = &confirm_group_kill {
+					let area = centered_rect(60, 40, size);
+					f.render_widget(ratatui::widgets::Clear, area);
+					let members = group_members(&sessions, group);
+					let mut body = vec![
+						Line::from(format!("⚠️  Kill all {} agents in {group}?", members.len())),
+						Line::from(""),
+					];
+					for m in &members {
+						let pin = if m.pinned { " 📌 pinned, kept" } else { "" };
+						body.push(Line::from(format!("  {} ({}){pin}", m.name, m.status.label())));
+					}
+					body.push(Line::from(""));
+					body.push(Line::from("  [y]   Yes, kill them all"));
+					body.push(Line::from("  [Esc] No, go back"));
+					let overlay = Paragraph::new(body)
+						.block(
+							Block::default()
+								.borders(Borders::ALL)
+								.title("⚠️ Confirm Kill Group")
+								.border_style(Style::default().fg(Color::Yellow))
+								.title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+						)
+						.wrap(Wrap { trim: true });
+					f.render_widget(overlay, area);
+				}
 
-swarm comes with Claude commands that help you
-work more effectively with AI coding agents:
+				if confirm_kill_mode {
+					let area = centered_rect(60, 40, size);
+					let clear = ratatui::widgets::Clear;
+					f.render_widget(clear, area);
+					let session_name = pending_kill_session
+						.as_deref()
+						.unwrap_or("unknown");
+					let body = format!(
+						r#"⚠️  Are you sure you want to kill this session?
 
-  /done       - End session, log work
-  /interview  - Detailed task planning
-  /log        - Save progress to task file
-  /worktree   - Create isolated git worktree
-  /poll-pr    - Monitor PR until CI green
-  /qa-swarm   - QA test the swarm TUI
+	Session: {}
 
-Install these commands to ~/.claude/commands/?
+	Did you run /done in Claude first?
+	(Saves learnings, updates daily log, marks task complete)
 
-  [y] Yes, install (recommended)
-  [n] No thanks"#;
-				let overlay = Paragraph::new(body)
-					.block(
-						Block::default()
-							.borders(Borders::ALL)
-							.title("Setup")
-							.border_style(Style::default().fg(Color::Green))
-							.title_style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
-					)
-					.wrap(Wrap { trim: true });
-				f.render_widget(overlay, area);
-			}
-		})?;
+	  [y]   Yes, kill it
+	  [Esc] No, go back"#,
+						session_name
+					);
+					let pinned = sessions.iter().any(|s| s.pinned && Some(s.session_name.as_str()) == pending_kill_session.as_deref());
+					let body = if pinned { format!("📌 This session is pinned.\n\n{body}") } else { body };
+					let overlay = Paragraph::new(body)
+						.block(
+							Block::default()
+								.borders(Borders::ALL)
+								.title("⚠️ Confirm Kill Session")
+								.border_style(Style::default().fg(Color::Yellow))
+								.title_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+						)
+						.wrap(Wrap { trim: true });
+					f.render_widget(overlay, area);
+				}
+
+				if handoff_mode {
+					let area = centered_rect(65, 45, size);
+					let clear = ratatui::widgets::Clear;
+					f.render_widget(clear, area);
+					let from = sessions.get(selected).map(|s| s.name.as_str()).unwrap_or("unknown");
+					let body = format!(
+						r#"Hand off {} to a new agent
+
+	Snapshots the diff summary, task Process Log and last output,
+	starts the new agent with that context, then ends {}.
+
+	Agent (claude, codex, ...)
+	> {}{}
+
+	Instructions for the next agent (optional)
+	> {}{}
+
+	Tab to switch fields, Enter to hand off, Esc to cancel"#,
+						from,
+						from,
+						handoff_agent_buf,
+						if handoff_field == 0 { "█" } else { "" },
+						handoff_note_buf,
+						if handoff_field == 1 { "█" } else { "" },
+					);
+					let overlay = Paragraph::new(body)
+						.block(
+							Block::default()
+								.borders(Borders::ALL)
+								.title("Handoff")
+								.border_style(Style::default().fg(Color::Magenta))
+								.title_style(Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD)),
+						)
+						.wrap(Wrap { trim: true });
+					f.render_widget(overlay, area);
+				}
 
-		if event::poll(Duration::from_millis(100))? {
-			if let Event::Key(key) = event::read()? {
+				if notes_mode {
+					let area = centered_rect(60, 40, size);
+					f.render_widget(ratatui::widgets::Clear, area);
+					let sel = sessions.get(selected);
+					let mut body = vec![Line::from(format!("Notes on {}", sel.map(|s| s.name.as_str()).unwrap_or("unknown"))), Line::from("")];
+					for note in sel.map(|s| s.notes.as_slice()).unwrap_or_default() {
+						body.push(Line::from(Span::styled(format!("• {note}"), Style::default().fg(Color::Yellow))));
+					}
+					body.push(Line::from(""));
+					body.push(Line::from(format!("> {}█", notes_buf)));
+					body.push(Line::from(""));
+					body.push(Line::from(Span::styled(
+						"Enter adds · Backspace on an empty line removes the last · Esc closes",
+						Style::default().fg(Color::DarkGray),
+					)));
+					let overlay = Paragraph::new(body)
+						.block(Block::default().borders(Borders::ALL).title("Session Notes"))
+						.wrap(Wrap { trim: true });
+					f.render_widget(overlay, area);
+				}
+
+				if run_mode {
+					let area = centered_rect(50, 30, size);
+					f.render_widget(ratatui::widgets::Clear, area);
+					let sel = sessions.get(selected);
+					let mut body = vec![
+						Line::from(format!("Run in {}:", sel.map(|s| s.name.as_str()).unwrap_or("unknown"))),
+						Line::from(""),
+						Line::from(format!("> {}█", run_buf)),
+						Line::from(""),
+					];
+					if let Some(dir) = sel.and_then(session_work_dir) {
+						body.push(Line::from(Span::styled(format!("in {}", dir.display()), Style::default().fg(Color::DarkGray))));
+					}
+					body.push(Line::from(Span::styled(
+						"Enter runs it in a window beside the agent · Esc cancels",
+						Style::default().fg(Color::DarkGray),
+					)));
+					let overlay = Paragraph::new(body)
+						.block(Block::default().borders(Borders::ALL).title("Run Command"))
+						.wrap(Wrap { trim: true });
+					f.render_widget(overlay, area);
+				}
+
+				if snooze_mode {
+					let area = centered_rect(50, 30, size);
+					f.render_widget(ratatui::widgets::Clear, area);
+					let title = tasks_state.selected().and_then(|i| tasks.get(i)).map(|t| t.title.as_str()).unwrap_or("unknown");
+					let mut body = vec![
+						Line::from(format!("Hide {title} until:")),
+						Line::from(""),
+						Line::from(format!("> {}█", snooze_buf)),
+						Line::from(""),
+					];
+					if let Ok(until) = parse_snooze(&snooze_buf) {
+						body.push(Line::from(Span::styled(
+							format!("Back: {}", describe_snooze(until)),
+							Style::default().fg(Color::DarkGray),
+						)));
+					}
+					body.push(Line::from(Span::styled(
+						"Tab: 1h / tonight / tomorrow · or 2h, 17:30, 2025-03-14 09:00 · Esc cancels",
+						Style::default().fg(Color::DarkGray),
+					)));
+					let overlay = Paragraph::new(body)
+						.block(Block::default().borders(Borders::ALL).title("Snooze Task"))
+						.wrap(Wrap { trim: true });
+					f.render_widget(overlay, area);
+				}
+
+				if deadline_mode {
+					let area = centered_rect(50, 30, size);
+					f.render_widget(ratatui::widgets::Clear, area);
+					let sel = sessions.get(selected);
+					let ran = sel
+						.and_then(|s| s.deadline.map(|(elapsed, _)| elapsed))
+						.or_else(|| sel.and_then(|s| tmux::session_created(&s.session_name)).and_then(|t| SystemTime::now().duration_since(t).ok()));
+					let mut body = vec![
+						Line::from(format!("{} should finish within (total runtime):", sel.map(|s| s.name.as_str()).unwrap_or("unknown"))),
+						Line::from(""),
+						Line::from(format!("> {}█", deadline_buf)),
+						Line::from(""),
+					];
+					if let Some(ran) = ran {
+						body.push(Line::from(Span::styled(format!("Running for {}", format_duration_hm(ran)), Style::default().fg(Color::DarkGray))));
+					}
+					body.push(Line::from(Span::styled(
+						"e.g. 2h, 90m · empty clears the deadline · Esc cancels",
+						Style::default().fg(Color::DarkGray),
+					)));
+					let overlay = Paragraph::new(body)
+						.block(Block::default().borders(Borders::ALL).title("Soft Deadline"))
+						.wrap(Wrap { trim: true });
+					f.render_widget(overlay, area);
+				}
+
+				if rename_mode {
+					let area = centered_rect(50, 25, size);
+					let clear = ratatui::widgets::Clear;
+					f.render_widget(clear, area);
+					let from = sessions.get(selected).map(|s| s.name.as_str()).unwrap_or("unknown");
+					let body = format!(
+						"Rename {}\n\n> {}█\n\nEnter to rename, Esc to cancel",
+						from, rename_buf
+					);
+					let overlay = Paragraph::new(body)
+						.block(Block::default().borders(Borders::ALL).title("Rename Session"))
+						.wrap(Wrap { trim: true });
+					f.render_widget(overlay, area);
+				}
+
+			if new_agent_mode {
+					let area = centered_rect(65, 50, size);
+					let clear = ratatui::widgets::Clear;
+					f.render_widget(clear, area);
+					let cursors = [
+						if new_agent_field == 0 { "█" } else { "" },
+						if new_agent_field == 1 { "█" } else { "" },
+						if new_agent_field == 2 { "█" } else { "" },
+						if new_agent_field == 3 { "█" } else { "" },
+					];
+					let due_display = &new_agent_due;
+					let default_model = cfg
+						.models
+						.get(&cfg.general.default_agent)
+						.map(String::as_str)
+						.unwrap_or("agent default");
+					let body = format!(
+						r#"What are you working on?
+	> {}{}
+
+	Who should be notified when done?
+	> {}{}
+
+	Due date (MM-DD, optionally HH:MM; blank for tomorrow)
+	> {}{}
+
+	Model (sonnet, opus, haiku; blank for {})
+	> {}{}
+
+	{}"#,
+						new_agent_buf.replace('\n', "\n  "), cursors[0],
+						new_agent_notify, cursors[1],
+						due_display, cursors[2],
+						default_model,
+						new_agent_model, cursors[3],
+						match (&new_agent_collision, &new_agent_similar) {
+							(Some(existing), _) => format!(
+								"⚠ {} already exists. Enter: save as {} · o: open the existing task · Esc: edit the description",
+								existing.file_name().unwrap_or_default().to_string_lossy(),
+								unique_task_path(existing).file_name().unwrap_or_default().to_string_lossy()
+							),
+							(None, Some(similar)) => format!(
+								"⚠ Similar task exists: {}. Enter: create anyway · o: open it instead · Esc: edit the description",
+								describe_similar(similar)
+							),
+							(None, None) => "Tab to switch fields, Enter to start, Alt+Enter new line, Ctrl+E $EDITOR, Esc to cancel".to_string(),
+						},
+					);
+					let overlay = Paragraph::new(body)
+						.block(
+							Block::default()
+								.borders(Borders::ALL)
+								.title("New Agent")
+								.border_style(Style::default().fg(Color::Cyan))
+								.title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+						)
+						.wrap(Wrap { trim: true });
+					f.render_widget(overlay, area);
+				}
+
+				// First-run hooks install prompt
+				if show_hooks_prompt {
+					let area = centered_rect(60, 50, size);
+					let clear = ratatui::widgets::Clear;
+					f.render_widget(clear, area);
+					let body = r#"Welcome to swarm!
+
+	swarm comes with Claude commands that help you
+	work more effectively with AI coding agents:
+
+	  /done       - End session, log work
+	  /interview  - Detailed task planning
+	  /log        - Save progress to task file
+	  /worktree   - Create isolated git worktree
+	  /poll-pr    - Monitor PR until CI green
+	  /qa-swarm   - QA test the swarm TUI
+
+	Install these commands to ~/.claude/commands/?
+
+	  [y] Yes, install (recommended)
+	  [n] No thanks"#;
+					let overlay = Paragraph::new(body)
+						.block(
+							Block::default()
+								.borders(Borders::ALL)
+								.title("Setup")
+								.border_style(Style::default().fg(Color::Green))
+								.title_style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
+						)
+						.wrap(Wrap { trim: true });
+					f.render_widget(overlay, area);
+				}
+			})?;
+		}
+
+		if event::poll(Duration::from_millis(if focused { 100 } else { 500 }))? {
+			needs_redraw = true;
+			let ev = event::read()?;
+			match &ev {
+				Event::FocusLost => focused = false,
+				Event::FocusGained => {
+					// Force a full refresh so the view is current the moment it's looked at
+					focused = true;
+					last_refresh = Instant::now() - Duration::from_millis(cfg.general.poll_interval_ms.min(5_000));
+					last_preview = Instant::now() - Duration::from_millis(cfg.general.preview_poll_ms);
+					last_tasks_reload = Instant::now() - Duration::from_millis(cfg.general.tasks_poll_ms);
+				}
+				// Some terminals don't report focus-in; typing means we're being looked at
+				Event::Key(_) => focused = true,
+				// Bracketed paste arrives as one event instead of a burst of key presses
+				Event::Paste(text) => {
+					let text = text.replace("\r\n", "\n").replace('\r', "\n");
+					if send_input_mode {
+						send_input_buf.push_str(&text);
+						reply_idx = None;
+						yolo_send_armed = false;
+					} else if new_agent_mode {
+						let single_line = text.replace('\n', " ");
+						match new_agent_field {
+							0 => new_agent_buf.push_str(&text),
+							1 => new_agent_notify.push_str(&single_line),
+							2 => new_agent_due.push_str(single_line.trim()),
+							3 => new_agent_model.push_str(single_line.trim()),
+							_ => {}
+						}
+					}
+				}
+				_ => {}
+			}
+			if let Event::Key(key) = ev {
 				if key.kind == KeyEventKind::Press {
 					if show_help && key.code != KeyCode::Char('?') && key.code != KeyCode::Esc {
 						continue;
 					}
+					// Land modal: closes once the run has finished; Esc stops a run
+					if land_view.is_some() {
+						if key.code == KeyCode::Esc && land_rx.is_some() {
+							if let Some(cancel) = land_cancel.take() {
+								cancel.cancel();
+							}
+							land_rx = None;
+							land_view = None;
+							status_message = Some(("Land cancelled".to_string(), Instant::now()));
+						} else if land_rx.is_none() && matches!(key.code, KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q')) {
+							land_view = None;
+						}
+						continue;
+					}
+					// History view: browse and search archived runs, Enter for one run's details
+					if let Some(runs) = &history_view {
+						let shown = runs.iter().filter(|r| r.matches(&history_query)).count();
+						if history_search {
+							match key.code {
+								KeyCode::Esc => {
+									history_search = false;
+									history_query.clear();
+								}
+								KeyCode::Enter => history_search = false,
+								KeyCode::Backspace => {
+									history_query.pop();
+								}
+								KeyCode::Char(c) if !c.is_control() => history_query.push(c),
+								_ => {}
+							}
+							history_idx = 0;
+							continue;
+						}
+						match key.code {
+							KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('O') | KeyCode::Char('s') if history_stats => history_stats = false,
+							KeyCode::Char('s') if !history_detail => history_stats = true,
+							KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('O') if history_detail => history_detail = false,
+							KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('O') => history_view = None,
+							KeyCode::Enter if shown > 0 => {
+								history_detail = !history_detail;
+								history_scroll = 0;
+							}
+							KeyCode::Up | KeyCode::Char('k') if history_detail => history_scroll = history_scroll.saturating_sub(1),
+							KeyCode::Down | KeyCode::Char('j') if history_detail => history_scroll += 1,
+							KeyCode::Up | KeyCode::Char('k') => history_idx = history_idx.saturating_sub(1),
+							KeyCode::Down | KeyCode::Char('j') => history_idx = (history_idx + 1).min(shown.saturating_sub(1)),
+							KeyCode::Char('/') if !history_detail => history_search = true,
+							_ => {}
+						}
+						continue;
+					}
+					// Process picker: signal one child without touching the agent
+					if let Some((session, list)) = &procs_view {
+						let armed = std::mem::take(&mut procs_kill_armed);
+						let chosen = list.get(procs_idx);
+						let agent_pid = procs::agent(list).map(|p| p.pid);
+						// Only what the agent started; the shell and the agent itself end with d
+						let target = chosen.filter(|p| agent_pid.is_some_and(|a| p.pid != a) && !(p.depth == 0 && p.is_shell()));
+						let signal = match key.code {
+							KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('K') => {
+								procs_view = None;
+								continue;
+							}
+							KeyCode::Up | KeyCode::Char('k') => {
+								procs_idx = procs_idx.saturating_sub(1);
+								continue;
+							}
+							KeyCode::Down | KeyCode::Char('j') => {
+								procs_idx = (procs_idx + 1).min(list.len().saturating_sub(1));
+								continue;
+							}
+							KeyCode::Char('i') => Some(procs::Signal::Interrupt),
+							KeyCode::Char('x') if armed => Some(procs::Signal::Kill),
+							KeyCode::Char('x') => {
+								procs_kill_armed = target.is_some();
+								None
+							}
+							KeyCode::Char('r') => None,
+							_ => continue,
+						};
+						match (signal, chosen, target) {
+							(Some(signal), Some(p), Some(_)) => {
+								let name = if signal == procs::Signal::Kill { "SIGKILL" } else { "SIGINT" };
+								let msg = match procs::send(p.pid, signal) {
+									Ok(()) => {
+										// Give it a moment to exit before the list is re-read
+										std::thread::sleep(Duration::from_millis(200));
+										format!("Sent {name} to {} ({})", p.name, p.pid)
+									}
+									Err(e) => format!("{name} to {} failed: {e}", p.pid),
+								};
+								status_message = Some((msg, Instant::now()));
+							}
+							(_, Some(p), None) if key.code != KeyCode::Char('r') => {
+								let what = if Some(p.pid) == agent_pid { "the agent itself" } else { "the pane's shell" };
+								status_message = Some((format!("{} ({}) is {what} - d ends the whole session", p.name, p.pid), Instant::now()));
+							}
+							_ => {}
+						}
+						// Re-read so the list shows what's left
+						let session = session.clone();
+						proc_monitor.refresh();
+						let list = tmux::pane_pid(&session).map(|pid| proc_monitor.tree(pid)).unwrap_or_default();
+						procs_idx = procs_idx.min(list.len().saturating_sub(1));
+						procs_view = Some((session, list));
+						continue;
+					}
+					// Command output: r runs it again, : edits it first
+					if let Some((session, command, _)) = &run_view {
+						match key.code {
+							KeyCode::Esc | KeyCode::Char('q') => run_view = None,
+							KeyCode::Char(':') => {
+								run_buf = command.clone();
+								run_view = None;
+								run_mode = true;
+							}
+							KeyCode::Char('r') => {
+								let command = command.clone();
+								if let Some(sel) = sessions.iter().find(|s| &s.session_name == session) {
+									if let Err(e) = run_in_session(sel, &command) {
+										status_message = Some((format!("Run failed: {e}"), Instant::now()));
+										run_view = None;
+									}
+								}
+							}
+							_ => {}
+						}
+						continue;
+					}
+					// Process log viewer: v or Esc closes it
+					if process_log_view.is_some() {
+						if matches!(key.code, KeyCode::Char('v') | KeyCode::Esc | KeyCode::Char('q')) {
+							process_log_view = None;
+						}
+						continue;
+					}
 					// Handle first-run hooks prompt
 					if show_hooks_prompt {
 						match key.code {
@@ -1481,64 +5566,481 @@ Install these commands to ~/.claude/commands/?
 					// Handle send-input mode first to capture typing.
 					if send_input_mode {
 						match key.code {
+							KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+								if let Some(text) = edit_in_editor(&mut terminal, &send_input_buf)? {
+									send_input_buf = text;
+								}
+								reply_idx = None;
+								yolo_send_armed = false;
+							}
+							// Alt+Enter / Ctrl+J start a new line; Enter sends
+							KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+								send_input_buf.push('\n');
+								yolo_send_armed = false;
+							}
+							KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => {
+								send_input_buf.push('\n');
+								yolo_send_armed = false;
+							}
 							KeyCode::Char(c) if !c.is_control() => {
 								send_input_buf.push(c);
+								reply_idx = None;
+								yolo_send_armed = false;
 								status_message =
 									Some((format!("Input: {}", send_input_buf), Instant::now()));
 							}
 							KeyCode::Backspace => {
 								send_input_buf.pop();
+								reply_idx = None;
+								yolo_send_armed = false;
+							}
+							KeyCode::Up if !input_recall.is_empty() => {
+								let idx = match recall_idx {
+									None => {
+										recall_draft = send_input_buf.clone();
+										0
+									}
+									Some(i) => (i + 1).min(input_recall.len() - 1),
+								};
+								recall_idx = Some(idx);
+								send_input_buf = input_recall[idx].clone();
+								reply_idx = None;
+								yolo_send_armed = false;
+							}
+							KeyCode::Down if recall_idx.is_some() => {
+								match recall_idx {
+									Some(0) | None => {
+										recall_idx = None;
+										send_input_buf = std::mem::take(&mut recall_draft);
+									}
+									Some(i) => {
+										recall_idx = Some(i - 1);
+										send_input_buf = input_recall[i - 1].clone();
+									}
+								}
+								yolo_send_armed = false;
+							}
+							KeyCode::Left | KeyCode::Right if !replies.is_empty() => {
+								let last = replies.len() - 1;
+								let idx = match (reply_idx, key.code) {
+									(None, KeyCode::Left) => last,
+									(None, _) => 0,
+									(Some(i), KeyCode::Left) => if i == 0 { last } else { i - 1 },
+									(Some(i), _) => if i == last { 0 } else { i + 1 },
+								};
+								reply_idx = Some(idx);
+								send_input_buf = replies[idx].text.clone();
+								yolo_send_armed = false;
 							}
 							KeyCode::Enter => {
-								if let Some(sel) = sessions.get(selected) {
-									if !send_input_buf.is_empty() {
-										let msg = send_input_buf.clone();
+								let targets: Vec<&AgentSession> = match &broadcast_group {
+									Some(group) => group_members(&sessions, group)
+										.into_iter()
+										.filter(|s| s.status != AgentStatus::Done)
+										.collect(),
+									None => sessions.get(selected).into_iter().collect(),
+								};
+								if targets.iter().any(|s| s.is_yolo) && !yolo_send_armed && !send_input_buf.is_empty() {
+									// First Enter on a YOLO session only arms the send
+									yolo_send_armed = true;
+									continue;
+								}
+								if !send_input_buf.is_empty() && !targets.is_empty() {
+									let msg = send_input_buf.clone();
+									for sel in &targets {
+										record_input(cfg, &sel.session_name);
+										let _ = input_history::record(&sel.session_name, &msg);
 										let _ = send_keys(&sel.session_name, &msg);
-										status_message = Some((
-											format!("Sent to {}: {}", sel.name, msg),
-											Instant::now(),
-										));
 									}
+									let to = match (&broadcast_group, targets.as_slice()) {
+										(None, [sel]) => sel.name.clone(),
+										_ => format!("{} agents", targets.len()),
+									};
+									status_message = Some((format!("Sent to {to}: {msg}"), Instant::now()));
 								}
 								send_input_mode = false;
 								send_input_buf.clear();
+								yolo_send_armed = false;
+								broadcast_group = None;
 							}
 							KeyCode::Esc => {
 								send_input_mode = false;
 								send_input_buf.clear();
+								yolo_send_armed = false;
+								broadcast_group = None;
+							}
+							_ => {}
+						}
+						continue;
+					}
+					// Compare view: toggle diffs, pick a winner, or close
+					if let Some((left, right)) = compare_view.clone() {
+						match key.code {
+							KeyCode::Tab | KeyCode::Char('D') => {
+								compare_diff = !compare_diff;
+								if compare_diff {
+									let diff_for = |name: &str| {
+										sessions
+											.iter()
+											.find(|s| s.session_name == name)
+											.map(|s| session_diff_lines(cfg, s))
+											.unwrap_or_default()
+									};
+									compare_diffs = (diff_for(&left), diff_for(&right));
+								}
+							}
+							KeyCode::Char(c @ ('1' | '2')) => {
+								let (keep, drop) = if c == '1' { (left, right) } else { (right, left) };
+								if let Some(idx) = sessions.iter().position(|s| s.session_name == keep) {
+									selected = idx;
+									list_state.select(Some(selected));
+								}
+								// Reuse the kill confirmation for the other session
+								pending_kill_session = Some(drop);
+								confirm_kill_mode = true;
+								compare_view = None;
+							}
+							KeyCode::Esc | KeyCode::Char('C') | KeyCode::Char('q') => {
+								compare_view = None;
+							}
+							_ => {}
+						}
+						continue;
+					}
+					// Filter input: narrows the list as you type; Enter keeps it, Esc clears it
+					if filter_mode {
+						match key.code {
+							KeyCode::Char(c) if !c.is_control() => agent_filter.push(c),
+							KeyCode::Backspace => {
+								agent_filter.pop();
+							}
+							KeyCode::Enter => filter_mode = false,
+							KeyCode::Esc => {
+								filter_mode = false;
+								agent_filter.clear();
+								active_view = None;
+								task_filter = None;
+							}
+							_ => {}
+						}
+						let hidden = hidden_rows(&sessions, &collapsed_groups, &agent_filter);
+						if hidden.get(selected) == Some(&true) {
+							selected = visible_selection(&sessions, selected, &hidden);
+							list_state.select(sessions.get(selected).map(|_| selected));
+						}
+						continue;
+					}
+					// Notes modal: Enter adds the typed note, Backspace on an empty line removes the last
+					if notes_mode {
+						let Some(sel) = sessions.get_mut(selected) else {
+							notes_mode = false;
+							continue;
+						};
+						match key.code {
+							KeyCode::Char(c) if !c.is_control() => notes_buf.push(c),
+							KeyCode::Backspace if notes_buf.is_empty() => {
+								sel.notes.pop();
+								if let Err(e) = write_session_notes(&sel.session_name, &sel.notes) {
+									status_message = Some((format!("Saving notes failed: {e}"), Instant::now()));
+								}
+							}
+							KeyCode::Backspace => {
+								notes_buf.pop();
+							}
+							KeyCode::Enter if notes_buf.trim().is_empty() => notes_mode = false,
+							KeyCode::Enter => {
+								sel.notes.push(notes_buf.trim().to_string());
+								notes_buf.clear();
+								if let Err(e) = write_session_notes(&sel.session_name, &sel.notes) {
+									status_message = Some((format!("Saving notes failed: {e}"), Instant::now()));
+								}
+							}
+							KeyCode::Esc => notes_mode = false,
+							_ => {}
+						}
+						continue;
+					}
+					// Command prompt: Enter runs it and shows the output
+					if run_mode {
+						match key.code {
+							KeyCode::Char(c) if !c.is_control() => run_buf.push(c),
+							KeyCode::Backspace => {
+								run_buf.pop();
+							}
+							KeyCode::Enter if !run_buf.trim().is_empty() => {
+								if let Some(sel) = sessions.get(selected) {
+									let command = run_buf.trim().to_string();
+									match run_in_session(sel, &command) {
+										Ok(dir) => run_view = Some((sel.session_name.clone(), command, dir)),
+										Err(e) => status_message = Some((format!("Run failed: {e}"), Instant::now())),
+									}
+								}
+								run_mode = false;
+							}
+							KeyCode::Esc => run_mode = false,
+							_ => {}
+						}
+						continue;
+					}
+					// Snooze prompt: Enter hides the task until then
+					if snooze_mode {
+						match key.code {
+							KeyCode::Char(c) if !c.is_control() => snooze_buf.push(c),
+							KeyCode::Backspace => {
+								snooze_buf.pop();
+							}
+							KeyCode::Tab => {
+								let next = SNOOZE_PRESETS
+									.iter()
+									.position(|p| *p == snooze_buf.trim())
+									.map_or(0, |i| (i + 1) % SNOOZE_PRESETS.len());
+								snooze_buf = SNOOZE_PRESETS[next].to_string();
+							}
+							KeyCode::Enter => {
+								if let Some(task) = tasks_state.selected().and_then(|i| tasks.get(i)) {
+									let result = parse_snooze(&snooze_buf).and_then(|until| {
+										set_frontmatter(&task.path, "snoozed_until", Some(&until.format("%Y-%m-%d %H:%M").to_string()))
+											.map(|_| until)
+									});
+									status_message = Some((
+										match result {
+											Ok(until) => format!("Snoozed {} until {}", task.title, describe_snooze(until)),
+											Err(e) => format!("Not snoozed: {e}"),
+										},
+										Instant::now(),
+									));
+									// Reload now so it leaves the list
+									last_tasks_reload = Instant::now() - Duration::from_millis(cfg.general.tasks_poll_ms);
+								}
+								snooze_mode = false;
+							}
+							KeyCode::Esc => snooze_mode = false,
+							_ => {}
+						}
+						continue;
+					}
+					// Soft deadline prompt: Enter sets (or with nothing typed clears) it
+					if deadline_mode {
+						match key.code {
+							KeyCode::Char(c) if !c.is_control() => deadline_buf.push(c),
+							KeyCode::Backspace => {
+								deadline_buf.pop();
+							}
+							KeyCode::Enter => {
+								if let Some(sel) = sessions.get_mut(selected) {
+									let budget = match deadline_buf.trim() {
+										"" => Ok(None),
+										input => parse_duration_arg(input).map(Some),
+									};
+									let result = budget.and_then(|b| write_session_deadline(&sel.session_name, b).map(|_| b));
+									status_message = Some((
+										match result {
+											Ok(Some(budget)) => {
+												sel.deadline = read_session_deadline(&sel.session_name);
+												format!("{} should finish within {}", sel.name, format_duration_hm(budget))
+											}
+											Ok(None) => {
+												sel.deadline = None;
+												format!("Cleared the deadline on {}", sel.name)
+											}
+											Err(e) => format!("Deadline not set: {e}"),
+										},
+										Instant::now(),
+									));
+								}
+								deadline_mode = false;
+							}
+							KeyCode::Esc => deadline_mode = false,
+							_ => {}
+						}
+						continue;
+					}
+					// Handle rename modal
+					if rename_mode {
+						match key.code {
+							KeyCode::Char(c) if !c.is_control() => rename_buf.push(c),
+							KeyCode::Backspace => {
+								rename_buf.pop();
+							}
+							KeyCode::Enter => {
+								if let Some(sel) = sessions.get(selected) {
+									match rename_session(cfg, &sel.name, &rename_buf) {
+										Ok(new_name) => {
+											status_message = Some((
+												format!("Renamed {} → {}", sel.name, new_name),
+												Instant::now(),
+											));
+											let full_session_name = format!("{SWARM_PREFIX}{new_name}");
+											if let Some(status) = prev_status.remove(&sel.session_name) {
+												prev_status.insert(full_session_name.clone(), status);
+											}
+											if let Ok(updated) = collect_sessions(cfg) {
+												sessions = updated;
+												selected = sessions
+													.iter()
+													.position(|s| s.session_name == full_session_name)
+													.unwrap_or(selected.min(sessions.len().saturating_sub(1)));
+												list_state.select(sessions.get(selected).map(|_| selected));
+											}
+										}
+										Err(e) => {
+											status_message = Some((format!("Rename failed: {e}"), Instant::now()));
+										}
+									}
+								}
+								rename_mode = false;
+							}
+							KeyCode::Esc => {
+								rename_mode = false;
+							}
+							_ => {}
+						}
+						continue;
+					}
+					// Handle handoff modal
+					if handoff_mode {
+						match key.code {
+							KeyCode::Char(c) if !c.is_control() => {
+								if handoff_field == 0 {
+									handoff_agent_buf.push(c);
+								} else {
+									handoff_note_buf.push(c);
+								}
+							}
+							KeyCode::Backspace => {
+								if handoff_field == 0 {
+									handoff_agent_buf.pop();
+								} else {
+									handoff_note_buf.pop();
+								}
+							}
+							KeyCode::Tab | KeyCode::BackTab => {
+								handoff_field = 1 - handoff_field;
+							}
+							KeyCode::Enter => {
+								if let Some(sel) = sessions.get(selected) {
+									let agent = handoff_agent_buf.trim();
+									let agent = if agent.is_empty() { sel.agent.clone() } else { agent.to_string() };
+									let note = Some(handoff_note_buf.trim()).filter(|n| !n.is_empty());
+									match handoff_session(cfg, sel, &agent, note, None) {
+										Ok(new_name) => {
+											status_message = Some((
+												format!("Handed off {} → {} ({})", sel.name, new_name, agent),
+												Instant::now(),
+											));
+											std::thread::sleep(Duration::from_millis(300));
+											if let Ok(updated) = collect_sessions(cfg) {
+												sessions = updated;
+												let full_session_name = format!("{SWARM_PREFIX}{new_name}");
+												selected = sessions
+													.iter()
+													.position(|s| s.session_name == full_session_name)
+													.unwrap_or(sessions.len().saturating_sub(1));
+												list_state.select(sessions.get(selected).map(|_| selected));
+											}
+										}
+										Err(e) => {
+											status_message = Some((format!("Handoff failed: {e}"), Instant::now()));
+										}
+									}
+								}
+								handoff_mode = false;
+							}
+							KeyCode::Esc => {
+								handoff_mode = false;
 							}
 							_ => {}
 						}
 						continue;
 					}
 					// Handle new agent mode (name your work prompt)
-					// Fields: 0 = description, 1 = notify, 2 = due, 3 = workspace
+					// Fields: 0 = description, 1 = notify, 2 = due, 3 = model
 					if new_agent_mode {
 						match key.code {
+							KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) && new_agent_field == 0 => {
+								if let Some(text) = edit_in_editor(&mut terminal, &new_agent_buf)? {
+									new_agent_buf = text;
+								}
+							}
+							KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) && new_agent_field == 0 => {
+								new_agent_buf.push('\n');
+							}
+							KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) && new_agent_field == 0 => {
+								new_agent_buf.push('\n');
+							}
+							KeyCode::Char('o') if new_agent_collision.is_some() || new_agent_similar.is_some() => {
+								// Show the existing task instead of creating another
+								let existing = new_agent_collision
+									.take()
+									.or_else(|| new_agent_similar.take().map(|t| t.path))
+									.unwrap_or_default();
+								tasks = load_tasks(cfg);
+								if let Some(idx) = tasks.iter().position(|t| t.path == existing) {
+									showing_tasks = true;
+									showing_daily = false;
+									tasks_state.select(Some(idx));
+								} else {
+									// Done or archived: not in the tasks view, so open the file
+									let app = obsidian::open(cfg, &existing);
+									status_message = Some((format!("Opened {} in {app}", existing.display()), Instant::now()));
+								}
+								new_agent_mode = false;
+								new_agent_buf.clear();
+								new_agent_notify = String::from("no one");
+								new_agent_due = String::from("tomorrow");
+								new_agent_model.clear();
+								new_agent_field = 0;
+							}
+							KeyCode::Esc if new_agent_collision.is_some() || new_agent_similar.is_some() => {
+								new_agent_collision = None;
+								new_agent_similar = None;
+								new_agent_field = 0;
+							}
 							KeyCode::Char(c) if !c.is_control() => {
+								new_agent_collision = None;
+								new_agent_similar = None;
 								match new_agent_field {
 									0 => new_agent_buf.push(c),
 									1 => new_agent_notify.push(c),
 									2 => new_agent_due.push(c),
+									3 => new_agent_model.push(c),
 									_ => {}
 								}
 							}
 							KeyCode::Backspace => {
+								new_agent_collision = None;
+								new_agent_similar = None;
 								match new_agent_field {
 									0 => { new_agent_buf.pop(); }
 									1 => { new_agent_notify.pop(); }
 									2 => { new_agent_due.pop(); }
+									3 => { new_agent_model.pop(); }
 									_ => {}
 								}
 							}
 							KeyCode::Tab => {
-								new_agent_field = (new_agent_field + 1) % 3;
+								new_agent_field = (new_agent_field + 1) % 4;
 							}
 							KeyCode::BackTab => {
-								new_agent_field = if new_agent_field == 0 { 2 } else { new_agent_field - 1 };
+								new_agent_field = if new_agent_field == 0 { 3 } else { new_agent_field - 1 };
 							}
 							KeyCode::Enter => {
 								if !new_agent_buf.is_empty() {
+									// Never overwrite a task with the same slug: ask first, then save alongside it.
+									// A similar title (open or archived) gets one warning too.
+									if new_agent_collision.is_none() && new_agent_similar.is_none() {
+										let existing = task_file_path(cfg, task_title(&new_agent_buf));
+										if existing.exists() {
+											new_agent_collision = Some(existing);
+											continue;
+										}
+										if let Some(similar) = similar_task(cfg, task_title(&new_agent_buf)) {
+											new_agent_similar = Some(similar);
+											continue;
+										}
+									}
+									new_agent_similar = None;
+									let on_existing = if new_agent_collision.take().is_some() { OnExisting::Suffix } else { OnExisting::Fail };
 									// Create task file and start agent
 									let notify = if new_agent_notify.trim().is_empty() || new_agent_notify.trim().to_lowercase() == "no one" {
 										None
@@ -1555,6 +6057,8 @@ Install these commands to ~/.claude/commands/?
 										&new_agent_buf,
 										notify.as_deref(),
 										due.as_deref(),
+										Some(new_agent_model.as_str()),
+										on_existing,
 									) {
 										Ok(session_name) => {
 											status_message = Some((
@@ -1593,6 +6097,7 @@ Install these commands to ~/.claude/commands/?
 								new_agent_buf.clear();
 								new_agent_notify = String::from("no one");
 								new_agent_due = String::from("tomorrow");
+								new_agent_model.clear();
 								new_agent_field = 0;
 							}
 							KeyCode::Esc => {
@@ -1600,12 +6105,146 @@ Install these commands to ~/.claude/commands/?
 								new_agent_buf.clear();
 								new_agent_notify = String::from("no one");
 								new_agent_due = String::from("tomorrow");
+								new_agent_model.clear();
 								new_agent_field = 0;
 							}
 							_ => {}
 						}
 						continue;
 					}
+					// Group kill confirmation: y kills every member, anything else backs out
+					if let Some(group) = confirm_group_kill.take() {
+						if key.code == KeyCode::Char('y') {
+							let members: Vec<AgentSession> =
+								group_members(&sessions, &group).into_iter().cloned().collect();
+							let mut killed = 0;
+							let pinned = members.iter().filter(|m| m.pinned).count();
+							for m in members.iter().filter(|m| !m.pinned) {
+								if m.status != AgentStatus::Done {
+									run_hook(cfg, "kill", m);
+								}
+								if mark_done(m, cfg).is_ok() {
+									record_completed(&mut completed_this_run, m);
+									killed += 1;
+								}
+							}
+							status_message = Some((
+								if pinned > 0 {
+									format!("Killed {killed} agent(s) in {group}, kept {pinned} pinned")
+								} else {
+									format!("Killed {killed} agent(s) in {group}")
+								},
+								Instant::now(),
+							));
+							collapsed_groups.remove(&group);
+							if let Ok(updated) = collect_sessions(cfg) {
+								sessions = updated;
+								selected = selected.min(sessions.len().saturating_sub(1));
+								list_state.select(sessions.get(selected).map(|_| selected));
+							}
+						} else {
+							status_message = Some(("Cancelled - group not killed".to_string(), Instant::now()));
+						}
+						continue;
+					}
+					// Group menu (G): act on every member of the selected agent's group
+					if group_mode {
+						group_mode = false;
+						let Some(group) = sessions.get(selected).and_then(|s| s.group.clone()) else {
+							continue;
+						};
+						match key.code {
+							KeyCode::Char('b') => {
+								broadcast_group = Some(group);
+								send_input_mode = true;
+								send_input_buf.clear();
+								replies = Vec::new();
+								reply_idx = None;
+								input_recall = Vec::new();
+								recall_idx = None;
+								recall_draft.clear();
+								yolo_send_armed = false;
+							}
+							KeyCode::Char('p') => {
+								let paused = group_members(&sessions, &group)
+									.into_iter()
+									.filter(|s| matches!(s.status, AgentStatus::Running | AgentStatus::NeedsInput))
+									.filter(|s| tmux::send_special_key(&s.session_name, "Escape").is_ok())
+									.count();
+								status_message = Some((format!("Paused {paused} agent(s) in {group}"), Instant::now()));
+							}
+							KeyCode::Char('x') => confirm_group_kill = Some(group),
+							KeyCode::Char('z') if !collapsed_groups.remove(&group) => {
+								selected = group_head(&sessions, selected);
+								list_state.select(Some(selected));
+								collapsed_groups.insert(group);
+							}
+							_ => {}
+						}
+						continue;
+					}
+					if copy_mode {
+						copy_mode = false;
+						let sel = sessions.get(selected);
+						let text = match key.code {
+							KeyCode::Char('p') => sel.map(|s| {
+								clean_preview(&tail_lines(&s.log_path, PREVIEW_DEPTH).unwrap_or_default()).join("\n")
+							}),
+							KeyCode::Char('a') => sel.map(|s| format!("tmux attach -t {}", s.session_name)),
+							KeyCode::Char('t') => sel
+								.and_then(|s| s.task.as_ref())
+								.map(|t| t.path.display().to_string()),
+							_ => None,
+						};
+						if let Some(text) = text {
+							status_message = Some((
+								match clipboard::copy(&text) {
+									Ok(how) => format!("Copied {} line(s) to the {how}", text.lines().count()),
+									Err(e) => format!("Copy failed: {e}"),
+								},
+								Instant::now(),
+							));
+						}
+						continue;
+					}
+					// Agenda (A in the tasks view): move between days; the task picked there
+					// is the tasks view's selection, so Enter, N, o, v... act on it as usual
+					if showing_tasks && agenda_view && !send_input_mode {
+						let handled = match key.code {
+							KeyCode::Left => Some(agenda_offset - 1),
+							KeyCode::Right => Some(agenda_offset + 1),
+							KeyCode::Char('[') => Some(agenda_offset - 7),
+							KeyCode::Char(']') => Some(agenda_offset + 7),
+							KeyCode::Char('.') => Some(0),
+							KeyCode::Up | KeyCode::Char('k') => {
+								agenda_idx = agenda_idx.saturating_sub(1);
+								Some(agenda_offset)
+							}
+							KeyCode::Down | KeyCode::Char('j') => {
+								agenda_idx += 1;
+								Some(agenda_offset)
+							}
+							KeyCode::Char('A') | KeyCode::Esc => {
+								agenda_view = false;
+								if tasks_state.selected().is_none() && !tasks.is_empty() {
+									tasks_state.select(Some(0));
+								}
+								None
+							}
+							_ => None,
+						};
+						if let Some(offset) = handled {
+							let offset = offset.clamp(0, AGENDA_DAYS - 1 - agenda_lead(Local::now().date_naive()));
+							if offset != agenda_offset {
+								agenda_offset = offset;
+								agenda_idx = 0;
+							}
+							agenda_idx = sync_agenda_selection(&tasks, agenda_offset, agenda_idx, &mut tasks_state);
+						}
+						if handled.is_some() || !agenda_view {
+							continue;
+						}
+					}
 					match key.code {
 						KeyCode::Char('q') if !send_input_mode => break,
 						KeyCode::Char('t') if !send_input_mode => {
@@ -1641,6 +6280,7 @@ Install these commands to ~/.claude/commands/?
 								new_agent_buf.clear();
 								new_agent_notify = String::from("no one");
 								new_agent_due = String::from("tomorrow");
+								new_agent_model.clear();
 								new_agent_field = 0;
 							} else if send_input_mode {
 								send_input_mode = false;
@@ -1651,6 +6291,10 @@ Install these commands to ~/.claude/commands/?
 							} else if showing_tasks {
 								// Go back to agents view
 								showing_tasks = false;
+							} else if !agent_filter.is_empty() {
+								agent_filter.clear();
+								active_view = None;
+								task_filter = None;
 							}
 							show_help = false;
 						}
@@ -1669,20 +6313,35 @@ Install these commands to ~/.claude/commands/?
 									}
 								}
 							} else if showing_tasks {
-								if let Some(sel) = tasks_state.selected() {
-									if sel + 1 < tasks.len() {
-										tasks_state.select(Some(sel + 1));
-									}
+								if let Some(next) = tasks_state.selected().and_then(|sel| shown.iter().copied().find(|&i| i > sel)) {
+									tasks_state.select(Some(next));
 								}
-							} else if selected + 1 < sessions.len() {
-								selected += 1;
+							} else if let Some(next) =
+								next_visible(&hidden_rows(&sessions, &collapsed_groups, &agent_filter), selected, true)
+							{
+								selected = next;
 								list_state.select(Some(selected));
 								// Update preview cache for newly selected session
 								if let Some(sel) = sessions.get(selected) {
-									if let Ok(lines) = capture_tail_ansi(&sel.session_name, 200) {
-										cached_preview = Some((sel.session_name.clone(), lines));
+									refresh_preview(sel, &mut preview_cache);
+								}
+							}
+						}
+						KeyCode::Tab | KeyCode::Char('!')
+							if !showing_tasks && !showing_daily && !send_input_mode =>
+						{
+							// Triage: hop to the next agent waiting on input or stopped with an error
+							match next_blocked(&sessions, selected) {
+								Some(idx) => {
+									selected = idx;
+									list_state.select(Some(selected));
+									if let Some(sel) = sessions.get(selected) {
+										refresh_preview(sel, &mut preview_cache);
 									}
 								}
+								None => {
+									status_message = Some(("No agents need input".to_string(), Instant::now()));
+								}
 							}
 						}
 						KeyCode::Char('k') | KeyCode::Up => {
@@ -1693,31 +6352,105 @@ Install these commands to ~/.claude/commands/?
 									}
 								}
 							} else if showing_tasks {
-								if let Some(sel) = tasks_state.selected() {
-									if sel > 0 {
-										tasks_state.select(Some(sel - 1));
-									}
+								if let Some(prev) = tasks_state.selected().and_then(|sel| shown.iter().rev().copied().find(|&i| i < sel)) {
+									tasks_state.select(Some(prev));
 								}
-							} else if selected > 0 {
-								selected -= 1;
+							} else if let Some(prev) =
+								next_visible(&hidden_rows(&sessions, &collapsed_groups, &agent_filter), selected, false)
+							{
+								selected = prev;
 								list_state.select(Some(selected));
 								// Update preview cache for newly selected session
 								if let Some(sel) = sessions.get(selected) {
-									if let Ok(lines) = capture_tail_ansi(&sel.session_name, 200) {
-										cached_preview = Some((sel.session_name.clone(), lines));
-									}
+									refresh_preview(sel, &mut preview_cache);
 								}
 							}
 						}
-						KeyCode::Char('d')
-							if !showing_tasks
-								&& !send_input_mode
-								&& !confirm_kill_mode =>
-						{
-							if let Some(sel) = sessions.get(selected) {
-								// Show confirmation instead of immediately killing
-								confirm_kill_mode = true;
-								pending_kill_session = Some(sel.session_name.clone());
+						KeyCode::Char('d')
+							if !showing_tasks
+								&& !send_input_mode
+								&& !confirm_kill_mode =>
+						{
+							if let Some(sel) = sessions.get(selected) {
+								// Show confirmation instead of immediately killing
+								confirm_kill_mode = true;
+								pending_kill_session = Some(sel.session_name.clone());
+							}
+						}
+						KeyCode::Char(':') if !showing_tasks && !showing_daily && !send_input_mode && !sessions.is_empty() => {
+							run_mode = true;
+						}
+						KeyCode::Char('K') if !showing_tasks && !showing_daily && !send_input_mode => {
+							if let Some(sel) = sessions.get(selected) {
+								proc_monitor.refresh();
+								let list = tmux::pane_pid(&sel.session_name).map(|pid| proc_monitor.tree(pid)).unwrap_or_default();
+								// Start on the busiest child, the usual reason to be here
+								let agent_depth = procs::agent(&list).map_or(0, |a| a.depth);
+								procs_idx = list
+									.iter()
+									.enumerate()
+									.filter(|(_, p)| p.depth > agent_depth && !p.is_shell())
+									.max_by(|a, b| a.1.cpu.total_cmp(&b.1.cpu))
+									.map_or(0, |(idx, _)| idx);
+								procs_kill_armed = false;
+								procs_view = Some((sel.session_name.clone(), list));
+							}
+						}
+						KeyCode::Char('O') if !send_input_mode => {
+							history_view = Some(history::load());
+							history_idx = 0;
+							history_query.clear();
+							history_detail = false;
+							history_stats = false;
+						}
+						KeyCode::Char('E') if !send_input_mode => {
+							show_console = !show_console;
+						}
+						KeyCode::Char('M') if !send_input_mode => {
+							// Do not disturb: off → 1h → until unmuted → off
+							let (result, msg) = match notify::mute_state() {
+								None => (notify::mute(Some(Duration::from_secs(3600))), "Muted for 1h (M again: until unmuted)"),
+								Some(Some(_)) => (notify::mute(None), "Muted until unmuted (M again: notifications on)"),
+								Some(None) => (notify::unmute(), "Notifications on"),
+							};
+							status_message = Some((
+								match result {
+									Ok(()) => msg.to_string(),
+									Err(e) => format!("Mute failed: {e}"),
+								},
+								Instant::now(),
+							));
+						}
+						KeyCode::Char('G') if !showing_tasks && !showing_daily && !send_input_mode => {
+							status_message = Some((
+								match sessions.get(selected).and_then(|s| s.group.as_ref()) {
+									Some(group) => {
+										group_mode = true;
+										format!("Group {group}: b broadcast · p pause all · x kill all · z collapse/expand (any other key cancels)")
+									}
+									None => "Not in a group - start agents with swarm new --group NAME".to_string(),
+								},
+								Instant::now(),
+							));
+						}
+						KeyCode::Char('y') if !confirm_kill_mode && !showing_daily => {
+							if showing_tasks {
+								if let Some(task) = tasks_state.selected().and_then(|i| tasks.get(i)) {
+									let path = task.path.display().to_string();
+									status_message = Some((
+										match clipboard::copy(&path) {
+											Ok(how) => format!("Copied {path} to the {how}"),
+											Err(e) => format!("Copy failed: {e}"),
+										},
+										Instant::now(),
+									));
+								}
+							} else if sessions.get(selected).is_some() {
+								copy_mode = true;
+								status_message = Some((
+									"Copy: p preview · a attach command · t task path (any other key cancels)".to_string(),
+									Instant::now(),
+								));
 							}
 						}
 						// Handle confirmation mode responses
@@ -1726,8 +6459,12 @@ Install these commands to ~/.claude/commands/?
 								if let Some(sel) =
 									sessions.iter().find(|s| s.session_name == session_name)
 								{
+									if sel.status != AgentStatus::Done {
+										run_hook(cfg, "kill", sel);
+									}
 									match mark_done(sel, cfg) {
 										Ok(()) => {
+											record_completed(&mut completed_this_run, sel);
 											status_message = Some((
 												format!("Marked {} done", sel.name),
 												Instant::now(),
@@ -1745,7 +6482,7 @@ Install these commands to ~/.claude/commands/?
 											}
 										}
 										Err(e) => {
-											eprintln!("Failed to mark done: {e}");
+											console::error("mark done", e);
 										}
 									}
 								}
@@ -1754,10 +6491,238 @@ Install these commands to ~/.claude/commands/?
 						}
 						KeyCode::Char('a') if !showing_tasks && !send_input_mode => {
 							// Attach to selected agent (full tmux takeover)
+							if tmux_missing {
+								status_message = Some((
+									"Attach needs tmux (brew install tmux)".to_string(),
+									Instant::now(),
+								));
+							} else if let Some(sel) = sessions.get(selected) {
+								let snapshot = AttachSnapshot::take(sel, &sessions, &tasks);
+								attach_to(&mut terminal, sel, false)?;
+								attached_total += snapshot.started.elapsed();
+								attach_return = Some(snapshot);
+								last_refresh = Instant::now() - Duration::from_millis(cfg.general.poll_interval_ms.min(5_000));
+							}
+						}
+						KeyCode::Char('A') if !showing_tasks && !send_input_mode => {
+							// Watch the selected agent without being able to type into it
+							if tmux_missing {
+								status_message = Some((
+									"Attach needs tmux (brew install tmux)".to_string(),
+									Instant::now(),
+								));
+							} else if let Some(sel) = sessions.get(selected) {
+								let snapshot = AttachSnapshot::take(sel, &sessions, &tasks);
+								attach_to(&mut terminal, sel, true)?;
+								attached_total += snapshot.started.elapsed();
+								attach_return = Some(snapshot);
+								last_refresh = Instant::now() - Duration::from_millis(cfg.general.poll_interval_ms.min(5_000));
+							}
+						}
+						KeyCode::Char('r')
+							if !showing_tasks && !showing_daily && !send_input_mode =>
+						{
+							// Respawn an agent whose process exited
+							if let Some(sel) = sessions.get(selected) {
+								if sel.exit_code.is_some() {
+									match tmux::respawn_pane(&sel.session_name) {
+										Ok(()) => {
+											status_message = Some((
+												format!("Respawned {}", sel.name),
+												Instant::now(),
+											));
+										}
+										Err(e) => {
+											status_message = Some((
+												format!("Failed to respawn: {e}"),
+												Instant::now(),
+											));
+										}
+									}
+								} else {
+									status_message = Some((
+										format!("{} is still running", sel.name),
+										Instant::now(),
+									));
+								}
+							}
+						}
+						KeyCode::Char('C') if !showing_tasks && !showing_daily && !send_input_mode => {
+							// Mark a session, then C on another to compare them side by side
+							if let Some(sel) = sessions.get(selected) {
+								match compare_mark.take() {
+									Some(first) if first != sel.session_name
+										&& sessions.iter().any(|s| s.session_name == first) =>
+									{
+										refresh_preview(sel, &mut preview_cache);
+										if let Some(other) = sessions.iter().find(|s| s.session_name == first) {
+											refresh_preview(other, &mut preview_cache);
+										}
+										compare_view = Some((first, sel.session_name.clone()));
+										compare_diff = false;
+									}
+									_ => {
+										// Variants compare with their next sibling right away
+										let sibling = sel.variant.as_ref().and_then(|v| {
+											let group: Vec<&AgentSession> = sessions
+												.iter()
+												.filter(|s| s.variant.as_ref().map(|o| o.group == v.group).unwrap_or(false))
+												.collect();
+											let pos = group.iter().position(|s| s.session_name == sel.session_name)?;
+											(group.len() > 1).then(|| group[(pos + 1) % group.len()])
+										});
+										if let Some(other) = sibling {
+											refresh_preview(sel, &mut preview_cache);
+											refresh_preview(other, &mut preview_cache);
+											compare_view = Some((sel.session_name.clone(), other.session_name.clone()));
+											compare_diff = true;
+											compare_diffs = (session_diff_lines(cfg, sel), session_diff_lines(cfg, other));
+										} else {
+											compare_mark = Some(sel.session_name.clone());
+											status_message = Some((
+												format!("Comparing {} - select another agent and press C", sel.name),
+												Instant::now(),
+											));
+										}
+									}
+								}
+							}
+						}
+						KeyCode::Char('N')
+							if !showing_tasks && !showing_daily && !send_input_mode && sessions.get(selected).is_some() =>
+						{
+							notes_mode = true;
+							notes_buf.clear();
+						}
+						KeyCode::Char('T')
+							if !showing_tasks && !showing_daily && !send_input_mode && sessions.get(selected).is_some() =>
+						{
+							deadline_mode = true;
+							deadline_buf = sessions[selected]
+								.deadline
+								.map(|(_, budget)| format_duration_hm(budget))
+								.unwrap_or_default();
+						}
+						KeyCode::Char('P') if !showing_tasks && !showing_daily && !send_input_mode => {
+							// Pin: protect from group kills, limit kills and orphan cleanup
+							if let Some(sel) = sessions.get_mut(selected) {
+								let result = session_pin_path(&sel.session_name).and_then(|p| {
+									if sel.pinned {
+										fs::remove_file(p)?;
+									} else {
+										fs::write(p, "")?;
+									}
+									Ok(())
+								});
+								status_message = Some((
+									match result {
+										Ok(()) => {
+											sel.pinned = !sel.pinned;
+											if sel.pinned {
+												format!("Pinned {} - kept out of group kills, limit kills and cleanup", sel.name)
+											} else {
+												format!("Unpinned {}", sel.name)
+											}
+										}
+										Err(e) => format!("Pin failed: {e}"),
+									},
+									Instant::now(),
+								));
+							}
+						}
+						KeyCode::Char('/') if !showing_tasks && !showing_daily && !send_input_mode => {
+							filter_mode = true;
+						}
+						KeyCode::Char('f') if !showing_daily && !send_input_mode && cfg.views.is_empty() => {
+							status_message = Some(("No saved views - add [[views]] to the config (c)".to_string(), Instant::now()));
+						}
+						KeyCode::Char('f') if !showing_daily && !send_input_mode => {
+							// Next saved view, then back to everything
+							active_view = match active_view {
+								None => Some(0),
+								Some(i) if i + 1 < cfg.views.len() => Some(i + 1),
+								Some(_) => None,
+							};
+							let view = active_view.and_then(|i| cfg.views.get(i));
+							agent_filter = view.map(|v| v.filter.clone()).unwrap_or_default();
+							let hidden = hidden_rows(&sessions, &collapsed_groups, &agent_filter);
+							if hidden.get(selected) == Some(&true) {
+								selected = visible_selection(&sessions, selected, &hidden);
+								list_state.select(sessions.get(selected).map(|_| selected));
+							}
+							task_filter = view.map(|v| parse_filter(&v.filter));
+							shown = shown_tasks(&tasks, &sessions, task_filter.as_ref());
+							tasks_state.select(visible_task(&shown, tasks_state.selected().unwrap_or(0)));
+							status_message = Some((
+								match view {
+									Some(v) => format!("View: {} ({})", v.name, v.filter),
+									None => "All agents and tasks".to_string(),
+								},
+								Instant::now(),
+							));
+						}
+						KeyCode::Char('R') if !showing_tasks && !showing_daily => {
+							// Rename the selected session
+							if let Some(sel) = sessions.get(selected) {
+								rename_mode = true;
+								rename_buf = sel.name.clone();
+							}
+						}
+						KeyCode::Char('L') if !showing_tasks && !showing_daily => {
+							// Land a finished worktree session: rebase, checks, push, PR
+							if let Some(sel) = sessions.get(selected) {
+								match land_plan(cfg, sel) {
+									Ok((dir, base, checks)) => {
+										land_view = Some((sel.name.clone(), land::steps(&base, &checks)));
+										let (rx, cancel) = land::spawn(dir, base, checks);
+										land_rx = Some(rx);
+										land_cancel = Some(cancel);
+									}
+									Err(e) => status_message = Some((e.to_string(), Instant::now())),
+								}
+							}
+						}
+						KeyCode::Char('H') if !showing_tasks && !showing_daily => {
+							// Hand the selected agent's work to a new agent
+							if let Some(sel) = sessions.get(selected) {
+								handoff_mode = true;
+								handoff_agent_buf = sel.agent.clone();
+								handoff_note_buf.clear();
+								handoff_field = 0;
+							}
+						}
+						KeyCode::Char('v') if !showing_daily && !send_input_mode => {
+							// View the task's process log (selected task, or selected agent's task)
+							let task = if showing_tasks {
+								tasks_state
+									.selected()
+									.and_then(|idx| tasks.get(idx))
+									.map(|t| build_task_info(t.path.clone()))
+							} else {
+								sessions.get(selected).and_then(|s| s.task.clone())
+							};
+							match task {
+								Some(task) => process_log_view = Some(task),
+								None => {
+									status_message = Some((
+										"No task linked to this agent".to_string(),
+										Instant::now(),
+									));
+								}
+							}
+						}
+						KeyCode::Char('p')
+							if !showing_tasks && !showing_daily && !send_input_mode =>
+						{
+							// Replay the selected agent's log, a page at a time
 							if let Some(sel) = sessions.get(selected) {
-								attach_to(&mut terminal, sel)?;
+								replay_in_terminal(&mut terminal, sel)?;
 							}
 						}
+						KeyCode::Char('z') if showing_tasks && !send_input_mode && tasks_state.selected().is_some() => {
+							snooze_mode = true;
+							snooze_buf = "tomorrow".to_string();
+						}
 						KeyCode::Char('x')
 							if showing_tasks && !send_input_mode =>
 						{
@@ -1791,12 +6756,12 @@ Install these commands to ~/.claude/commands/?
 						KeyCode::Char('o')
 							if showing_tasks && !send_input_mode =>
 						{
-							// Open task in Cursor
+							// Open task in Cursor (Obsidian for tasks in the vault)
 							if let Some(idx) = tasks_state.selected() {
 								if let Some(task) = tasks.get(idx) {
-									let _ = Command::new("cursor").arg(&task.path).status();
+									let app = obsidian::open(cfg, &task.path);
 									status_message = Some((
-										format!("Opened {} in Cursor", task.title),
+										format!("Opened {} in {app}", task.title),
 										Instant::now(),
 									));
 								}
@@ -1805,12 +6770,12 @@ Install these commands to ~/.claude/commands/?
 						KeyCode::Char('o')
 							if showing_daily && !send_input_mode =>
 						{
-							// Open daily log in Cursor
+							// Open daily log in Cursor (Obsidian for daily notes in the vault)
 							if let Some(idx) = daily_state.selected() {
 								if let Some(daily) = daily_logs.get(idx) {
-									let _ = Command::new("cursor").arg(&daily.path).status();
+									let app = obsidian::open(cfg, &daily.path);
 									status_message = Some((
-										format!("Opened {} in Cursor", daily.date),
+										format!("Opened {} in {app}", daily.date),
 										Instant::now(),
 									));
 								}
@@ -1824,6 +6789,7 @@ Install these commands to ~/.claude/commands/?
 							new_agent_buf.clear();
 							new_agent_notify = String::from("no one");
 							new_agent_due = String::from("tomorrow");
+							new_agent_model.clear();
 							new_agent_field = 0;
 						}
 						KeyCode::Char('Y') if showing_tasks => {
@@ -1856,6 +6822,73 @@ Install these commands to ~/.claude/commands/?
 								}
 							}
 						}
+						KeyCode::Char('V') if showing_tasks => {
+							// A/B: two agents on this task, each in its own worktree
+							if let Some(task) = tasks_state.selected().and_then(|idx| tasks.get(idx)) {
+								let repo = std::env::current_dir()
+									.map(|d| d.to_string_lossy().into_owned())
+									.unwrap_or_else(|_| ".".to_string());
+								match launch_variants(
+									cfg,
+									&task.title,
+									&cfg.general.default_agent,
+									&repo,
+									None,
+									Some(task.path.to_string_lossy().into_owned()),
+									2,
+									false,
+									None,
+									None,
+									// No terminal prompt in the TUI
+									(cfg.worktrees.on_conflict == "ask").then_some("suffix"),
+								) {
+									Ok(names) => {
+										status_message = Some((
+											format!("Started variants {} - press C to compare", names.join(", ")),
+											Instant::now(),
+										));
+										showing_tasks = false;
+										sessions = collect_sessions(cfg)?;
+										selected = sessions.len().saturating_sub(1);
+										list_state.select(sessions.get(selected).map(|_| selected));
+									}
+									Err(e) => {
+										status_message = Some((
+											format!("Failed to start variants: {e}"),
+											Instant::now(),
+										));
+									}
+								}
+							}
+						}
+						KeyCode::Char('A') if showing_tasks && !send_input_mode => {
+							agenda_view = true;
+							agenda_offset = 0;
+							agenda_idx = sync_agenda_selection(&tasks, 0, 0, &mut tasks_state);
+						}
+						KeyCode::Char('F') if showing_tasks => {
+							// Fan out: a planner splits the task, then workers start per subtask
+							if let Some(task) = tasks_state.selected().and_then(|idx| tasks.get(idx)) {
+								match start_fanout(cfg, task, None, None) {
+									Ok((name, group)) => {
+										status_message = Some((
+											format!("Started planner {name} - its subtasks start in group {group} when it's done"),
+											Instant::now(),
+										));
+										showing_tasks = false;
+										sessions = collect_sessions(cfg)?;
+										selected = sessions
+											.iter()
+											.position(|s| s.name == name)
+											.unwrap_or(sessions.len().saturating_sub(1));
+										list_state.select(sessions.get(selected).map(|_| selected));
+									}
+									Err(e) => {
+										status_message = Some((format!("Failed to start planner: {e}"), Instant::now()));
+									}
+								}
+							}
+						}
 						// Force new session (even if one exists for this task)
 						KeyCode::Char('N') if showing_tasks => {
 							if let Some(idx) = tasks_state.selected() {
@@ -1877,7 +6910,7 @@ Install these commands to ~/.claude/commands/?
 												.select(sessions.get(selected).map(|_| selected));
 										}
 										Err(e) => {
-											eprintln!("Failed to start session: {e}");
+											console::error("start session", e);
 										}
 									}
 								}
@@ -1933,16 +6966,26 @@ Install these commands to ~/.claude/commands/?
 													);
 												}
 												Err(e) => {
-													eprintln!("Failed to start session: {e}");
+													console::error("start session", e);
 												}
 											}
 										}
 									}
 								}
-							} else if sessions.get(selected).is_some() {
+							} else if let Some(sel) = sessions.get(selected) {
 								// Enter = send input (most common action when monitoring)
 								send_input_mode = true;
 								send_input_buf.clear();
+								replies = if sel.status == AgentStatus::NeedsInput {
+									let detection = detection_for_agent(&sel.agent, &cfg.detection);
+									quick_replies(&tail_lines(&sel.log_path, 80).unwrap_or_default(), &detection)
+								} else {
+									Vec::new()
+								};
+								reply_idx = None;
+								input_recall = input_history::recall(&sel.session_name);
+								recall_idx = None;
+								recall_draft.clear();
 							}
 						}
 						KeyCode::Char(c)
@@ -1958,9 +7001,7 @@ Install these commands to ~/.claude/commands/?
 									list_state.select(Some(selected));
 									// Update preview cache for selected session
 									if let Some(sel) = sessions.get(selected) {
-										if let Ok(lines) = capture_tail_ansi(&sel.session_name, 200) {
-											cached_preview = Some((sel.session_name.clone(), lines));
-										}
+										refresh_preview(sel, &mut preview_cache);
 									}
 								}
 							}
@@ -2017,34 +7058,236 @@ Install these commands to ~/.claude/commands/?
 			}
 		}
 
-		if last_refresh.elapsed() >= Duration::from_millis(cfg.general.poll_interval_ms.min(5_000))
-		{
-			if let Ok(updated) = collect_sessions(cfg) {
+		let poll_ms = |ms: u64| Duration::from_millis(if focused { ms } else { ms.max(cfg.general.blur_poll_ms) });
+		if last_refresh.elapsed() >= poll_ms(cfg.general.poll_interval_ms.min(5_000)) {
+			let _refresh = tracing::debug_span!("refresh", sessions = sessions.len()).entered();
+			if let Ok(mut updated) = collect_sessions(cfg) {
+				// A checklist scrolls out of the tail as the agent works; keep the last one seen
+				for session in updated.iter_mut().filter(|s| s.progress.is_none()) {
+					session.progress = sessions
+						.iter()
+						.find(|old| old.session_name == session.session_name)
+						.and_then(|old| old.progress);
+				}
 				// Check for state changes and fire notifications
-				if cfg.notifications.enabled {
-					for session in &updated {
-						let old_status = prev_status.get(&session.session_name);
-						let new_status = session.status;
+				for session in &updated {
+					let old_status = prev_status.get(&session.session_name);
+					let new_status = session.status;
+					if (new_status == AgentStatus::NeedsInput) != (old_status == Some(&AgentStatus::NeedsInput)) {
+						record_blocked(&session.session_name, new_status == AgentStatus::NeedsInput);
+					}
+
+					// Notify on transition to NeedsInput
+					if cfg.notifications.enabled
+						&& new_status == AgentStatus::NeedsInput
+						&& old_status != Some(&AgentStatus::NeedsInput)
+					{
+						notify::notify_needs_input(
+							&session.name,
+							cfg.notifications.sound("needs_input", session_priority(session)),
+						);
+					}
+					if new_status == AgentStatus::NeedsInput
+						&& old_status != Some(&AgentStatus::NeedsInput)
+					{
+						notify::push(
+							&cfg.push,
+							notify::PushEvent::NeedsInput,
+							&session.name,
+							&session.session_name,
+							&format!("{} needs input", session.name),
+						);
+						if cfg.notifications.terminal_bell && !notify::is_muted() {
+							// BEL reaches the host terminal (or swarm's own tmux window)
+							use std::io::Write;
+							let mut out = stdout();
+							let _ = out.write_all(b"\x07");
+							let _ = out.flush();
+						}
+						if cfg.notifications.tmux_alerts && !notify::is_muted() {
+							let _ = tmux::ring_bell();
+						}
+					}
 
-						// Notify on transition to NeedsInput
-						if new_status == AgentStatus::NeedsInput
-							&& old_status != Some(&AgentStatus::NeedsInput)
+					// On transition to Done: notify, and advance pipelines to their next stage
+					if new_status == AgentStatus::Done && old_status != Some(&AgentStatus::Done) {
+						if cfg.notifications.enabled {
+							notify::notify_done(&session.name, cfg.notifications.sound("done", session_priority(session)));
+						}
+						notify::push(
+							&cfg.push,
+							notify::PushEvent::Done,
+							&session.name,
+							&session.session_name,
+							&format!("{} completed", session.name),
+						);
+						record_completed(&mut completed_this_run, session);
+						run_hook(cfg, "done", session);
+						if let Some(stage) = &session.stage {
+							let msg = match advance_pipeline(cfg, session) {
+								Ok(Some(next)) => format!(
+									"Stage {}/{} done → started {}",
+									stage.index + 1,
+									stage.total,
+									next
+								),
+								Ok(None) => format!("Pipeline complete: {}", session.name),
+								Err(e) => format!("Pipeline advance failed: {}", e),
+							};
+							status_message = Some((msg, Instant::now()));
+						} else if let (Some(group), Some(subtasks)) =
+							(&session.group, fanout_subtasks(cfg, session, &updated))
 						{
-							notify::notify_needs_input(
+							// Only once, even if the planner is respawned and finishes again
+							let _ = session_fanout_path(&session.session_name).map(fs::remove_file);
+							let dir = session_work_dir(session);
+							let queued = subtasks.len();
+							fanout_queue.extend(subtasks.into_iter().map(|t| (t, group.clone(), dir.clone())));
+							status_message = Some((
+								if queued == 0 {
+									format!("Fan-out {group}: the planner wrote no subtasks")
+								} else {
+									format!("Fan-out {group}: {queued} subtask(s) → starting workers")
+								},
+								Instant::now(),
+							));
+						} else if let Some(group) = &session.group {
+							// Composite completion: queued subtasks count as not done yet
+							let members = group_members(&updated, group);
+							let done = members.iter().filter(|m| m.status == AgentStatus::Done).count();
+							let total = members.len() + fanout_queue.iter().filter(|(_, g, _)| g == group).count();
+							let msg = if done == total {
+								format!(
+									"Group {group} complete: all {total} agents done - report: {}",
+									group_report_path(cfg, group).display()
+								)
+							} else {
+								format!("Group {group}: {done}/{total} done")
+							};
+							if done == total {
+								// One report for the whole effort; gh and git can be slow, so off the UI thread
+								let members: Vec<AgentSession> = members.into_iter().cloned().collect();
+								let (cfg, group, session_name) = (cfg.clone(), group.clone(), session.session_name.clone());
+								std::thread::spawn(move || {
+									let report = write_group_report(&cfg, &group, &members);
+									let text = match &report {
+										Ok(path) => format!("Group {group} complete - report: {}", path.display()),
+										Err(e) => {
+											console::error("group report", e);
+											format!("Group {group} complete (report failed: {e})")
+										}
+									};
+									if let Ok(path) = &report {
+										let _ = append_daily_summary(
+											&cfg,
+											&[format!("Group {group} complete"), format!("- Report: {}", path.display())],
+										);
+									}
+									if cfg.notifications.enabled {
+										notify::notify_group_done(&group, &cfg.notifications.sound_done);
+									}
+									notify::push(&cfg.push, notify::PushEvent::Done, &group, &session_name, &text);
+								});
+							}
+							status_message = Some((msg, Instant::now()));
+						}
+					}
+
+					if new_status == AgentStatus::Error && old_status != Some(&AgentStatus::Error) {
+						if cfg.notifications.enabled {
+							notify::notify_error(
 								&session.name,
-								&cfg.notifications.sound_needs_input,
+								"hit an error",
+								cfg.notifications.sound("error", session_priority(session)),
 							);
 						}
+						notify::push(
+							&cfg.push,
+							notify::PushEvent::Error,
+							&session.name,
+							&session.session_name,
+							&format!("{} hit an error", session.name),
+						);
+					}
 
-						// Notify on transition to Done
-						if new_status == AgentStatus::Done
-							&& old_status != Some(&AgentStatus::Done)
-						{
-							notify::notify_done(&session.name, &cfg.notifications.sound_done);
+					prev_status.insert(session.session_name.clone(), new_status);
+				}
+
+				// Start queued fan-out subtasks while there's room
+				if !fanout_queue.is_empty() {
+					let limit = cfg.general.max_agents;
+					let mut running = updated.iter().filter(|s| s.status != AgentStatus::Done).count();
+					while !fanout_queue.is_empty() && (limit == 0 || running < limit) {
+						let (task, group, dir) = fanout_queue.remove(0);
+						if let Err(e) = start_subtask(cfg, &task, &group, dir.as_deref()) {
+							status_message = Some((format!("Fan-out {group}: failed to start {}: {e}", task.title), Instant::now()));
 						}
+						running += 1;
+					}
+				}
+
+				// Soft deadlines: notify once when a session runs past its budget
+				for session in &updated {
+					let over = session.status != AgentStatus::Done
+						&& session.deadline.is_some_and(|(elapsed, budget)| elapsed >= budget);
+					if !over {
+						// A raised deadline can be passed (and notified) again
+						deadlines_hit.remove(&session.session_name);
+						continue;
+					}
+					if !deadlines_hit.insert(session.session_name.clone()) {
+						continue;
+					}
+					let Some((elapsed, budget)) = session.deadline else { continue };
+					let msg = format!("{} has run {} (should finish within {})", session.name, format_duration_hm(elapsed), format_duration_hm(budget));
+					if cfg.notifications.enabled {
+						notify::notify_over_time(&session.name, &msg, cfg.notifications.sound("error", session_priority(session)));
+					}
+					status_message = Some((format!("⏱ {msg}"), Instant::now()));
+				}
 
-						prev_status.insert(session.session_name.clone(), new_status);
+				// Runtime and budget limits
+				for session in &updated {
+					if limits_hit.contains(&session.session_name) || session.status == AgentStatus::Done {
+						continue;
+					}
+					let cost = if session.agent == "claude" && effective_limits(cfg, session).max_cost.is_some() {
+						session_work_dir(session).map(|dir| {
+							let state = claude_states.entry(session.session_name.clone()).or_default();
+							claude_session::refresh(state, &dir);
+							state.cost_estimate()
+						})
+					} else {
+						None
+					};
+					let Some((reason, action)) = limit_breach(cfg, session, cost) else {
+						continue;
+					};
+					limits_hit.insert(session.session_name.clone());
+					let outcome = match action.as_str() {
+						"kill" if session.pinned => Ok("pinned, not killed"),
+						"kill" => {
+							run_hook(cfg, "kill", session);
+							tmux::kill_session(&session.session_name).map(|_| "killed")
+						}
+						"pause" => tmux::send_special_key(&session.session_name, "Escape").map(|_| "paused"),
+						_ => Ok("still running"),
+					};
+					let msg = match outcome {
+						Ok(outcome) => format!("{} {reason} - {outcome}", session.name),
+						Err(e) => format!("{} {reason} - {action} failed: {e}", session.name),
+					};
+					if cfg.notifications.enabled {
+						notify::notify_error(&session.name, &msg, cfg.notifications.sound("error", session_priority(session)));
 					}
+					notify::push(
+						&cfg.push,
+						notify::PushEvent::Error,
+						&session.name,
+						&session.session_name,
+						&msg,
+					);
+					status_message = Some((msg, Instant::now()));
 				}
 
 				if updated.is_empty() {
@@ -2054,15 +7297,83 @@ Install these commands to ~/.claude/commands/?
 					selected = updated.len() - 1;
 					list_state.select(Some(selected));
 				}
+				let hidden = hidden_rows(&updated, &collapsed_groups, &agent_filter);
+				if hidden.get(selected) == Some(&true) {
+					selected = visible_selection(&updated, selected, &hidden);
+					list_state.select(Some(selected));
+				}
 				sessions = updated;
-				// Update preview cache for selected session
-				if let Some(sel) = sessions.get(selected) {
-					if let Ok(lines) = capture_tail_ansi(&sel.session_name, 200) {
-						cached_preview = Some((sel.session_name.clone(), lines));
+				let _ = write_fleet_snapshot(&sessions);
+				if let Some(snapshot) = attach_return.take() {
+					tasks = load_tasks(cfg);
+					status_message = Some((snapshot.summary(&sessions, &tasks), Instant::now()));
+				}
+				preview_cache.retain(|name, _| sessions.iter().any(|s| &s.session_name == name));
+				claude_states.retain(|name, _| sessions.iter().any(|s| &s.session_name == name));
+				needs_redraw = true;
+			}
+			last_refresh = Instant::now();
+		}
+
+		if last_preview.elapsed() >= poll_ms(cfg.general.preview_poll_ms) {
+			// Update preview cache for selected session
+			if let Some(sel) = sessions.get(selected) {
+				if refresh_preview(sel, &mut preview_cache) {
+					needs_redraw = true;
+				}
+				if sel.agent == "claude" {
+					if let Some(dir) = session_work_dir(sel) {
+						let state = claude_states.entry(sel.session_name.clone()).or_default();
+						if claude_session::refresh(state, &dir) {
+							needs_redraw = true;
+						}
+					}
+				}
+			}
+			if let Some((left, right)) = &compare_view {
+				for s in sessions.iter().filter(|s| &s.session_name == left || &s.session_name == right) {
+					if refresh_preview(s, &mut preview_cache) {
+						needs_redraw = true;
 					}
 				}
+				if sessions.iter().filter(|s| &s.session_name == left || &s.session_name == right).count() < 2 {
+					// One of the pair went away
+					compare_view = None;
+				}
+			}
+			last_preview = Instant::now();
+		}
+
+		if last_procs.is_none_or(|t| t.elapsed() >= PROCS_POLL) {
+			proc_monitor.refresh();
+			session_procs = sessions
+				.iter()
+				.filter_map(|s| Some((s.session_name.clone(), proc_monitor.tree(tmux::pane_pid(&s.session_name)?))))
+				.collect();
+			last_procs = Some(Instant::now());
+			needs_redraw = true;
+		}
+
+		if last_tasks_reload.elapsed() >= poll_ms(cfg.general.tasks_poll_ms) {
+			let woken = wake_snoozed_tasks(cfg);
+			if let Some(first) = woken.first() {
+				let message = match woken.len() {
+					1 => format!("{} is back from snooze", first.title),
+					n => format!("{} and {} more are back from snooze", first.title, n - 1),
+				};
+				if cfg.notifications.enabled {
+					notify::notify_snooze_over(&message, &cfg.notifications.sound_needs_input);
+				}
+				status_message = Some((format!("⏰ {message}"), Instant::now()));
 			}
+			snoozed_count = snoozed_tasks(cfg).len();
 			tasks = load_tasks(cfg);
+			if cfg.notifications.enabled {
+				maybe_send_daily_digest(cfg, &tasks, &sessions);
+				if let Some(msg) = send_due_reminders(cfg, &tasks, &sessions) {
+					status_message = Some((msg, Instant::now()));
+				}
+			}
 			if tasks.is_empty() {
 				tasks_state.select(None);
 			} else if tasks_state.selected().is_none() {
@@ -2074,28 +7385,343 @@ Install these commands to ~/.claude/commands/?
 					tasks_state.select(Some(tasks.len() - 1));
 				}
 			}
-			last_refresh = Instant::now();
+			if let Some(sel) = tasks_state.selected() {
+				shown = shown_tasks(&tasks, &sessions, task_filter.as_ref());
+				tasks_state.select(visible_task(&shown, sel));
+			}
+			if agenda_view {
+				agenda_idx = sync_agenda_selection(&tasks, agenda_offset, agenda_idx, &mut tasks_state);
+			}
+			needs_redraw = true;
+			last_tasks_reload = Instant::now();
+		}
+
+		if task_sync_enabled
+			&& task_sync_rx.is_none()
+			&& !net::known_offline()
+			&& last_task_sync.is_none_or(|t| t.elapsed() >= Duration::from_secs(cfg.task_sync.interval_secs))
+		{
+			let (tx, rx) = std::sync::mpsc::channel();
+			let dir = PathBuf::from(&cfg.general.tasks_dir);
+			let remote = cfg.task_sync.remote.clone();
+			std::thread::spawn(move || {
+				let _ = tx.send(task_sync::sync(&dir, &remote));
+			});
+			task_sync_rx = Some(rx);
+			last_task_sync = Some(Instant::now());
+		}
+		if !cfg.team.publish_to.is_empty()
+			&& team_publish_rx.is_none()
+			&& !net::known_offline()
+			&& last_team_publish.is_none_or(|t| t.elapsed() >= Duration::from_secs(cfg.team.interval_secs))
+		{
+			let (tx, rx) = std::sync::mpsc::channel();
+			let status = team_status(cfg, &sessions);
+			let (target, token) = (cfg.team.publish_to.clone(), cfg.team.token.clone());
+			std::thread::spawn(move || {
+				let _ = tx.send(team::publish(&target, &token, &status));
+			});
+			team_publish_rx = Some(rx);
+			last_team_publish = Some(Instant::now());
+		}
+		if let Some(rx) = &land_rx {
+			loop {
+				match rx.try_recv() {
+					Ok(update) => {
+						if let Some(step) = land_view.as_mut().and_then(|(_, steps)| steps.get_mut(update.step)) {
+							step.state = update.state;
+							step.detail = update.detail;
+						}
+						needs_redraw = true;
+					}
+					Err(std::sync::mpsc::TryRecvError::Empty) => break,
+					Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+						land_rx = None;
+						land_cancel = None;
+						needs_redraw = true;
+						break;
+					}
+				}
+			}
+		}
+		if cfg.general.conflict_check_ms > 0
+			&& conflicts_rx.is_none()
+			&& last_conflict_check.is_none_or(|t| t.elapsed() >= poll_ms(cfg.general.conflict_check_ms))
+		{
+			let (tx, rx) = std::sync::mpsc::channel();
+			let active: Vec<(String, String, PathBuf)> = sessions
+				.iter()
+				.filter(|s| s.status != AgentStatus::Done)
+				.filter_map(|s| Some((s.session_name.clone(), s.name.clone(), session_work_dir(s)?)))
+				.collect();
+			std::thread::spawn(move || {
+				let _ = tx.send(conflicts::find(&active));
+			});
+			conflicts_rx = Some(rx);
+			last_conflict_check = Some(Instant::now());
+		}
+		if let Some(found) = conflicts_rx.as_ref().and_then(|rx| rx.try_recv().ok()) {
+			conflicts_rx = None;
+			// Announce only conflicts that weren't there on the last check
+			let new = found.iter().find_map(|(session, list)| {
+				let name = sessions.iter().find(|s| &s.session_name == session)?.name.clone();
+				let conflict = list.iter().find(|c| !conflicts.get(session).is_some_and(|old| old.contains(c)))?;
+				Some(format!("⚠ {name} {}", conflicts::describe(conflict)))
+			});
+			if let Some(msg) = new {
+				status_message = Some((msg, Instant::now()));
+			}
+			if found != conflicts {
+				needs_redraw = true;
+			}
+			conflicts = found;
+		}
+		if let Some(result) = team_publish_rx.as_ref().and_then(|rx| rx.try_recv().ok()) {
+			team_publish_rx = None;
+			if let Err(e) = result {
+				console::error("team publish", &e);
+				status_message = Some((format!("Team publish failed: {e}"), Instant::now()));
+				needs_redraw = true;
+			}
+		}
+		if let Some(result) = task_sync_rx.as_ref().and_then(|rx| rx.try_recv().ok()) {
+			task_sync_rx = None;
+			match result {
+				Ok(_) => {
+					// Pick up tasks pulled from the other machine on the next reload
+					last_tasks_reload = Instant::now() - Duration::from_millis(cfg.general.tasks_poll_ms);
+				}
+				Err(e) => {
+					console::error("task sync", &e);
+					status_message = Some((format!("Task sync failed: {e}"), Instant::now()));
+					needs_redraw = true;
+				}
+			}
+		}
+	}
+
+	teardown_terminal()?;
+	if dashboard_session.is_some() {
+		let _ = dashboard_session_path().map(fs::remove_file);
+	}
+
+	let final_sessions = collect_sessions(cfg).unwrap_or(sessions);
+	let summary = fleet_summary(
+		&final_sessions,
+		&completed_this_run,
+		attached_total,
+		tui_started.elapsed(),
+	);
+	println!("{}", summary.join("\n"));
+	if let Err(e) = append_daily_summary(cfg, &summary) {
+		eprintln!("Failed to write daily log: {e}");
+	}
+	if task_sync_enabled {
+		// Push edits made since the last interval so the other machine sees them
+		match task_sync::sync(Path::new(&cfg.general.tasks_dir), &cfg.task_sync.remote) {
+			Ok(result) => println!("Tasks synced: {result}"),
+			Err(e) => eprintln!("Task sync failed: {e}"),
+		}
+	}
+	Ok(())
+}
+
+/// Index of the next session after `from` (wrapping) that needs input or errored
+fn next_blocked(sessions: &[AgentSession], from: usize) -> Option<usize> {
+	(1..=sessions.len())
+		.map(|offset| (from + offset) % sessions.len())
+		.find(|&i| matches!(sessions[i].status, AgentStatus::NeedsInput | AgentStatus::Error))
+}
+
+/// Remember a finished session's task (or name) for the quit summary
+fn record_completed(completed: &mut Vec<String>, session: &AgentSession) {
+	let label = session
+		.task
+		.as_ref()
+		.map(|t| t.title.clone())
+		.unwrap_or_else(|| session.name.clone());
+	if !completed.contains(&label) {
+		completed.push(label);
+	}
+}
+
+/// Fleet state captured when attaching, to report what changed while the TUI was hidden
+struct AttachSnapshot {
+	session_name: String,
+	started: Instant,
+	statuses: std::collections::HashMap<String, AgentStatus>,
+	open_tasks: Vec<PathBuf>,
+}
+
+impl AttachSnapshot {
+	fn take(sel: &AgentSession, sessions: &[AgentSession], tasks: &[TaskEntry]) -> Self {
+		Self {
+			session_name: sel.session_name.clone(),
+			started: Instant::now(),
+			statuses: sessions.iter().map(|s| (s.session_name.clone(), s.status)).collect(),
+			open_tasks: tasks.iter().map(|t| t.path.clone()).collect(),
+		}
+	}
+
+	/// "While attached (12m): api needs input · docs done · 1 task done"
+	fn summary(&self, sessions: &[AgentSession], tasks: &[TaskEntry]) -> String {
+		let mut changes: Vec<String> = sessions
+			.iter()
+			.filter(|s| s.session_name != self.session_name)
+			.filter_map(|s| match self.statuses.get(&s.session_name) {
+				Some(old) if *old == s.status => None,
+				Some(_) => Some(format!("{} {}", s.name, s.status.label())),
+				None => Some(format!("{} started", s.name)),
+			})
+			.collect();
+		let ended = self
+			.statuses
+			.keys()
+			.filter(|name| !sessions.iter().any(|s| &s.session_name == *name))
+			.count();
+		if ended > 0 {
+			changes.push(format!("{} agent{} closed", ended, if ended == 1 { "" } else { "s" }));
+		}
+		let done = self
+			.open_tasks
+			.iter()
+			.filter(|path| !tasks.iter().any(|t| &t.path == *path))
+			.count();
+		if done > 0 {
+			changes.push(format!("{} task{} done", done, if done == 1 { "" } else { "s" }));
+		}
+		let minutes = self.started.elapsed().as_secs().div_ceil(60);
+		if changes.is_empty() {
+			format!("Nothing changed while attached ({minutes}m)")
+		} else {
+			format!("While attached ({minutes}m): {}", changes.join(" · "))
 		}
 	}
+}
 
-	teardown_terminal()?;
+/// What's still in flight when the dashboard closes
+fn fleet_summary(
+	sessions: &[AgentSession],
+	completed: &[String],
+	attached: Duration,
+	uptime: Duration,
+) -> Vec<String> {
+	let names = |status: AgentStatus| -> Vec<String> {
+		sessions
+			.iter()
+			.filter(|s| s.status == status)
+			.map(|s| s.name.clone())
+			.collect()
+	};
+	let list = |items: &[String]| {
+		if items.is_empty() {
+			String::new()
+		} else {
+			format!(": {}", items.join(", "))
+		}
+	};
+	let blocked = names(AgentStatus::NeedsInput);
+	let running: Vec<String> = sessions
+		.iter()
+		.filter(|s| !matches!(s.status, AgentStatus::NeedsInput | AgentStatus::Done))
+		.map(|s| s.name.clone())
+		.collect();
+	let minutes = |d: Duration| format!("{}m", d.as_secs().div_ceil(60));
+	vec![
+		format!(
+			"swarm summary ({} open, {} attached)",
+			minutes(uptime),
+			minutes(attached)
+		),
+		format!("- Still running: {}{}", running.len(), list(&running)),
+		format!("- Blocked on input: {}{}", blocked.len(), list(&blocked)),
+		format!("- Completed: {}{}", completed.len(), list(completed)),
+	]
+}
+
+/// Append the quit summary to today's daily log (or the vault's daily note)
+fn append_daily_summary(cfg: &Config, summary: &[String]) -> Result<()> {
+	let now = Local::now();
+	let file = obsidian::daily_log(cfg, now.date_naive());
+	if let Some(dir) = file.parent() {
+		fs::create_dir_all(dir)?;
+	}
+	let mut f = fs::OpenOptions::new().create(true).append(true).open(&file)?;
+	use std::io::Write;
+	writeln!(f, "\n### {} {}", now.format("%H:%M"), summary[0])?;
+	for line in &summary[1..] {
+		writeln!(f, "{}", line)?;
+	}
 	Ok(())
 }
 
+/// Full-depth capture of the selected session's pane, kept until the session has new output
+struct PreviewCache {
+	last_output: Option<SystemTime>,
+	hash: u64,
+	lines: Vec<String>,
+}
+
+/// Lines captured for the selected session's preview; other sessions use the
+/// shallow log tail collected with their status
+const PREVIEW_DEPTH: usize = 200;
+
+/// How often the process table behind the Details pane's CPU/memory is re-read
+const PROCS_POLL: Duration = Duration::from_secs(5);
+
+/// Re-capture the preview only if the session produced output since the last capture.
+/// Returns true when the cached content actually changed.
+fn refresh_preview(
+	sel: &AgentSession,
+	cache: &mut std::collections::HashMap<String, PreviewCache>,
+) -> bool {
+	if let Some(entry) = cache.get(&sel.session_name) {
+		if entry.last_output.is_some() && entry.last_output == sel.last_output {
+			return false;
+		}
+	}
+	let Ok(lines) = capture_tail_ansi(&sel.session_name, PREVIEW_DEPTH) else {
+		return false;
+	};
+	let hash = {
+		use std::hash::{Hash, Hasher};
+		let mut hasher = std::collections::hash_map::DefaultHasher::new();
+		lines.hash(&mut hasher);
+		hasher.finish()
+	};
+	let changed = cache
+		.get(&sel.session_name)
+		.map(|entry| entry.hash != hash)
+		.unwrap_or(true);
+	let entry = cache
+		.entry(sel.session_name.clone())
+		.or_insert_with(|| PreviewCache {
+			last_output: None,
+			hash: 0,
+			lines: Vec::new(),
+		});
+	entry.last_output = sel.last_output;
+	if changed {
+		entry.hash = hash;
+		entry.lines = lines;
+	}
+	changed
+}
+
 fn agents_footer_text(width: u16) -> String {
 	if width < 100 {
-		"A: enter | S-Tab | 1-9 | a | n | d | t | s | h | q".to_string()
+		"A: enter | S-Tab | 1-9 | a | A ro | p | n | d | t | s | h | q".to_string()
 	} else {
-		"Agents: enter | S-Tab mode | 1-9 | a attach | n new | d done | t tasks | s style | h | q".to_string()
+		"Agents: enter | Tab next blocked | S-Tab mode | 1-9 | a attach | A read-only | p replay | y copy | n new | d done | t tasks | s style | h | q".to_string()
 	}
 }
 
 fn tasks_footer_text(width: u16) -> String {
 	if width < 100 {
-		"T: enter | N new | n new task | Y⚠️ yolo | Esc back | h | q"
+		"T: enter | N new | F fan out | A agenda | n new task | Y⚠️ yolo | Esc back | h | q"
 			.to_string()
 	} else {
-		"Tasks: enter/N start | n new task | Y⚠️ yolo | o open | x del | Esc back | h help | q"
+		"Tasks: enter/N start | n new task | A agenda | z snooze | Y⚠️ yolo | V a/b | v log | o open | y copy path | x del | Esc back | h help | q"
 			.to_string()
 	}
 }
@@ -2189,19 +7815,14 @@ fn clean_preview(lines: &[String]) -> Vec<String> {
 
 fn mini_log_preview(lines: &[String]) -> Option<String> {
 	let cleaned = clean_preview(lines);
-	let snippet = cleaned
-		.iter()
-		.rev()
-		.find(|l| !l.trim().is_empty())
-		.cloned()?;
-	let max_chars = 80;
-	let count = snippet.chars().count();
-	if count > max_chars {
-		let truncated: String = snippet.chars().take(max_chars).collect();
-		Some(format!("{truncated}…"))
-	} else {
-		Some(snippet)
-	}
+	let snippet = cleaned.iter().map(|l| text::sanitize(l)).rev().find(|l| !l.trim().is_empty())?;
+	Some(text::truncate(&snippet, 80))
+}
+
+/// Small bar like "▰▰▱▱▱" for done/total
+fn progress_bar(done: usize, total: usize, width: usize) -> String {
+	let filled = (done * width).checked_div(total).unwrap_or(0).min(width);
+	format!("{}{}", "▰".repeat(filled), "▱".repeat(width - filled))
 }
 
 fn status_indicator(status: AgentStatus, style: &str) -> (&'static str, Style) {
@@ -2211,6 +7832,7 @@ fn status_indicator(status: AgentStatus, style: &str) -> (&'static str, Style) {
 			AgentStatus::Running => ("🟢", Style::default()),
 			AgentStatus::Idle => ("🟡", Style::default()),
 			AgentStatus::Done => ("✓ ", Style::default().add_modifier(Modifier::DIM)),
+			AgentStatus::Error => ("❌", Style::default()),
 			AgentStatus::Unknown => ("⚪", Style::default()),
 		},
 		"unicode" => match status {
@@ -2226,6 +7848,10 @@ fn status_indicator(status: AgentStatus, style: &str) -> (&'static str, Style) {
 			),
 			AgentStatus::Idle => ("○", Style::default().fg(Color::Yellow)),
 			AgentStatus::Done => ("✓", Style::default().fg(Color::Cyan)),
+			AgentStatus::Error => (
+				"✗",
+				Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+			),
 			AgentStatus::Unknown => ("·", Style::default().fg(Color::DarkGray)),
 		},
 		"text" => match status {
@@ -2244,6 +7870,13 @@ fn status_indicator(status: AgentStatus, style: &str) -> (&'static str, Style) {
 			),
 			AgentStatus::Idle => ("[idle]", Style::default().fg(Color::Yellow)),
 			AgentStatus::Done => ("[done]", Style::default().fg(Color::Cyan)),
+			AgentStatus::Error => (
+				"[ERR] ",
+				Style::default()
+					.fg(Color::White)
+					.bg(Color::Magenta)
+					.add_modifier(Modifier::BOLD),
+			),
 			AgentStatus::Unknown => ("[ ? ] ", Style::default().fg(Color::DarkGray)),
 		},
 		// Default to unicode style for unknown values
@@ -2260,39 +7893,137 @@ fn status_indicator(status: AgentStatus, style: &str) -> (&'static str, Style) {
 			),
 			AgentStatus::Idle => ("○", Style::default().fg(Color::Yellow)),
 			AgentStatus::Done => ("✓", Style::default().fg(Color::Cyan)),
+			AgentStatus::Error => (
+				"✗",
+				Style::default().fg(Color::Magenta).add_modifier(Modifier::BOLD),
+			),
 			AgentStatus::Unknown => ("·", Style::default().fg(Color::DarkGray)),
 		},
 	}
 }
 
 fn format_human_duration(d: Duration) -> String {
+	format!("{} ago", format_duration_short(d))
+}
+
+/// "45s", "12m", "3h", "2d"
+fn format_duration_short(d: Duration) -> String {
 	let secs = d.as_secs();
 	if secs < 60 {
-		format!("{secs}s ago")
+		format!("{secs}s")
 	} else if secs < 3600 {
-		format!("{}m ago", secs / 60)
+		format!("{}m", secs / 60)
 	} else if secs < 86_400 {
-		format!("{}h ago", secs / 3600)
+		format!("{}h", secs / 3600)
 	} else {
-		format!("{}d ago", secs / 86_400)
+		format!("{}d", secs / 86_400)
+	}
+}
+
+/// "45m", "2h", "1h30m" - minutes kept, for runtime against a deadline
+fn format_duration_hm(d: Duration) -> String {
+	let minutes = d.as_secs() / 60;
+	match (minutes / 60, minutes % 60) {
+		(0, m) => format!("{m}m"),
+		(h, 0) => format!("{h}h"),
+		(h, m) => format!("{h}h{m}m"),
+	}
+}
+
+/// Details lines from the agent's Claude transcript: turns, last tool call, todo progress
+fn claude_details(state: &claude_session::ClaudeState) -> String {
+	let mut details = format!(
+		"Turns: {} | ~${:.2} | Last tool: {}",
+		state.turns,
+		state.cost_estimate(),
+		state.last_tool.as_deref().unwrap_or("-")
+	);
+	if !state.todos.is_empty() {
+		details.push_str(&format!("\nTodos {}/{}", state.todos_done(), state.todos.len()));
+		if let Some(todo) = state.current_todo() {
+			details.push_str(&format!(" ▸ {}", todo.content));
+		}
+	}
+	details
+}
+
+/// "Asked 1h ago: fix the login redirect…" - the last thing you told the agent,
+/// from swarm's input history or (typed while attached) Claude's transcript,
+/// whichever is newer
+fn last_instruction(session: &str, claude: Option<&claude_session::ClaudeState>) -> Option<String> {
+	let sent = input_history::last(session);
+	let typed = claude.and_then(|c| c.last_prompt.clone());
+	let (text, at) = match (sent, typed) {
+		(Some(sent), Some(typed)) if sent.1 > typed.1 => sent,
+		(sent, typed) => typed.or(sent)?,
+	};
+	let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+	let first = lines.next()?;
+	let more = if lines.next().is_some() { " …" } else { "" };
+	let ago = at
+		.map(|at| Local::now().timestamp() - at)
+		.filter(|secs| *secs >= 60)
+		.map(|secs| format!(" {} ago", format_duration_short(Duration::from_secs(secs as u64))))
+		.unwrap_or_default();
+	Some(format!("Asked{ago}: {}{more}", text::truncate(&text::sanitize(first), 120)))
+}
+
+/// Children named in the Details pane, busiest first
+const MAX_PROCESS_CHILDREN: usize = 3;
+
+/// Details lines for the processes under a pane: the agent and totals, its
+/// busiest children, and any runaway
+fn process_details(procs: &[procs::Proc]) -> String {
+	let describe = |p: &procs::Proc| format!("{} ({}) {:.0}% {}", p.name, p.pid, p.cpu, procs::format_memory(p.memory));
+	let cpu: f32 = procs.iter().map(|p| p.cpu).sum();
+	let memory: u64 = procs.iter().map(|p| p.memory).sum();
+	// The pane's shell is just the launcher; the agent is the first thing under it
+	let Some(agent) = procs::agent(procs) else {
+		return format!("Processes: no agent process · {}", procs::format_memory(memory));
+	};
+	let mut details = format!("Process: {} · total {:.0}% {}", describe(agent), cpu, procs::format_memory(memory));
+	let mut children: Vec<&procs::Proc> = procs.iter().filter(|p| p.depth > agent.depth && !p.is_shell()).collect();
+	if !children.is_empty() {
+		children.sort_by(|a, b| b.cpu.total_cmp(&a.cpu));
+		let named: Vec<String> = children.iter().take(MAX_PROCESS_CHILDREN).map(|p| describe(p)).collect();
+		details.push_str(&format!("\nChildren: {}", named.join(", ")));
+		if children.len() > MAX_PROCESS_CHILDREN {
+			details.push_str(&format!(" +{} more", children.len() - MAX_PROCESS_CHILDREN));
+		}
 	}
+	for p in procs.iter().filter(|p| p.is_runaway()) {
+		let busy = p.busy_for.map(format_duration_short).unwrap_or_default();
+		details.push_str(&format!("\n⚠ Runaway: {} ({}) has used a full core for {busy}", p.name, p.pid));
+	}
+	details
 }
 
 fn agent_details(sel: &AgentSession) -> String {
 	let task_path = sel
 		.task
 		.as_ref()
-		.map(|t| t.path.display().to_string())
+		.map(|t| format!("{} (v: process log)", t.path.display()))
 		.unwrap_or_else(|| "-".to_string());
 	let repo_path = session_path(&sel.session_name)
 		.ok()
 		.flatten()
 		.unwrap_or_else(|| "-".to_string());
 	let read_cmd = format!("tmux capture-pane -p -S -500 -t {}", sel.session_name);
-	format!(
-		"Task: {}\nRepo: {}\n\nRead from another Claude:\n{}",
-		task_path, repo_path, read_cmd
-	)
+	let model = sel.model.as_deref().unwrap_or("default");
+	let mut details = format!(
+		"Task: {}\nRepo: {}\nModel: {} ({})\n\nRead from another Claude:\n{}",
+		task_path, repo_path, model, sel.agent, read_cmd
+	);
+	if sel.interviewing {
+		details = format!("Interviewing you to write the task's spec; ends when its status changes\n{details}");
+	}
+	if let Some(code) = sel.exit_code {
+		details = format!(
+			"Agent exited (code {}) - press r to respawn, d to clean up\n{}",
+			code, details
+		);
+	}
+	details
 }
 
 fn help_text() -> String {
@@ -2307,7 +8038,24 @@ Agents
   enter  send input       a  attach tmux
   S-Tab  cycle mode       n  new agent
   1-9    quick select     d  kill session
+  A      attach read-only (watch, no typing)
   s      cycle style      c  open config
+  p      replay log         r  respawn exited agent
+  v      task process log   H  hand off to new agent
+  R      rename session     C  compare two agents
+  y      copy preview / attach command / task path
+  Tab/!  jump to next agent needing input (or errored)
+  L      land worktree: rebase, checks, push, open/update PR
+  G      group actions: broadcast, pause all, kill all, collapse
+  M      do not disturb: 1h → until unmuted → off
+  N      notes on the session    /  filter (name, task, notes...)
+  f      next saved view ([[views]]: status:blocked age>10m...)
+  P      pin: skip in group kills, limit kills, cleanup
+  T      soft deadline ("should finish within 2h"): ⏱ on the row, notifies once past it
+  K      processes: SIGINT/SIGKILL a hung child (tests, builds)
+  :      run a command in the agent's directory (cargo test), output in a modal
+  E      error console (also in ~/.swarm/swarm.log)
+  O      history: finished runs, / to search by task, s for stats
 
 Claude Slash Commands
   /done       end session, log work
@@ -2320,7 +8068,7 @@ Git Worktrees
   Claude asks if you want a worktree for code tasks
   Config: worktree_dir = "~/worktrees" in ~/.swarm/config.toml
 
-tmux: Alt+d detach · Alt+↑/↓ scroll
+tmux: Alt+d detach · Alt+↑/↓ scroll · Alt+. / Alt+, next/prev agent · Alt+0 dashboard
 
 ──────────────────────────────────────
 Voice input: wisprflow.ai/r?JACK4715
@@ -2360,6 +8108,7 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
 fn attach_to(
 	terminal: &mut ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
 	sel: &AgentSession,
+	read_only: bool,
 ) -> Result<()> {
 	// Leave TUI
 	teardown_terminal()?;
@@ -2374,30 +8123,285 @@ fn attach_to(
 		}
 	}
 
-	let status = Command::new(find_tmux())
-		.arg("attach-session")
+	let mut cmd = Command::new(find_tmux());
+	cmd.arg("attach-session");
+	let mut badge = None;
+	if read_only {
+		// -r: keystrokes aren't sent to the pane (Alt+d still detaches)
+		cmd.arg("-r");
+		badge = tmux::show_readonly_badge(&sel.session_name).ok();
+	}
+	let status = cmd
 		.arg("-t")
 		.arg(&sel.session_name)
 		.status()
+		.context("failed to attach to tmux session");
+	// Back from the read-only view: the session's status bar is the agent's again
+	if let Some(previous) = badge {
+		let _ = tmux::restore_status_left(&sel.session_name, previous.as_deref());
+	}
+	let status = status?;
+	if !status.success() {
+		console::error("attach", format!("tmux attach failed: {} (using {})", status, find_tmux()));
+	}
+	reenter_tui(terminal)
+}
+
+/// Something `swarm pick` can jump to
+enum PickTarget {
+	Session(String),
+	Task(TaskEntry),
+}
+
+/// Rows the inline picker takes up below the prompt
+const PICK_HEIGHT: u16 = 15;
+
+/// fzf-style picker over sessions and tasks, drawn inline below the shell prompt
+fn run_pick(cfg: &Config) -> Result<()> {
+	use fuzzy_matcher::FuzzyMatcher;
+
+	if !tmux::is_available() {
+		return Err(anyhow::anyhow!("tmux not found - swarm pick attaches to tmux sessions. Install with: brew install tmux"));
+	}
+	let sessions = collect_sessions(cfg)?;
+	let tasks = load_tasks(cfg);
+	let mut items: Vec<(String, PickTarget)> = sessions
+		.iter()
+		.map(|s| {
+			let task = s.task.as_ref().map(|t| format!("  · {}", t.title)).unwrap_or_default();
+			(
+				format!("{} {}{}", status_indicator(s.status, "emoji").0, s.name, task),
+				PickTarget::Session(s.session_name.clone()),
+			)
+		})
+		.collect();
+	// Tasks that already have an agent are reachable through their session
+	items.extend(
+		tasks
+			.into_iter()
+			.filter(|t| find_sessions_for_task(&sessions, &t.path).is_empty())
+			.map(|t| {
+				let due = t.due.map(|d| format!("  · {}", format_due(d))).unwrap_or_default();
+				(format!("📝 {}{}", t.title, due), PickTarget::Task(t))
+			}),
+	);
+	if items.is_empty() {
+		println!("No agents or tasks yet");
+		return Ok(());
+	}
+
+	let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+	let mut query = String::new();
+	let mut list_state = ListState::default();
+	enable_raw_mode()?;
+	let mut terminal = ratatui::Terminal::with_options(
+		ratatui::backend::CrosstermBackend::new(stdout()),
+		ratatui::TerminalOptions {
+			viewport: ratatui::Viewport::Inline(PICK_HEIGHT),
+		},
+	)?;
+	let choice = loop {
+		// Best match first; ties keep sessions before tasks
+		let mut filtered: Vec<(i64, usize)> = items
+			.iter()
+			.enumerate()
+			.filter_map(|(idx, (label, _))| {
+				if query.is_empty() {
+					Some((0, idx))
+				} else {
+					matcher.fuzzy_match(label, &query).map(|score| (score, idx))
+				}
+			})
+			.collect();
+		filtered.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+		let selected = list_state
+			.selected()
+			.unwrap_or(0)
+			.min(filtered.len().saturating_sub(1));
+		list_state.select((!filtered.is_empty()).then_some(selected));
+
+		terminal.draw(|f| {
+			let rows = Layout::default()
+				.direction(Direction::Vertical)
+				.constraints([Constraint::Length(1), Constraint::Min(1)].as_ref())
+				.split(f.area());
+			let prompt = Line::from(vec![
+				Span::styled("> ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+				Span::raw(query.as_str()),
+				Span::styled(
+					format!("  {}/{}", filtered.len(), items.len()),
+					Style::default().fg(Color::DarkGray),
+				),
+			]);
+			f.render_widget(Paragraph::new(prompt), rows[0]);
+			let list_items: Vec<ListItem> = filtered
+				.iter()
+				.map(|(_, idx)| ListItem::new(items[*idx].0.as_str()))
+				.collect();
+			let list = List::new(list_items)
+				.highlight_symbol("▶ ")
+				.highlight_style(Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED));
+			f.render_stateful_widget(list, rows[1], &mut list_state);
+		})?;
+
+		let Event::Key(key) = event::read()? else {
+			continue;
+		};
+		if key.kind != KeyEventKind::Press {
+			continue;
+		}
+		let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+		match key.code {
+			KeyCode::Esc => break None,
+			KeyCode::Char('c') if ctrl => break None,
+			KeyCode::Enter => break filtered.get(selected).map(|(_, idx)| *idx),
+			KeyCode::Up => list_state.select(Some(selected.saturating_sub(1))),
+			KeyCode::Char('p') if ctrl => list_state.select(Some(selected.saturating_sub(1))),
+			KeyCode::Down => list_state.select(Some(selected + 1)),
+			KeyCode::Char('n') if ctrl => list_state.select(Some(selected + 1)),
+			KeyCode::Backspace => {
+				query.pop();
+				list_state.select(Some(0));
+			}
+			KeyCode::Char(c) if !ctrl => {
+				query.push(c);
+				list_state.select(Some(0));
+			}
+			_ => {}
+		}
+	};
+	terminal.clear()?;
+	disable_raw_mode()?;
+
+	let Some(idx) = choice else {
+		return Ok(());
+	};
+	let session_name = match &items[idx].1 {
+		PickTarget::Session(name) => name.clone(),
+		PickTarget::Task(task) => {
+			let name = start_from_task(cfg, task)?;
+			println!("Started {}{}", SWARM_PREFIX, name);
+			format!("{SWARM_PREFIX}{name}")
+		}
+	};
+	if let Some(conf) = tmux_conf_path() {
+		if conf.exists() {
+			let _ = Command::new(find_tmux()).arg("source-file").arg(&conf).status();
+		}
+	}
+	// Inside tmux, attaching would nest; switch this client instead
+	let action = if std::env::var_os("TMUX").is_some() { "switch-client" } else { "attach-session" };
+	let status = Command::new(find_tmux())
+		.args([action, "-t", &session_name])
+		.status()
 		.context("failed to attach to tmux session")?;
 	if !status.success() {
-		eprintln!("tmux attach failed: {} (using {})", status, find_tmux());
+		eprintln!("tmux {} failed: {} (using {})", action, status, find_tmux());
+	}
+	Ok(())
+}
+
+fn replay_in_terminal(
+	terminal: &mut ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
+	sel: &AgentSession,
+) -> Result<()> {
+	// Leave TUI so the log renders with its original colors
+	teardown_terminal()?;
+	if let Err(e) = logs::replay(&sel.log_path, Duration::ZERO, true) {
+		console::error("replay", format!("{}: {}", sel.session_name, e));
 	}
-	// Re-enter TUI
+	reenter_tui(terminal)
+}
+
+fn reenter_tui(
+	terminal: &mut ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
+) -> Result<()> {
 	enable_raw_mode()?;
 	let mut stdout_handle = stdout();
-	execute!(stdout_handle, EnterAlternateScreen)?;
+	execute!(stdout_handle, EnterAlternateScreen, EnableFocusChange, EnableBracketedPaste)?;
 	*terminal = ratatui::Terminal::new(ratatui::backend::CrosstermBackend::new(stdout_handle))?;
 	Ok(())
 }
 
+/// Compose text in $VISUAL/$EDITOR (vi if unset), starting from `initial`.
+/// Returns None if the editor failed, so the caller keeps what it had.
+fn edit_in_editor(
+	terminal: &mut ratatui::Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>,
+	initial: &str,
+) -> Result<Option<String>> {
+	let path = std::env::temp_dir().join(format!("swarm-input-{}.md", std::process::id()));
+	fs::write(&path, initial)?;
+	let editor = std::env::var("VISUAL")
+		.or_else(|_| std::env::var("EDITOR"))
+		.unwrap_or_else(|_| "vi".to_string());
+	teardown_terminal()?;
+	// Through sh so editors with arguments ("code -w") work
+	let status = Command::new("sh")
+		.arg("-c")
+		.arg(format!("{editor} \"$1\""))
+		.arg("sh")
+		.arg(&path)
+		.status();
+	reenter_tui(terminal)?;
+	let text = fs::read_to_string(&path).ok();
+	let _ = fs::remove_file(&path);
+	match status {
+		Ok(s) if s.success() => Ok(text.map(|t| t.trim_end().to_string())),
+		_ => Ok(None),
+	}
+}
+
+/// On a panic, give the terminal back (for the UI thread) and save a crash
+/// report with the backtrace to ~/.swarm/crash/ before the default message
+fn install_panic_hook() {
+	let default_hook = std::panic::take_hook();
+	std::panic::set_hook(Box::new(move |info| {
+		let thread = std::thread::current().name().unwrap_or("unnamed").to_string();
+		// A background thread panicking leaves the TUI running; don't pull the screen from under it
+		if thread == "main" {
+			let _ = teardown_terminal();
+		}
+		let report = format!(
+			"swarm v{} crashed at {}\nthread: {thread}\n{info}\n\nbacktrace:\n{}\n",
+			env!("CARGO_PKG_VERSION"),
+			Local::now().format("%Y-%m-%d %H:%M:%S"),
+			std::backtrace::Backtrace::force_capture()
+		);
+		let saved = config::base_dir().and_then(|dir| {
+			let dir = dir.join("crash");
+			fs::create_dir_all(&dir)?;
+			let path = dir.join(format!("{}.txt", Local::now().format("%Y%m%d-%H%M%S")));
+			fs::write(&path, &report)?;
+			Ok(path)
+		});
+		default_hook(info);
+		if let Ok(path) = saved {
+			eprintln!("\nswarm crashed - report saved to {}", path.display());
+		}
+	}));
+}
+
 fn teardown_terminal() -> Result<()> {
 	disable_raw_mode()?;
-	execute!(stdout(), LeaveAlternateScreen)?;
+	execute!(stdout(), DisableBracketedPaste, DisableFocusChange, LeaveAlternateScreen)?;
 	Ok(())
 }
 
-fn mark_done(session: &AgentSession, _cfg: &Config) -> Result<()> {
+fn mark_done(session: &AgentSession, cfg: &Config) -> Result<()> {
+	// Capture the closing state first so the archived timeline includes it
+	if let Some(task) = &session.task {
+		let summary = closing_summary(session);
+		if !summary.is_empty() {
+			let heading = format!("### {} - Session ended (captured by swarm)", Local::now().format("%Y-%m-%d %H:%M"));
+			if let Err(e) = append_process_log(&task.path, &heading, &summary) {
+				console::error("append process log", e);
+			}
+		}
+	}
+	// Archive while the tmux session, its log and metadata still exist
+	if let Err(e) = archive_session(cfg, session) {
+		console::error("archive session", e);
+	}
 	// Just kill the session and clean up session store
 	kill_session(&session.session_name)?;
 
@@ -2413,20 +8417,468 @@ fn mark_done(session: &AgentSession, _cfg: &Config) -> Result<()> {
 	}
 	// Remove log file
 	let _ = fs::remove_file(&session.log_path);
+	if let Some(task) = &session.task {
+		release_task_claim(cfg, &task.path);
+	}
+	Ok(())
+}
+
+/// `swarm history`: one line per archived run
+fn print_history(task: Option<&str>, json: bool, limit: usize) -> Result<()> {
+	let runs: Vec<history::Run> = history::load()
+		.into_iter()
+		.filter(|r| task.is_none_or(|q| r.matches(q)))
+		.take(limit)
+		.collect();
+	if json {
+		println!("{}", serde_json::to_string_pretty(&runs)?);
+		return Ok(());
+	}
+	if runs.is_empty() {
+		println!("No finished runs yet (sessions are archived when marked done)");
+		return Ok(());
+	}
+	for run in &runs {
+		println!("{}", history_row(run));
+	}
+	Ok(())
+}
+
+/// Text charts for `swarm stats` and the History view's stats page
+fn stats_lines(stats: &history::Stats) -> Vec<String> {
+	let avg = |d: Option<Duration>| d.map(format_duration_short).unwrap_or_else(|| "-".to_string());
+	let rate = |t: &history::Tally| t.success_rate().map(|r| format!("{r:.0}%")).unwrap_or_else(|| "-".to_string());
+	let bar = |n: usize, max: usize| {
+		let filled = (n * 24).checked_div(max).unwrap_or(0);
+		format!("{}{}", "█".repeat(filled), "░".repeat(24 - filled))
+	};
+	let total = &stats.total;
+	let mut lines = vec![
+		format!(
+			"{} run(s) · {} done · avg {} · avg blocked {}",
+			total.runs,
+			rate(total),
+			avg(total.avg_duration()),
+			avg(total.avg_blocked())
+		),
+		String::new(),
+		"Completed per week".to_string(),
+	];
+	let most = stats.weeks.iter().map(|(_, t)| t.succeeded).max().unwrap_or(0);
+	for (week, t) in &stats.weeks {
+		lines.push(format!(
+			"  {}  {} {:>3}  avg {:<4} blocked {}",
+			text::pad(&dates::short_date(*week), 6),
+			bar(t.succeeded, most),
+			t.succeeded,
+			avg(t.avg_duration()),
+			avg(t.avg_blocked())
+		));
+	}
+	lines.push(String::new());
+	lines.push("Success rate by agent".to_string());
+	for (agent, t) in &stats.agents {
+		lines.push(format!(
+			"  {}  {} {:>4}  {} run(s), avg {}",
+			text::pad(&text::truncate(agent, 20), 20),
+			bar(t.succeeded, t.runs),
+			rate(t),
+			t.runs,
+			avg(t.avg_duration())
+		));
+	}
+	lines
+}
+
+/// "Oct 16 14:02  auth-fix        claude  1h  done              Fix login"
+fn history_row(run: &history::Run) -> String {
+	let ended = chrono::DateTime::from_timestamp(run.ended, 0)
+		.unwrap_or_default()
+		.with_timezone(&Local);
+	format!(
+		"{}  {}  {}  {}  {}  {}",
+		text::pad(&format!("{} {}", dates::short_date(ended.date_naive()), dates::time(ended.time())), 14),
+		text::pad(&text::truncate(&run.name, 16), 16),
+		text::pad(&text::truncate(&run.agent, 7), 7),
+		text::pad(&run.duration().map(format_duration_short).unwrap_or_else(|| "-".to_string()), 4),
+		text::pad(&text::truncate(&run.outcome, 22), 22),
+		run.task.as_deref().unwrap_or("-"),
+	)
+}
+
+/// Save a finished session to ~/.swarm/history/ (see history.rs)
+fn archive_session(cfg: &Config, session: &AgentSession) -> Result<PathBuf> {
+	let work_dir = session_work_dir(session);
+	let timeline = session
+		.task
+		.as_ref()
+		.and_then(|t| parse_process_log(&t.path))
+		.unwrap_or_default()
+		.into_iter()
+		.map(|l| l.trim().to_string())
+		.filter(|l| !l.is_empty())
+		.collect();
+	let mut log_tail: Vec<String> = tail_lines(&session.log_path, 40)
+		.unwrap_or_default()
+		.iter()
+		.map(|l| text::sanitize(l).trim_end().to_string())
+		.collect();
+	while log_tail.last().is_some_and(|l| l.is_empty()) {
+		log_tail.pop();
+	}
+	let run = history::Run {
+		name: session.name.clone(),
+		session: session.session_name.clone(),
+		agent: session.agent.clone(),
+		model: session.model.clone(),
+		task: session.task.as_ref().map(|t| t.title.clone()),
+		task_path: session.task.as_ref().map(|t| t.path.clone()),
+		group: session.group.clone(),
+		branch: work_dir.as_deref().and_then(|d| git_output(d, &["branch", "--show-current"])),
+		diff: session_diff_lines(cfg, session),
+		work_dir,
+		started: tmux::session_created(&session.session_name)
+			.and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+			.map(|d| d.as_secs() as i64),
+		ended: Local::now().timestamp(),
+		blocked: Some(blocked_secs(&session.session_name)),
+		status: session.status,
+		exit_code: session.exit_code,
+		outcome: history::outcome(session.status, session.exit_code),
+		timeline,
+		log_tail,
+	};
+	history::save(&run)
+}
+
+/// The agent's todo list and final message when a session ends, as markdown
+/// lines for the Process Log: from Claude's transcript when there is one, else
+/// the last checklist and lines of output in the log
+fn closing_summary(session: &AgentSession) -> Vec<String> {
+	let mut lines = Vec::new();
+	let mut state = claude_session::ClaudeState::default();
+	if let (Some(dir), "claude") = (session_work_dir(session), session.agent.as_str()) {
+		claude_session::refresh(&mut state, &dir);
+	}
+	let message: Vec<String> = if !state.path.as_os_str().is_empty() {
+		for todo in &state.todos {
+			let mark = if todo.status == "completed" { "x" } else { " " };
+			lines.push(format!("- [{mark}] {}", todo.content));
+		}
+		state.last_message.iter().flat_map(|m| m.lines()).map(str::to_string).collect()
+	} else {
+		let output: Vec<String> = tail_lines(&session.log_path, 200)
+			.unwrap_or_default()
+			.iter()
+			.map(|l| text::sanitize(l).trim_end().to_string())
+			.collect();
+		for (checked, item) in detection::latest_checklist(&output) {
+			lines.push(format!("- [{}] {item}", if checked { "x" } else { " " }));
+		}
+		// The reply is what follows the checklist, minus the input box and
+		// separators the agent's UI draws below it
+		let after = output.iter().rposition(|l| detection::checklist_item(l).is_some()).map_or(0, |i| i + 1);
+		let mut tail: Vec<String> = output[after..]
+			.iter()
+			.rev()
+			.map(|l| l.trim())
+			.filter(|l| !l.is_empty() && !l.starts_with(['│', '╭', '╰', '─', '━', '>', '❯']))
+			.take(10)
+			.map(str::to_string)
+			.collect();
+		tail.reverse();
+		tail
+	};
+	if !message.is_empty() {
+		if !lines.is_empty() {
+			lines.push(String::new());
+		}
+		lines.extend(message.iter().map(|l| format!("> {l}").trim_end().to_string()));
+	}
+	lines
+}
+
+/// Add an entry to the end of a task file's "## Process Log" section, creating
+/// the section if the file has none
+fn append_process_log(path: &Path, heading: &str, body: &[String]) -> Result<()> {
+	let content = fs::read_to_string(path)?;
+	let entry = format!("{heading}\n\n{}\n", body.join("\n"));
+	let mut lines: Vec<&str> = content.lines().collect();
+	let updated = match lines.iter().position(|l| l.trim().eq_ignore_ascii_case("## Process Log")) {
+		Some(start) => {
+			let end = lines[start + 1..]
+				.iter()
+				.position(|l| l.starts_with("## "))
+				.map_or(lines.len(), |i| start + 1 + i);
+			// Drop trailing blank lines so entries stay one blank line apart
+			let mut last = end;
+			while last > start + 1 && lines[last - 1].trim().is_empty() {
+				last -= 1;
+			}
+			let rest = lines.split_off(end);
+			lines.truncate(last);
+			let mut out = lines.join("\n") + "\n\n" + &entry;
+			if !rest.is_empty() {
+				out.push_str(&format!("\n{}\n", rest.join("\n")));
+			}
+			out
+		}
+		None => format!("{}\n\n## Process Log\n\n{entry}", content.trim_end()),
+	};
+	fs::write(path, updated)?;
 	Ok(())
 }
 
+/// Trimmed stdout of a git command run in dir, or None on failure/empty output
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+	Command::new("git")
+		.arg("-C")
+		.arg(dir)
+		.args(args)
+		.output()
+		.ok()
+		.filter(|o| o.status.success())
+		.map(|o| String::from_utf8_lossy(&o.stdout).trim_end().to_string())
+		.filter(|s| !s.is_empty())
+}
+
+/// Working directory of a session: its worktree, else the pane's current path
+fn session_work_dir(session: &AgentSession) -> Option<PathBuf> {
+	session
+		.worktree_path
+		.clone()
+		.or_else(|| session_path(&session.session_name).ok().flatten().map(PathBuf::from))
+}
+
+/// Output and exit code of the last command run with : in a session
+fn session_run_paths(session: &str) -> Result<(PathBuf, PathBuf)> {
+	let dir = session_store_dir()?.join(session);
+	fs::create_dir_all(&dir)?;
+	Ok((dir.join("run.log"), dir.join("run.status")))
+}
+
+/// Run `command` in the session's working directory, in a window beside the
+/// agent. Returns the directory it runs in.
+fn run_in_session(session: &AgentSession, command: &str) -> Result<PathBuf> {
+	let dir = session_work_dir(session).ok_or_else(|| anyhow::anyhow!("no working directory for {}", session.name))?;
+	let (output, status) = session_run_paths(&session.session_name)?;
+	let _ = fs::remove_file(&output);
+	let _ = fs::remove_file(&status);
+	tmux::run_in_window(&session.session_name, &dir, command, &output, &status)?;
+	Ok(dir)
+}
+
+/// Changed files and diff stat for a session's working directory (compare view)
+fn session_diff_lines(cfg: &Config, session: &AgentSession) -> Vec<String> {
+	let Some(dir) = session_work_dir(session) else {
+		return vec!["(unknown working directory)".to_string()];
+	};
+	let mut lines = vec![format!("{}", dir.display()), String::new()];
+	match git_output(&dir, &["status", "--short"]) {
+		Some(status) => lines.extend(status.lines().map(str::to_string)),
+		None => lines.push("(no changes)".to_string()),
+	}
+	if let Some(stat) = git_output(&dir, &["diff", "--stat", "HEAD"]) {
+		lines.push(String::new());
+		lines.extend(stat.lines().map(str::to_string));
+	}
+	// Committed work on the branch (variants commit as they go), against the
+	// same base branch a worktree would start from
+	let main_repo = main_repo_dir(&dir).unwrap_or_else(|| dir.clone());
+	let project = project::load(&main_repo).ok().flatten();
+	let base = worktree_base(cfg, project.as_ref(), &main_repo, None);
+	if let Some(log) = git_output(&dir, &["log", "--oneline", "-n", "15", &format!("{base}..HEAD")]) {
+		lines.push(String::new());
+		lines.push(format!("Commits vs {base}:"));
+		lines.extend(log.lines().map(str::to_string));
+		if let Some(stat) = git_output(&dir, &["diff", "--stat", &format!("{base}...HEAD")]) {
+			lines.extend(stat.lines().map(str::to_string));
+		}
+	}
+	lines
+}
+
+/// Daily-dir file for a finished group's combined report
+fn group_report_path(cfg: &Config, group: &str) -> PathBuf {
+	PathBuf::from(&cfg.general.daily_dir).join(format!("{}-{}.md", Local::now().format("%Y-%m-%d"), slugify(group)))
+}
+
+/// One markdown report for a finished group: per agent its task, branch, PR,
+/// changes and latest Process Log entries
+fn write_group_report(cfg: &Config, group: &str, members: &[AgentSession]) -> Result<PathBuf> {
+	let mut doc = format!(
+		"# Group {group}\n\n{} agent(s), finished {}\n",
+		members.len(),
+		Local::now().format("%Y-%m-%d %H:%M")
+	);
+	for session in members {
+		doc.push_str(&format!("\n## {}\n\nAgent: {}\n", session.name, session.agent));
+		if let Some(task) = &session.task {
+			doc.push_str(&format!("Task: {} ({})\n", task.title, task.path.display()));
+		}
+		let dir = session_work_dir(session);
+		if let Some(branch) = dir.as_deref().and_then(|d| git_output(d, &["branch", "--show-current"])) {
+			doc.push_str(&format!("Branch: {branch}\n"));
+		}
+		if let Some(pr) = dir.as_deref().and_then(pull_request_info) {
+			doc.push_str(&format!(
+				"PR: {} ({})\n",
+				pr["url"].as_str().unwrap_or_default(),
+				pr["state"].as_str().unwrap_or_default().to_lowercase()
+			));
+		}
+		doc.push_str("\n### Changes\n\n```\n");
+		doc.push_str(session_diff_lines(cfg, session).join("\n").trim());
+		doc.push_str("\n```\n");
+		if let Some(log) = session.task.as_ref().and_then(|t| parse_process_log(&t.path)) {
+			let recent: Vec<&String> = log.iter().rev().filter(|l| !l.trim().is_empty()).take(20).collect();
+			if !recent.is_empty() {
+				doc.push_str("\n### Process Log (latest)\n\n");
+				for line in recent.into_iter().rev() {
+					doc.push_str(line);
+					doc.push('\n');
+				}
+			}
+		}
+	}
+	let path = group_report_path(cfg, group);
+	fs::create_dir_all(path.parent().unwrap_or(Path::new(".")))?;
+	fs::write(&path, doc)?;
+	Ok(path)
+}
+
+/// Snapshot a session's context (diff summary, process log, last output) to a markdown file
+fn write_handoff_snapshot(session: &AgentSession, dir: &Path) -> Result<PathBuf> {
+	let mut doc = format!(
+		"# Handoff from {}\n\nAgent: {}\nRepo: {}\n",
+		session.name,
+		session.agent,
+		dir.display()
+	);
+	if let Some(task) = &session.task {
+		doc.push_str(&format!("Task: {}\n", task.path.display()));
+	}
+
+	doc.push_str("\n## Diff Summary\n\n```\n");
+	doc.push_str(&git_output(dir, &["status", "--short"]).unwrap_or_else(|| "(no changes)".to_string()));
+	if let Some(stat) = git_output(dir, &["diff", "--stat", "HEAD"]) {
+		doc.push_str("\n\n");
+		doc.push_str(&stat);
+	}
+	doc.push_str("\n```\n");
+
+	if let Some(log) = session.task.as_ref().and_then(|t| parse_process_log(&t.path)) {
+		let recent: Vec<&String> = log.iter().rev().take(40).collect();
+		doc.push_str("\n## Process Log (latest)\n\n");
+		for line in recent.into_iter().rev() {
+			doc.push_str(line);
+			doc.push('\n');
+		}
+	}
+
+	let last_output = tmux::capture_tail(&session.session_name, 60)
+		.or_else(|_| tail_lines(&session.log_path, 60))
+		.unwrap_or_default();
+	doc.push_str("\n## Last Output\n\n```\n");
+	doc.push_str(clean_preview(&last_output).join("\n").trim());
+	doc.push_str("\n```\n");
+
+	let ts = chrono::Local::now().format("%Y%m%d-%H%M%S");
+	let path = snapshots_dir()?.join(format!("{}-handoff-{}.md", session.session_name, ts));
+	fs::write(&path, doc)?;
+	Ok(path)
+}
+
+/// Hand a session's work to a new agent: snapshot context, start successor, end the old session
+fn handoff_session(
+	cfg: &Config,
+	session: &AgentSession,
+	agent: &str,
+	note: Option<&str>,
+	base_name: Option<&str>,
+) -> Result<String> {
+	let dir = session_work_dir(session)
+		.ok_or_else(|| anyhow::anyhow!("could not determine working directory for {}", session.name))?;
+	let snapshot = write_handoff_snapshot(session, &dir)?;
+
+	let base = base_name
+		.map(str::to_string)
+		.unwrap_or_else(|| format!("{}-handoff", session.name.trim_end_matches("-handoff")));
+	let new_name = unique_session_name(cfg, &base)?;
+	let mut prompt = format!(
+		"You are taking over work from another agent ({}). Read {} for the handoff context (diff summary, process log, last output) and continue from where it left off.",
+		session.name,
+		snapshot.display()
+	);
+	if let Some(task) = &session.task {
+		prompt.push_str(&format!(" The task file is {} - keep its Process Log up to date.", task.path.display()));
+	}
+	if let Some(note) = note {
+		prompt.push_str(&format!("\n\nInstructions: {}", note));
+	}
+
+	handle_new(
+		cfg,
+		new_name.clone(),
+		agent.to_string(),
+		dir.to_string_lossy().into_owned(),
+		Some(prompt),
+		session.task.as_ref().map(|t| t.path.to_string_lossy().into_owned()),
+		session.is_yolo,
+		// Keep the model only when the same kind of agent takes over
+		if session.agent == agent { session.model.clone() } else { None },
+		None, // base
+		false, // announce
+	)?;
+	if let Some(wt) = &session.worktree_path {
+		let _ = fs::write(session_worktree_path(&format!("{SWARM_PREFIX}{new_name}"))?, wt.to_string_lossy().as_bytes());
+	}
+	mark_done(session, cfg)?;
+	Ok(new_name)
+}
+
+/// Start the next stage of a pipeline session. Returns the new session name,
+/// or None when the finished stage was the last one.
+fn advance_pipeline(cfg: &Config, session: &AgentSession) -> Result<Option<String>> {
+	let (Some(stage), Some(task)) = (&session.stage, &session.task) else {
+		return Ok(None);
+	};
+	let stages = pipeline::parse_stages(&task.path);
+	let next_index = stage.index + 1;
+	let Some(next) = stages.get(next_index) else {
+		return Ok(None);
+	};
+	let agent = next
+		.agent
+		.clone()
+		.unwrap_or_else(|| cfg.general.default_agent.clone());
+	let note = pipeline::stage_prompt(next, next_index, stages.len());
+	let base = pipeline_session_name(&task.title, &next.name);
+	let new_name = handoff_session(cfg, session, &agent, Some(&note), Some(&base))?;
+	fs::write(
+		session_stage_path(&format!("{SWARM_PREFIX}{new_name}"))?,
+		next_index.to_string(),
+	)?;
+	Ok(Some(new_name))
+}
+
+fn pipeline_session_name(task_title: &str, stage_name: &str) -> String {
+	let stage = slugify(stage_name);
+	// Keep the stage suffix visible when the task title is long
+	let base: String = slugify(task_title)
+		.chars()
+		.take(100usize.saturating_sub(stage.len() + 1))
+		.collect();
+	format!("{}-{}", base, stage)
+}
+
 #[allow(dead_code)] // May be useful for future daily logging features
 fn append_daily(session: &AgentSession, cfg: &Config) -> Result<()> {
-	let dir = PathBuf::from(&cfg.general.daily_dir);
-	fs::create_dir_all(&dir)?;
 	let date = Local::now();
-	let file = dir.join(format!(
-		"{}-{:02}-{:02}.md",
-		date.year(),
-		date.month(),
-		date.day()
-	));
+	let file = obsidian::daily_log(cfg, date.date_naive());
+	if let Some(dir) = file.parent() {
+		fs::create_dir_all(dir)?;
+	}
 	let mut f = fs::OpenOptions::new()
 		.create(true)
 		.append(true)
@@ -2449,15 +8901,21 @@ fn append_daily(session: &AgentSession, cfg: &Config) -> Result<()> {
 }
 
 fn start_from_task(cfg: &Config, task: &TaskEntry) -> Result<String> {
-	start_from_task_inner(cfg, task, false)
+	start_from_task_inner(cfg, task, false, None, None)
 }
 
 /// ⚠️ YOLO MODE - Start task with --dangerously-skip-permissions
 fn start_from_task_yolo(cfg: &Config, task: &TaskEntry) -> Result<String> {
-	start_from_task_inner(cfg, task, true)
+	start_from_task_inner(cfg, task, true, None, None)
 }
 
-fn start_from_task_inner(cfg: &Config, task: &TaskEntry, auto_accept: bool) -> Result<String> {
+fn start_from_task_inner(
+	cfg: &Config,
+	task: &TaskEntry,
+	auto_accept: bool,
+	model: Option<&str>,
+	dir: Option<&Path>,
+) -> Result<String> {
 	let base_name = slugify(task.title.clone());
 	// Truncate base name to avoid "file name too long" errors (macOS limit is 255 bytes)
 	// Keep it under 100 chars to leave room for session prefix and other path components
@@ -2466,8 +8924,11 @@ fn start_from_task_inner(cfg: &Config, task: &TaskEntry, auto_accept: bool) -> R
 	} else {
 		base_name
 	};
-	let session_name = unique_session_name(&truncated_name)?;
-	let repo = std::env::current_dir()?.to_string_lossy().into_owned();
+	let session_name = unique_session_name(cfg, &truncated_name)?;
+	let repo = match dir {
+		Some(dir) => dir.to_string_lossy().into_owned(),
+		None => std::env::current_dir()?.to_string_lossy().into_owned(),
+	};
 
 	// Build prompt with additional directories hint if configured
 	let additional_dirs_note = if !cfg.allowed_tools.additional_directories.is_empty() {
@@ -2485,38 +8946,499 @@ fn start_from_task_inner(cfg: &Config, task: &TaskEntry, auto_accept: bool) -> R
 		String::new()
 	};
 
-	let prompt = format!(
+	let mut prompt = format!(
 		"Starting task. Read {} for context (include any Process Log). Summarize the task file before acting.{}",
 		task.path.display(),
 		additional_dirs_note
 	);
+
+	// Pipelines start at their first stage, possibly with a stage-specific agent
+	let stages = pipeline::parse_stages(&task.path);
+	let mut agent = cfg.general.default_agent.clone();
+	let mut session_name = session_name;
+	if let Some(first) = stages.first() {
+		prompt.push_str("\n\n");
+		prompt.push_str(&pipeline::stage_prompt(first, 0, stages.len()));
+		if let Some(stage_agent) = &first.agent {
+			agent = stage_agent.clone();
+		}
+		session_name = unique_session_name(cfg, &pipeline_session_name(&task.title, &first.name))?;
+	}
+	let interview = stages.is_empty() && wants_interview(cfg, &agent, &task.path);
+	if interview {
+		prompt = interview_prompt(&task.path.to_string_lossy());
+	}
+
 	handle_new(
 		cfg,
 		session_name.clone(),
-		cfg.general.default_agent.clone(),
+		agent,
 		repo,
 		Some(prompt),
 		Some(task.path.to_string_lossy().into_owned()),
 		auto_accept,
+		model.map(str::to_string),
+		None, // base
 		false, // announce
 	)?;
+	if !stages.is_empty() {
+		fs::write(session_stage_path(&format!("{SWARM_PREFIX}{session_name}"))?, "0")?;
+	}
+	if interview {
+		mark_interviewing(&format!("{SWARM_PREFIX}{session_name}"), &task.path)?;
+	}
 	Ok(session_name)
 }
 
-fn unique_session_name(base: &str) -> Result<String> {
+/// Start a planner on `task` that splits it into subtask files (see
+/// pipeline::planner_prompt). Returns the session and group names.
+fn start_fanout(cfg: &Config, task: &TaskEntry, group: Option<String>, agent: Option<String>) -> Result<(String, String)> {
+	let group = group.unwrap_or_else(|| slugify(task.title.clone()).chars().take(50).collect());
+	let name = unique_session_name(cfg, &format!("{group}-plan"))?;
+	let parent = task.path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+	let prompt = pipeline::planner_prompt(&task.path, &config::expand_path(&cfg.general.tasks_dir), &parent);
+	handle_new(
+		cfg,
+		name.clone(),
+		agent.unwrap_or_else(|| cfg.general.default_agent.clone()),
+		std::env::current_dir()?.to_string_lossy().into_owned(),
+		Some(prompt),
+		Some(task.path.to_string_lossy().into_owned()),
+		false,
+		None,
+		None, // base
+		false, // announce
+	)?;
+	let session = format!("{SWARM_PREFIX}{name}");
+	fs::write(session_group_path(&session)?, &group)?;
+	fs::write(session_fanout_path(&session)?, task.path.to_string_lossy().as_bytes())?;
+	Ok((name, group))
+}
+
+/// Open subtasks a finished fan-out planner wrote (`parent:` its task), minus
+/// any that already have an agent
+fn fanout_subtasks(cfg: &Config, planner: &AgentSession, sessions: &[AgentSession]) -> Option<Vec<TaskEntry>> {
+	let parent = fs::read_to_string(session_fanout_path(&planner.session_name).ok()?).ok()?;
+	let parent = Path::new(parent.trim()).file_stem()?.to_string_lossy().into_owned();
+	Some(
+		load_tasks(cfg)
+			.into_iter()
+			.filter(|t| parse_frontmatter(&t.path, "parent").as_deref() == Some(parent.as_str()))
+			.filter(|t| find_sessions_for_task(sessions, &t.path).is_empty())
+			.collect(),
+	)
+}
+
+/// Start a fan-out worker for `task` in `group`, in the planner's directory
+fn start_subtask(cfg: &Config, task: &TaskEntry, group: &str, dir: Option<&Path>) -> Result<String> {
+	let name = start_from_task_inner(cfg, task, false, None, dir)?;
+	fs::write(session_group_path(&format!("{SWARM_PREFIX}{name}"))?, group)?;
+	Ok(name)
+}
+
+fn unique_session_name(cfg: &Config, base: &str) -> Result<String> {
 	let mut name = base.to_string();
 	let mut counter = 1;
 	let existing = list_sessions()?;
-	while existing
-		.iter()
-		.any(|s| s.trim_start_matches(SWARM_PREFIX) == name)
-	{
+	while session_name_taken(cfg, &existing, &name) {
 		counter += 1;
 		name = format!("{base}-{counter}");
 	}
 	Ok(name)
 }
 
+/// Worktree, base branch and checks for landing `session` (L)
+fn land_plan(cfg: &Config, session: &AgentSession) -> Result<(PathBuf, String, Vec<String>)> {
+	let Some(dir) = session.worktree_path.clone() else {
+		return Err(anyhow::anyhow!("Land works on worktree sessions - {} has no worktree", session.name));
+	};
+	if matches!(session.status, AgentStatus::Running | AgentStatus::NeedsInput) {
+		return Err(anyhow::anyhow!("{} is still working - land it once it's done", session.name));
+	}
+	let main_repo = main_repo_dir(&dir).unwrap_or_else(|| dir.clone());
+	// Not the worktree's copy: the agent could have put anything in that
+	let project = project::load_committed(&main_repo)?;
+	if let Some(project) = &project {
+		confirm_project_trust(project, &main_repo, false)?;
+	}
+	let base = worktree_base(cfg, project.as_ref(), &main_repo, None);
+	let checks = cfg
+		.land
+		.checks
+		.iter()
+		.chain(project.iter().flat_map(|p| &p.land.checks))
+		.cloned()
+		.collect();
+	Ok((dir, base, checks))
+}
+
+/// What new worktrees branch from: --base, else [worktrees] base_branch (repo's
+/// .swarm.toml, then config), else origin's default branch via `git symbolic-ref`
+fn worktree_base(cfg: &Config, project: Option<&project::Project>, repo: &Path, requested: Option<&str>) -> String {
+	let configured = requested
+		.map(str::to_string)
+		.or_else(|| project.map(|p| p.worktrees.base_branch.clone()))
+		.filter(|b| !b.trim().is_empty())
+		.or_else(|| Some(cfg.worktrees.base_branch.clone()).filter(|b| !b.trim().is_empty()));
+	match configured.as_deref().map(str::trim) {
+		Some("current") => git_output(repo, &["branch", "--show-current"]).unwrap_or_else(|| "HEAD".to_string()),
+		Some(branch) => {
+			let remote = format!("origin/{branch}");
+			if git_output(repo, &["rev-parse", "--verify", "--quiet", &remote]).is_some() {
+				remote
+			} else {
+				branch.to_string()
+			}
+		}
+		None => git_output(repo, &["symbolic-ref", "--short", "refs/remotes/origin/HEAD"])
+			.or_else(|| {
+				git_output(repo, &["rev-parse", "--verify", "--quiet", "origin/main"]).map(|_| "origin/main".to_string())
+			})
+			.unwrap_or_else(|| "HEAD".to_string()),
+	}
+}
+
+/// Create a git worktree for a new branch off the latest `base` (see worktree_base).
+/// If the path or branch is already there, `on_conflict` picks the recovery.
+/// Returns the worktree path and the branch it's on.
+fn create_worktree(repo: &Path, name: &str, branch: &str, base: &str, on_conflict: &str) -> Result<(PathBuf, String)> {
+	let root = dirs::home_dir()
+		.ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
+		.join("worktrees");
+	fs::create_dir_all(&root)?;
+	let path = root.join(name);
+	let branch_exists = |b: &str| git_output(repo, &["rev-parse", "--verify", "--quiet", &format!("refs/heads/{b}")]).is_some();
+	let path_taken = path.exists();
+	let branch_taken = branch_exists(branch);
+	if path_taken || branch_taken {
+		let choice = match on_conflict {
+			"ask" => ask_worktree_conflict(&path, branch, path_taken, branch_taken)?,
+			other => other.to_string(),
+		};
+		match choice.as_str() {
+			"reuse" if path_taken => {
+				if git_output(&path, &["rev-parse", "--is-inside-work-tree"]).is_none() {
+					return Err(anyhow::anyhow!("{} exists but is not a git worktree", path.display()));
+				}
+				let current = git_output(&path, &["branch", "--show-current"]).unwrap_or_else(|| branch.to_string());
+				return Ok((path, current));
+			}
+			"checkout" if branch_taken && !path_taken => {
+				add_worktree(repo, &path, &[branch])?;
+				return Ok((path, branch.to_string()));
+			}
+			"suffix" => {}
+			_ => {
+				let what = match (path_taken, branch_taken) {
+					(true, true) => format!("{} and branch {branch} already exist", path.display()),
+					(true, false) => format!("{} already exists", path.display()),
+					_ => format!("branch {branch} already exists"),
+				};
+				return Err(anyhow::anyhow!(
+					"{what} - rerun with --on-conflict {}",
+					if path_taken { "reuse or suffix" } else { "checkout or suffix" }
+				));
+			}
+		}
+	}
+	if let Some(remote_branch) = base.strip_prefix("origin/") {
+		let _ = git_output(repo, &["fetch", "origin", remote_branch]);
+	}
+	if git_output(repo, &["rev-parse", "--verify", "--quiet", base]).is_none() {
+		return Err(anyhow::anyhow!("base branch {base} not found in {}", repo.display()));
+	}
+	let (path, branch) = if path_taken || branch_taken {
+		(2..)
+			.map(|n| (root.join(format!("{name}-{n}")), format!("{branch}-{n}")))
+			.find(|(p, b)| !p.exists() && !branch_exists(b))
+			.unwrap_or_default()
+	} else {
+		(path, branch.to_string())
+	};
+	add_worktree(repo, &path, &["-b", &branch, base])?;
+	Ok((path, branch))
+}
+
+fn add_worktree(repo: &Path, path: &Path, args: &[&str]) -> Result<()> {
+	let output = Command::new("git")
+		.arg("-C")
+		.arg(repo)
+		.args(["worktree", "add"])
+		.arg(path)
+		.args(args)
+		.output()
+		.context("failed to run git worktree add")?;
+	if !output.status.success() {
+		return Err(anyhow::anyhow!(
+			"git worktree add failed: {}",
+			String::from_utf8_lossy(&output.stderr).trim()
+		));
+	}
+	Ok(())
+}
+
+/// Ask in the terminal how to recover from an existing worktree path/branch
+fn ask_worktree_conflict(path: &Path, branch: &str, path_taken: bool, branch_taken: bool) -> Result<String> {
+	use std::io::{IsTerminal, Write};
+	if !std::io::stdin().is_terminal() {
+		return Ok("abort".to_string());
+	}
+	if path_taken {
+		println!("Worktree {} already exists", path.display());
+	}
+	if branch_taken {
+		println!("Branch {branch} already exists");
+	}
+	let mut options = Vec::new();
+	if path_taken {
+		options.push(("r", "reuse", "reuse the existing worktree"));
+	}
+	if branch_taken && !path_taken {
+		options.push(("c", "checkout", "check out the existing branch in a new worktree"));
+	}
+	options.push(("s", "suffix", "create a fresh one with a numeric suffix"));
+	options.push(("a", "abort", "abort"));
+	for (key, _, label) in &options {
+		println!("  [{key}] {label}");
+	}
+	print!("Choice: ");
+	std::io::stdout().flush()?;
+	let mut answer = String::new();
+	std::io::stdin().read_line(&mut answer)?;
+	let answer = answer.trim().to_lowercase();
+	Ok(options
+		.iter()
+		.find(|(key, choice, _)| answer == *key || answer == *choice)
+		.map_or("abort", |(_, choice, _)| choice)
+		.to_string())
+}
+
+/// Copy/symlink untracked files (globs relative to the main checkout) into a
+/// worktree, skipping anything the worktree already has
+fn seed_worktree(main_repo: &Path, worktree: &Path, copy: &[&String], symlink: &[&String]) -> Result<()> {
+	for (patterns, link) in [(copy, false), (symlink, true)] {
+		for pattern in patterns {
+			let full = main_repo.join(pattern);
+			let Ok(paths) = glob::glob(&full.to_string_lossy()) else {
+				return Err(anyhow::anyhow!("invalid [worktrees] pattern: {pattern}"));
+			};
+			for src in paths.flatten() {
+				let Ok(rel) = src.strip_prefix(main_repo) else {
+					continue;
+				};
+				let dst = worktree.join(rel);
+				if dst.symlink_metadata().is_ok() {
+					continue;
+				}
+				if let Some(parent) = dst.parent() {
+					fs::create_dir_all(parent)?;
+				}
+				if link {
+					std::os::unix::fs::symlink(&src, &dst)
+						.with_context(|| format!("failed to link {} into the worktree", rel.display()))?;
+				} else {
+					copy_recursive(&src, &dst)
+						.with_context(|| format!("failed to copy {} into the worktree", rel.display()))?;
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
+fn copy_recursive(src: &Path, dst: &Path) -> Result<()> {
+	if src.is_dir() {
+		fs::create_dir_all(dst)?;
+		for entry in fs::read_dir(src)? {
+			let entry = entry?;
+			copy_recursive(&entry.path(), &dst.join(entry.file_name()))?;
+		}
+	} else {
+		fs::copy(src, dst)?;
+	}
+	Ok(())
+}
+
+/// Start `count` agents on the same work, each in its own worktree, grouped as variants
+#[allow(clippy::too_many_arguments)]
+fn launch_variants(
+	cfg: &Config,
+	base: &str,
+	agent: &str,
+	repo: &str,
+	prompt: Option<String>,
+	task: Option<String>,
+	count: usize,
+	auto_accept: bool,
+	model: Option<String>,
+	base_branch: Option<&str>,
+	on_conflict: Option<&str>,
+) -> Result<Vec<String>> {
+	let on_conflict = on_conflict.unwrap_or(&cfg.worktrees.on_conflict);
+	let repo_dir = resolve_repo_path(repo)?;
+	let project = project::load(&repo_dir)?;
+	let base_branch = worktree_base(cfg, project.as_ref(), &repo_dir, base_branch);
+	let group = slugify(base.trim_start_matches(SWARM_PREFIX));
+	let base_prompt = prompt.unwrap_or_else(|| match &task {
+		Some(path) => format!(
+			"Starting task. Read {} for context (include any Process Log). Summarize the task file before acting.",
+			path
+		),
+		None => format!("Work on: {}", base),
+	});
+	let mut names = Vec::new();
+	for index in 1..=count {
+		let name = unique_session_name(cfg, &format!("{group}-v{index}"))?;
+		let branch = format!("{}{}", cfg.general.branch_prefix, name);
+		let (worktree, branch) = create_worktree(&repo_dir, &name, &branch, &base_branch, on_conflict)?;
+		let prompt = format!(
+			"{}\n\nYou are variant {} of {} working on this independently in an isolated git worktree ({}, branch {}). The worktree already exists - do not create another one. Commit your work on this branch so the variants can be compared.",
+			base_prompt,
+			index,
+			count,
+			worktree.display(),
+			branch
+		);
+		handle_new(
+			cfg,
+			name.clone(),
+			agent.to_string(),
+			worktree.to_string_lossy().into_owned(),
+			Some(prompt),
+			task.clone(),
+			auto_accept,
+			model.clone(),
+			Some(&base_branch),
+			false, // announce
+		)?;
+		let session = format!("{SWARM_PREFIX}{name}");
+		fs::write(session_worktree_path(&session)?, worktree.to_string_lossy().as_bytes())?;
+		fs::write(session_variant_path(&session)?, format!("{} {} {}", group, index, count))?;
+		names.push(name);
+	}
+	Ok(names)
+}
+
+/// A name is taken by a live session, or by a log / session-store leftover
+/// that a new session would otherwise inherit
+fn session_name_taken(cfg: &Config, existing: &[String], name: &str) -> bool {
+	let session = format!("{SWARM_PREFIX}{name}");
+	existing.iter().any(|s| s == &session)
+		|| Path::new(&cfg.general.logs_dir)
+			.join(format!("{session}.log"))
+			.exists()
+		|| session_store_dir()
+			.map(|d| d.join(&session).exists())
+			.unwrap_or(false)
+}
+
+/// How long the auto_name call to claude -p may take before the prompt's first words are used
+const AUTO_NAME_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Name for an agent started with only a prompt: a short slug from claude -p
+/// with general.auto_name on, else the prompt's first words. "-2", "-3"… when taken.
+fn auto_session_name(cfg: &Config, prompt: &str) -> String {
+	let base = cfg
+		.general
+		.auto_name
+		.then(|| model_slug(prompt))
+		.flatten()
+		.unwrap_or_else(|| short_slug(&prompt.split_whitespace().take(5).collect::<Vec<_>>().join(" ")));
+	let base = if base.is_empty() {
+		format!("agent-{}", Local::now().format("%H%M%S"))
+	} else {
+		base
+	};
+	let existing = list_sessions().unwrap_or_default();
+	std::iter::once(base.clone())
+		.chain((2..).map(|n| format!("{base}-{n}")))
+		.find(|name| !session_name_taken(cfg, &existing, name))
+		.unwrap_or(base)
+}
+
+/// Slug of at most 40 characters, not ending in a dash
+fn short_slug(text: &str) -> String {
+	let slug: String = slugify(text).chars().take(40).collect();
+	slug.trim_end_matches('-').to_string()
+}
+
+/// Ask haiku for a 2-4 word name. None when claude is missing, fails or is slow.
+fn model_slug(prompt: &str) -> Option<String> {
+	let ask = format!(
+		"Reply with only a 2-4 word lowercase kebab-case name for this coding task, nothing else:\n\n{}",
+		prompt.chars().take(2000).collect::<String>()
+	);
+	// Run from the temp dir so the throwaway transcript isn't taken for the agent's
+	let mut child = Command::new("claude")
+		.args(["-p", "--model", "haiku", &ask])
+		.current_dir(std::env::temp_dir())
+		.stdin(std::process::Stdio::null())
+		.stdout(std::process::Stdio::piped())
+		.stderr(std::process::Stdio::null())
+		.spawn()
+		.ok()?;
+	let started = Instant::now();
+	loop {
+		match child.try_wait().ok()? {
+			Some(status) if status.success() => break,
+			Some(_) => return None,
+			None if started.elapsed() >= AUTO_NAME_TIMEOUT => {
+				let _ = child.kill();
+				let _ = child.wait();
+				return None;
+			}
+			None => std::thread::sleep(Duration::from_millis(100)),
+		}
+	}
+	let mut out = String::new();
+	std::io::Read::read_to_string(&mut child.stdout.take()?, &mut out).ok()?;
+	let slug = short_slug(out.lines().find(|l| !l.trim().is_empty())?);
+	(!slug.is_empty()).then_some(slug)
+}
+
+/// Rename a session's tmux session, log file and session-store directory together,
+/// rolling back earlier steps if a later one fails. Returns the new short name.
+fn rename_session(cfg: &Config, old: &str, new: &str) -> Result<String> {
+	let old_session = format!("{SWARM_PREFIX}{}", old.trim_start_matches(SWARM_PREFIX));
+	let new_name = slugify(new.trim_start_matches(SWARM_PREFIX));
+	if new_name.is_empty() {
+		return Err(anyhow::anyhow!("new name is empty"));
+	}
+	let new_session = format!("{SWARM_PREFIX}{new_name}");
+	let existing = list_sessions()?;
+	if !existing.contains(&old_session) {
+		return Err(anyhow::anyhow!("no session named {}", old));
+	}
+	if session_name_taken(cfg, &existing, &new_name) {
+		return Err(anyhow::anyhow!("{} is already in use", new_name));
+	}
+
+	let logs_dir = Path::new(&cfg.general.logs_dir);
+	let old_log = logs_dir.join(format!("{old_session}.log"));
+	let new_log = logs_dir.join(format!("{new_session}.log"));
+	let store = session_store_dir()?;
+	let old_store = store.join(&old_session);
+	let new_store = store.join(&new_session);
+
+	tmux::rename_session(&old_session, &new_session)?;
+	if old_log.exists() {
+		if let Err(e) = fs::rename(&old_log, &new_log) {
+			let _ = tmux::rename_session(&new_session, &old_session);
+			return Err(e).context("failed to rename log file");
+		}
+	}
+	if old_store.exists() {
+		if let Err(e) = fs::rename(&old_store, &new_store) {
+			let _ = fs::rename(&new_log, &old_log);
+			let _ = tmux::rename_session(&new_session, &old_session);
+			return Err(e).context("failed to rename session store");
+		}
+	}
+	// Point the output pipe at the renamed log
+	let _ = ensure_pipe(&new_session, &new_log);
+	Ok(new_name)
+}
+
 #[allow(dead_code)] // May be useful for quick untracked agents later
 fn quick_new(cfg: &Config, task: Option<String>) -> Result<String> {
 	let base = format!("agent-{}", chrono::Local::now().format("%H%M%S"));
@@ -2529,6 +9451,8 @@ fn quick_new(cfg: &Config, task: Option<String>) -> Result<String> {
 		None,
 		task,
 		false, // auto_accept
+		None, // model
+		None, // base
 		false, // announce
 	)?;
 	Ok(base)
@@ -2540,88 +9464,302 @@ fn create_task_and_start_agent(
 	description: &str,
 	notify: Option<&str>,
 	due_input: Option<&str>,
+	model: Option<&str>,
+	on_existing: OnExisting,
 ) -> Result<String> {
-	// Slugify the description for filename
-	let slug = slug::slugify(description);
-	let slug = if slug.len() > 50 {
-		slug[..50].to_string()
+	let due = parse_due_input(due_input);
+	let title = task_title(description);
+	// The agent starts here, so the task is for this repo
+	let repo = std::env::current_dir()?;
+	let task_path = write_task_file(cfg, title, description, notify, due, DEFAULT_TASK_TAGS, on_existing, Some(&repo))?;
+
+	// Create agent with this task
+	let task_entry = TaskEntry {
+		title: title.to_string(),
+		path: task_path.clone(),
+		due: Some(due),
+		status: Some("todo".to_string()),
+		claimed_by: None,
+		tags: Vec::new(),
+		modified: None,
+	};
+
+	start_from_task_inner(cfg, &task_entry, false, model, Some(&repo))
+}
+
+/// A multi-line description: first line is the title, all of it is the body
+fn task_title(description: &str) -> &str {
+	description.lines().find(|l| !l.trim().is_empty()).unwrap_or(description).trim()
+}
+
+/// Parse a user-entered due date (MM-DD or YYYY-MM-DD, optionally followed by
+/// HH:MM; a time alone means today), defaulting to tomorrow
+fn parse_due_input(due_input: Option<&str>) -> Due {
+	let input = due_input.map(str::trim).unwrap_or_default();
+	let (date_part, time_part) = match input.rsplit_once(' ') {
+		Some((date, time)) => (date.trim(), Some(time)),
+		None if input.contains(':') => ("", Some(input)),
+		None => (input, None),
+	};
+	let time = time_part.and_then(|t| chrono::NaiveTime::parse_from_str(t.trim(), "%H:%M").ok());
+	let today = Local::now().date_naive();
+	let date = if date_part.is_empty() && time.is_some() {
+		today
 	} else {
-		slug
+		parse_due_date(date_part)
 	};
+	Due { date, time }
+}
 
-	// Calculate due date
+/// MM-DD or YYYY-MM-DD; anything else is tomorrow
+fn parse_due_date(input: &str) -> NaiveDate {
 	let today = Local::now().date_naive();
-	let due_date = if let Some(input) = due_input {
-		// Parse MM-DD format
-		let parts: Vec<&str> = input.split('-').collect();
-		if parts.len() == 2 {
-			if let (Ok(month), Ok(day)) = (parts[0].parse::<u32>(), parts[1].parse::<u32>()) {
-				// Use current year, bump to next year if date has passed
-				let mut year = today.year();
-				if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
-					if date < today {
-						year += 1;
-					}
-					NaiveDate::from_ymd_opt(year, month, day).unwrap_or(today + chrono::Duration::days(1))
-				} else {
-					today + chrono::Duration::days(1)
+	let tomorrow = today + chrono::Duration::days(1);
+	if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+		return date;
+	}
+	// Parse MM-DD format
+	let parts: Vec<&str> = input.split('-').collect();
+	if parts.len() == 2 {
+		if let (Ok(month), Ok(day)) = (parts[0].parse::<u32>(), parts[1].parse::<u32>()) {
+			// Use current year, bump to next year if date has passed
+			let mut year = today.year();
+			if let Some(date) = NaiveDate::from_ymd_opt(year, month, day) {
+				if date < today {
+					year += 1;
 				}
-			} else {
-				today + chrono::Duration::days(1)
+				return NaiveDate::from_ymd_opt(year, month, day).unwrap_or(tomorrow);
 			}
-		} else {
-			today + chrono::Duration::days(1)
 		}
-	} else {
-		today + chrono::Duration::days(1)
-	};
+	}
+	tomorrow
+}
 
-	// Build task file content
-	let notify_section = if let Some(who) = notify {
-		format!("- {}", who)
+/// Path a new task file would be written to for this description
+fn task_file_path(cfg: &Config, description: &str) -> PathBuf {
+	// Slugify the description for filename
+	let slug = slug::slugify(description);
+	let slug = if slug.len() > 50 {
+		slug[..50].to_string()
 	} else {
-		"- (fill in who to notify)".to_string()
+		slug
 	};
+	PathBuf::from(&cfg.general.tasks_dir).join(format!("{}.md", slug))
+}
+
+/// First free "<slug>-N.md" next to `path`
+fn unique_task_path(path: &Path) -> PathBuf {
+	let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+	(2..)
+		.map(|n| path.with_file_name(format!("{stem}-{n}.md")))
+		.find(|p| !p.exists())
+		.unwrap_or_else(|| path.to_path_buf())
+}
+
+/// {{tags}} for tasks created without any
+const DEFAULT_TASK_TAGS: &str = "work";
 
-	let content = format!(
-		r#"---
+/// Built-in task file skeleton; see task_template for overriding it
+const DEFAULT_TASK_TEMPLATE: &str = r#"---
 status: todo
-due: {}
-tags: [work]
-summary: {}
+due: {{due}}
+tags: [{{tags}}]
+summary: {{title}}
 ---
 
-# {}
+# {{title}}
 
-{}
+{{description}}
 
 ## When done
-{}
+- {{notify}}
 
 ## Process Log
 (Claude logs progress here)
-"#,
-		due_date.format("%Y-%m-%d"),
-		description,
-		description,
-		description,
-		notify_section,
-	);
+"#;
+
+/// Skeleton for new task files: the `.swarm.toml` task_template of `repo`, the
+/// repo the task is for, else ~/.swarm/templates/task.md, else the built-in one
+fn task_template(repo: Option<&Path>) -> String {
+	repo.and_then(repo_task_template)
+		.or_else(|| config::base_dir().ok().and_then(|d| fs::read_to_string(d.join("templates").join("task.md")).ok()))
+		.unwrap_or_else(|| DEFAULT_TASK_TEMPLATE.to_string())
+}
 
-	// Write task file
-	let tasks_dir = PathBuf::from(&cfg.general.tasks_dir);
-	let task_path = tasks_dir.join(format!("{}.md", slug));
-	fs::write(&task_path, &content)?;
+/// `repo`'s task_template, once the repo is trusted (`swarm trust`) and only
+/// from a file inside it
+fn repo_task_template(repo: &Path) -> Option<String> {
+	let canonical = repo.canonicalize().ok()?;
+	let main_repo = main_repo_dir(&canonical);
+	// A worktree without its own .swarm.toml uses the main repo's
+	let (dir, project) = [Some(canonical.clone()), main_repo.clone()]
+		.into_iter()
+		.flatten()
+		.find_map(|dir| Some((dir.clone(), project::load(&dir).ok()??)))?;
+	let path = project.task_template.as_deref()?;
+	let trust_key = main_repo.unwrap_or(canonical);
+	if !project::template_trusted(&project, &trust_key) {
+		tracing::warn!(repo = %trust_key.display(), "task_template in an untrusted .swarm.toml, using the default");
+		return None;
+	}
+	let file = dir.join(path).canonicalize().ok().filter(|f| f.starts_with(&dir))?;
+	fs::read_to_string(file).ok()
+}
 
-	// Create agent with this task
-	let task_entry = TaskEntry {
-		title: description.to_string(),
-		path: task_path.clone(),
-		due: Some(due_date),
-		status: Some("todo".to_string()),
+/// Fill `{{name}}` placeholders; unknown ones are left as they are
+fn render_task_template(template: &str, fields: &[(&str, &str)]) -> String {
+	fields
+		.iter()
+		.fold(template.to_string(), |out, (name, value)| out.replace(&format!("{{{{{name}}}}}"), value))
+}
+
+/// What write_task_file does when the title's file already exists
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OnExisting {
+	Fail,
+	Suffix,
+	Overwrite,
+}
+
+/// Write a task file with frontmatter and the standard sections, from `repo`'s template
+#[allow(clippy::too_many_arguments)]
+fn write_task_file(
+	cfg: &Config,
+	description: &str,
+	body: &str,
+	notify: Option<&str>,
+	due: Due,
+	tags: &str,
+	on_existing: OnExisting,
+	repo: Option<&Path>,
+) -> Result<PathBuf> {
+	let today = Local::now().format("%Y-%m-%d").to_string();
+	let notify = notify.unwrap_or("(fill in who to notify)");
+	let fields = [
+		("title", description),
+		("description", body),
+		("due", &due.to_string()),
+		("notify", notify),
+		("tags", tags),
+		("date", &today),
+	];
+	let template = task_template(repo);
+	// In an Obsidian vault, frontmatter values use its property formats so they
+	// show up as properties instead of broken YAML
+	let content = match template.strip_prefix("---\n").and_then(|t| t.split_once("\n---")) {
+		Some((front, rest)) if obsidian::contains(cfg, Path::new(&cfg.general.tasks_dir)) => {
+			let tags = tags.split(',').map(obsidian::tag).filter(|t| !t.is_empty()).collect::<Vec<_>>().join(", ");
+			let front_fields = [
+				("title", obsidian::property(description)),
+				("description", obsidian::property(body)),
+				("due", obsidian::date_property(due)),
+				("notify", obsidian::property(notify)),
+				("tags", tags),
+				("date", today.clone()),
+			];
+			let front_fields = front_fields.iter().map(|(k, v)| (*k, v.as_str())).collect::<Vec<_>>();
+			format!(
+				"---\n{}\n---{}",
+				render_task_template(front, &front_fields),
+				render_task_template(rest, &fields)
+			)
+		}
+		_ => render_task_template(&template, &fields),
 	};
 
-	start_from_task(cfg, &task_entry)
+	let mut task_path = task_file_path(cfg, description);
+	if task_path.exists() {
+		match on_existing {
+			OnExisting::Fail => {
+				return Err(anyhow::anyhow!(
+					"task file {} already exists (--force overwrites it, --suffix keeps both)",
+					task_path.display()
+				))
+			}
+			OnExisting::Suffix => task_path = unique_task_path(&task_path),
+			OnExisting::Overwrite => {}
+		}
+	}
+	fs::write(&task_path, &content)?;
+	Ok(task_path)
+}
+
+/// Import tasks from a markdown/CSV file, previewing the split before writing
+fn import_tasks(cfg: &Config, file: &str, yes: bool, dry_run: bool) -> Result<()> {
+	let path = PathBuf::from(config::expand_path(file));
+	let imported = import::parse_file(&path)
+		.with_context(|| format!("failed to read {}", path.display()))?;
+	if imported.is_empty() {
+		println!("No tasks found in {}", path.display());
+		return Ok(());
+	}
+
+	// Files already in tasks_dir are skipped; titles that slugify to the same file
+	// within this import get -2, -3, as write_task_file's Suffix would number them
+	let mut planned: HashSet<PathBuf> = HashSet::new();
+	let targets: Vec<Option<PathBuf>> = imported
+		.iter()
+		.map(|task| {
+			let path = task_file_path(cfg, &task.title);
+			if path.exists() {
+				return None;
+			}
+			let stem = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+			let target = std::iter::once(path.clone())
+				.chain((2..).map(|n| path.with_file_name(format!("{stem}-{n}.md"))))
+				.find(|p| !planned.contains(p) && !p.exists())
+				.unwrap_or(path);
+			planned.insert(target.clone());
+			Some(target)
+		})
+		.collect();
+
+	println!("Found {} tasks in {}:", imported.len(), path.display());
+	for (idx, (task, target)) in imported.iter().zip(&targets).enumerate() {
+		let due = parse_due_input(task.due.as_deref());
+		let path = task_file_path(cfg, &task.title);
+		let (shown, note) = match target {
+			None => (&path, "  (exists, will skip)"),
+			Some(target) if *target != path => (target, "  (same file name as a task above, numbered)"),
+			Some(target) => (target, ""),
+		};
+		println!("  {:>2}. {} (due {})", idx + 1, task.title, due);
+		println!("      → {}{}", shown.display(), note);
+	}
+
+	if dry_run {
+		return Ok(());
+	}
+	if !yes {
+		print!("Create these task files? [y/N] ");
+		use std::io::Write;
+		std::io::stdout().flush()?;
+		let mut answer = String::new();
+		std::io::stdin().read_line(&mut answer)?;
+		if !answer.trim().eq_ignore_ascii_case("y") {
+			println!("Aborted, nothing written");
+			return Ok(());
+		}
+	}
+
+	// Tasks imported from a shell are for the repo it's in, as with `swarm task add`
+	let cwd = std::env::current_dir()?;
+	let mut created = 0;
+	for (task, target) in imported.iter().zip(&targets) {
+		if target.is_none() {
+			continue;
+		}
+		let body = if task.body.trim().is_empty() {
+			task.title.clone()
+		} else {
+			task.body.trim().to_string()
+		};
+		let due = parse_due_input(task.due.as_deref());
+		write_task_file(cfg, &task.title, &body, task.notify.as_deref(), due, DEFAULT_TASK_TAGS, OnExisting::Suffix, Some(&cwd))?;
+		created += 1;
+	}
+	println!("✓ Created {} task files in {}", created, cfg.general.tasks_dir);
+	Ok(())
 }
 
 #[allow(dead_code)] // Kept for potential Claude-assisted task creation
@@ -2636,6 +9774,8 @@ fn quick_new_with_prompt(cfg: &Config, prompt: &str) -> Result<String> {
 		Some(prompt.to_string()),
 		None,
 		false, // auto_accept
+		None, // model
+		None, // base
 		false, // announce
 	)?;
 	Ok(base)
@@ -2665,3 +9805,76 @@ fn snapshot_session(session: &AgentSession) -> Result<String> {
 	fs::write(&path, output.stdout)?;
 	Ok(path.to_string_lossy().to_string())
 }
+
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::cmp::Ordering;
+
+	fn due_in(days: i64) -> Option<Due> {
+		Some(Due {
+			date: Local::now().date_naive() + chrono::Duration::days(days),
+			time: None,
+		})
+	}
+
+	#[test]
+	fn parse_filter_splits_terms_from_text() {
+		let filter = parse_filter("status:Needs-Input age>10m Fix  LOGIN due<3d tag:#backend no:agent");
+		assert_eq!(filter.text, "fix login");
+		assert_eq!(
+			filter.terms,
+			[
+				FilterTerm::Status("needs_input".to_string()),
+				FilterTerm::Age(Ordering::Greater, Duration::from_secs(600)),
+				FilterTerm::DueWithin(Duration::from_secs(3 * 86_400)),
+				FilterTerm::Tag("backend".to_string()),
+				FilterTerm::HasAgent(false),
+			]
+		);
+	}
+
+	#[test]
+	fn parse_filter_keeps_unknown_and_invalid_terms_as_text() {
+		let filter = parse_filter("age>soon due:someday status:");
+		assert_eq!(filter.text, "age>soon due:someday");
+		assert_eq!(filter.terms, [FilterTerm::Status(String::new())]);
+	}
+
+	#[test]
+	fn status_prefix_matches() {
+		let Filter { terms, .. } = parse_filter("status:needs");
+		let FilterTerm::Status(want) = &terms[0] else {
+			panic!("expected a status term");
+		};
+		assert!(status_matches(AgentStatus::NeedsInput, want));
+		assert!(!status_matches(AgentStatus::Running, want));
+		assert!(status_matches(AgentStatus::NeedsInput, "blocked"));
+	}
+
+	#[test]
+	fn due_within_includes_overdue() {
+		let within = parse_filter("due<3d").terms.remove(0);
+		assert!(due_matches(&within, due_in(2)));
+		assert!(due_matches(&within, due_in(-5)));
+		assert!(!due_matches(&within, due_in(4)));
+		assert!(!due_matches(&within, None));
+	}
+
+	#[test]
+	fn due_keywords() {
+		let term = |f: &str| parse_filter(f).terms.remove(0);
+		assert!(due_matches(&term("due:today"), due_in(0)));
+		assert!(!due_matches(&term("due:today"), due_in(1)));
+		assert!(due_matches(&term("due:overdue"), due_in(-1)));
+		assert!(!due_matches(&term("due:overdue"), due_in(0)));
+		assert!(due_matches(&term("due:none"), None));
+		assert!(!due_matches(&term("due:none"), due_in(0)));
+		assert!(due_matches(&term("due:week"), due_in(0)));
+	}
+}
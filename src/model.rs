@@ -1,17 +1,57 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
-#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum AgentStatus {
 	NeedsInput,
 	Running,
 	Idle,
 	Done,
+	Error,
 	Unknown,
 }
 
+impl AgentStatus {
+	/// Lower rank = needs the user's attention sooner
+	pub fn attention_rank(self) -> u8 {
+		match self {
+			AgentStatus::NeedsInput => 0,
+			AgentStatus::Error => 1,
+			AgentStatus::Running => 2,
+			AgentStatus::Idle => 3,
+			AgentStatus::Unknown => 4,
+			AgentStatus::Done => 5,
+		}
+	}
+
+	/// Combined status of several sessions working on the same task
+	pub fn aggregate(statuses: impl IntoIterator<Item = AgentStatus>) -> Option<AgentStatus> {
+		statuses.into_iter().min_by_key(|s| s.attention_rank())
+	}
+
+	pub fn label(self) -> &'static str {
+		match self {
+			AgentStatus::NeedsInput => "needs input",
+			AgentStatus::Running => "running",
+			AgentStatus::Idle => "idle",
+			AgentStatus::Done => "done",
+			AgentStatus::Error => "error",
+			AgentStatus::Unknown => "unknown",
+		}
+	}
+}
+
+/// Per-session runtime/spend limits from `swarm new --max-minutes/--max-cost`,
+/// stored in the session directory; unset fields fall back to [limits]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionLimits {
+	pub max_minutes: Option<u64>,
+	pub max_cost: Option<f64>,
+	pub action: Option<String>, // warn, pause, kill
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct AgentSession {
 	pub name: String,
@@ -24,20 +64,129 @@ pub struct AgentSession {
 	pub task: Option<TaskInfo>,
 	pub is_yolo: bool,           // ⚠️ Started with --dangerously-skip-permissions
 	pub worktree_path: Option<PathBuf>, // Some if running in git worktree
+	pub exit_code: Option<i32>,         // Some once the agent process has exited
+	pub stage: Option<StageInfo>,       // Some if the task is a multi-stage pipeline
+	pub variant: Option<VariantInfo>,   // Some if launched as one of several A/B variants
+	pub group: Option<String>,          // Named group (swarm new --group) for group-wide actions
+	pub notes: Vec<String>,             // Freeform labels/notes added with N ("don't kill this one")
+	pub pinned: bool,                   // Kept out of group kills, limit kills and orphan cleanup (P)
+	pub model: Option<String>,          // Model passed to the agent CLI, if any
+	pub activity: Option<String>,       // Latest tool action seen in output ("Running cargo test")
+	pub progress: Option<(usize, usize)>, // Done/total items of the agent's latest todo checklist
+	pub interviewing: bool,             // Started with /interview on a one-line task; ends when its status changes
+	pub deadline: Option<(Duration, Duration)>, // Soft deadline (--within, T): runtime so far and the budget
+	pub repo: Option<PathBuf>,          // Where the agent is working (the pane's directory)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VariantInfo {
+	pub group: String,
+	pub index: usize, // 1-based
+	pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StageInfo {
+	pub index: usize,
+	pub total: usize,
+	pub name: String,
+}
+
+/// Last known fleet state, cached to disk so `swarm statusline` and prompt
+/// segments can read it without touching tmux
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FleetSnapshot {
+	pub updated_at: u64, // unix seconds
+	pub sessions: Vec<(String, AgentStatus)>,
+}
+
+impl FleetSnapshot {
+	pub fn count(&self, status: AgentStatus) -> usize {
+		self.sessions.iter().filter(|(_, s)| *s == status).count()
+	}
+}
+
+/// What a teammate's swarm publishes for `swarm fleet`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamStatus {
+	pub member: String,
+	pub updated_at: u64, // unix seconds
+	pub sessions: Vec<TeamSession>,
+	#[serde(default)]
+	pub tasks: Vec<TeamTask>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamSession {
+	pub name: String,
+	pub status: AgentStatus,
+	pub task: Option<String>,
+	pub activity: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamTask {
+	pub title: String,
+	pub due: Option<String>, // YYYY-MM-DD, or YYYY-MM-DD HH:MM
+	pub status: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct TaskInfo {
 	pub path: PathBuf,
 	pub title: String,
+	pub priority: Option<String>, // `priority:` frontmatter, e.g. "P0"
+	pub tags: Vec<String>,
+	#[serde(skip)]
+	pub due: Option<Due>,
+}
+
+/// A task's `due:` - a day, or a day and time ("2025-03-14 17:00")
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Due {
+	pub date: chrono::NaiveDate,
+	pub time: Option<chrono::NaiveTime>,
+}
+
+impl Due {
+	pub fn parse(value: &str) -> Option<Due> {
+		let value = value.trim();
+		if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+			return Some(Due { date, time: None });
+		}
+		["%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S"]
+			.iter()
+			.find_map(|format| chrono::NaiveDateTime::parse_from_str(value, format).ok())
+			.map(|at| Due {
+				date: at.date(),
+				time: Some(at.time()),
+			})
+	}
+
+	/// The exact deadline, for dues with a time
+	pub fn at(&self) -> Option<chrono::NaiveDateTime> {
+		self.time.map(|t| self.date.and_time(t))
+	}
+}
+
+impl std::fmt::Display for Due {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self.time {
+			Some(time) => write!(f, "{} {}", self.date.format("%Y-%m-%d"), time.format("%H:%M")),
+			None => write!(f, "{}", self.date.format("%Y-%m-%d")),
+		}
+	}
 }
 
 #[derive(Debug, Clone)]
 pub struct TaskEntry {
 	pub title: String,
 	pub path: PathBuf,
-	pub due: Option<chrono::NaiveDate>,
+	pub due: Option<Due>,
 	pub status: Option<String>,
+	pub claimed_by: Option<String>, // Who started work on it (shared tasks_dir)
+	pub tags: Vec<String>,
+	pub modified: Option<SystemTime>, // Last edit of the file, for age> / age< in saved views
 }
 
 #[derive(Debug, Clone)]
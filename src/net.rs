@@ -0,0 +1,102 @@
+// Connectivity. With --offline (or SWARM_OFFLINE=1) swarm stays off the network
+// entirely; otherwise a quick connect, repeated at most once a minute, decides:
+// to the proxy when HTTPS_PROXY is set, else to the update server. Offline, the
+// update check, PR lookups through gh, team publishing, background task sync and
+// push notifications are skipped instead of each waiting out a 10s timeout.
+
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// What the probe connects to by default: the host the update check and gh talk to
+const PROBE_ADDR: &str = "api.github.com:443";
+
+/// Longest a probe may take, DNS included
+const PROBE_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// How long a probe's answer is trusted
+const RECHECK: Duration = Duration::from_secs(60);
+
+static FORCED: AtomicBool = AtomicBool::new(false);
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+static PROBED: Mutex<Option<Instant>> = Mutex::new(None);
+static PROBE_URL: OnceLock<String> = OnceLock::new();
+
+/// --offline / SWARM_OFFLINE: never probe, never connect
+pub fn force_offline() {
+	FORCED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_forced() -> bool {
+	FORCED.load(Ordering::Relaxed)
+}
+
+/// Probe the host of `url` (e.g. an internal releases mirror) instead of GitHub
+pub fn probe_via(url: &str) {
+	if !url.trim().is_empty() {
+		let _ = PROBE_URL.set(url.trim().to_string());
+	}
+}
+
+/// host:port to probe. Behind a proxy only the proxy has to be reachable.
+fn probe_addr() -> String {
+	let proxy = ["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+		.iter()
+		.find_map(|key| std::env::var(key).ok().filter(|v| !v.trim().is_empty()));
+	proxy
+		.as_deref()
+		.or(PROBE_URL.get().map(String::as_str))
+		.and_then(host_port)
+		.unwrap_or_else(|| PROBE_ADDR.to_string())
+}
+
+/// "https://ghe.corp/api/v3/..." → "ghe.corp:443"; "proxy:3128" → "proxy:3128"
+fn host_port(url: &str) -> Option<String> {
+	let url = if url.contains("://") { url.to_string() } else { format!("http://{url}") };
+	let url = reqwest::Url::parse(&url).ok()?;
+	Some(format!("{}:{}", url.host_str()?, url.port_or_known_default()?))
+}
+
+/// Whether to skip network calls. Probes when the last answer is stale, so this
+/// can block for up to PROBE_TIMEOUT; keep it off the UI thread.
+pub fn offline() -> bool {
+	if is_forced() {
+		return true;
+	}
+	let mut probed = PROBED.lock().unwrap_or_else(|e| e.into_inner());
+	if probed.is_none_or(|at| at.elapsed() >= RECHECK) {
+		OFFLINE.store(!reachable(), Ordering::Relaxed);
+		*probed = Some(Instant::now());
+	}
+	OFFLINE.load(Ordering::Relaxed)
+}
+
+/// The last answer without waiting, for the UI thread. A stale answer is
+/// refreshed on a background thread; until the first probe is back this says online.
+pub fn known_offline() -> bool {
+	if is_forced() {
+		return true;
+	}
+	let stale = PROBED
+		.try_lock()
+		.is_ok_and(|probed| probed.is_none_or(|at| at.elapsed() >= RECHECK));
+	if stale {
+		std::thread::spawn(offline);
+	}
+	OFFLINE.load(Ordering::Relaxed)
+}
+
+fn reachable() -> bool {
+	// A DNS lookup can hang well past any connect timeout, so both run on a thread
+	let (tx, rx) = mpsc::channel();
+	std::thread::spawn(move || {
+		let connected = probe_addr()
+			.to_socket_addrs()
+			.ok()
+			.and_then(|mut addrs| addrs.next())
+			.is_some_and(|addr| TcpStream::connect_timeout(&addr, PROBE_TIMEOUT).is_ok());
+		let _ = tx.send(connected);
+	});
+	rx.recv_timeout(PROBE_TIMEOUT).unwrap_or(false)
+}
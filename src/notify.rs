@@ -1,7 +1,78 @@
-use std::process::Command;
+use crate::config::Push;
+use anyhow::Result;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-/// Send a macOS notification via osascript
+// [notifications] command, set once at startup; replaces the built-in notifiers
+static COMMAND: OnceLock<String> = OnceLock::new();
+
+pub fn set_command(command: &str) {
+	let _ = COMMAND.set(command.to_string());
+}
+
+// Do-not-disturb lives in ~/.swarm/mute so every swarm process honours it:
+// empty = muted until unmuted, else the unix time it expires
+fn mute_path() -> Result<std::path::PathBuf> {
+	Ok(crate::config::base_dir()?.join("mute"))
+}
+
+/// Mute notifications, sounds and pushes; `duration` None = until unmuted
+pub fn mute(duration: Option<Duration>) -> Result<()> {
+	let until = duration
+		.map(|d| (SystemTime::now() + d).duration_since(UNIX_EPOCH).unwrap_or_default().as_secs().to_string())
+		.unwrap_or_default();
+	std::fs::write(mute_path()?, until)?;
+	Ok(())
+}
+
+pub fn unmute() -> Result<()> {
+	match std::fs::remove_file(mute_path()?) {
+		Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+		_ => Ok(()),
+	}
+}
+
+/// None = not muted; Some(None) = muted until unmuted; Some(Some(d)) = muted for d more
+pub fn mute_state() -> Option<Option<Duration>> {
+	let content = std::fs::read_to_string(mute_path().ok()?).ok()?;
+	let Ok(until) = content.trim().parse::<u64>() else {
+		return Some(None);
+	};
+	let left = (UNIX_EPOCH + Duration::from_secs(until)).duration_since(SystemTime::now()).ok()?;
+	Some(Some(left))
+}
+
+pub fn is_muted() -> bool {
+	mute_state().is_some()
+}
+
+/// Desktop notification: the configured command, else osascript on macOS and
+/// notify-send plus a sound theme sound elsewhere. Nothing while muted.
 pub fn notify(title: &str, message: &str, sound: Option<&str>) {
+	if is_muted() {
+		return;
+	}
+	if let Some(command) = COMMAND.get().filter(|c| !c.trim().is_empty()) {
+		let _ = Command::new("sh")
+			.args(["-c", command])
+			.env("SWARM_NOTIFY_TITLE", title)
+			.env("SWARM_NOTIFY_MESSAGE", message)
+			.env("SWARM_NOTIFY_SOUND", sound.unwrap_or_default())
+			.stdin(Stdio::null())
+			.stdout(Stdio::null())
+			.stderr(Stdio::null())
+			.spawn();
+		return;
+	}
+	if cfg!(target_os = "macos") {
+		notify_macos(title, message, sound);
+	} else {
+		notify_linux(title, message, sound);
+	}
+}
+
+fn notify_macos(title: &str, message: &str, sound: Option<&str>) {
 	let script = if let Some(sound_name) = sound {
 		format!(
 			r#"display notification "{}" with title "{}" sound name "{}""#,
@@ -20,6 +91,38 @@ pub fn notify(title: &str, message: &str, sound: Option<&str>) {
 	let _ = Command::new("osascript").arg("-e").arg(&script).output();
 }
 
+fn notify_linux(title: &str, message: &str, sound: Option<&str>) {
+	let _ = Command::new("notify-send")
+		.args(["--app-name=swarm", title, message])
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.spawn();
+	if let Some(sound) = sound.filter(|s| !s.is_empty()) {
+		play_sound_linux(sound);
+	}
+}
+
+/// Sound files play with paplay; names go to canberra-gtk-play, with the
+/// config's macOS defaults mapped to freedesktop sound theme ids
+fn play_sound_linux(sound: &str) {
+	let quiet = |cmd: &mut Command| {
+		cmd.stdout(Stdio::null()).stderr(Stdio::null()).spawn().is_ok()
+	};
+	if sound.contains('/') {
+		quiet(Command::new("paplay").arg(crate::config::expand_path(sound)));
+		return;
+	}
+	let id = match sound {
+		"Ping" => "message-new-instant",
+		"Glass" => "complete",
+		"Basso" => "dialog-error",
+		other => other,
+	};
+	if !quiet(Command::new("canberra-gtk-play").args(["-i", id])) {
+		quiet(Command::new("paplay").arg(format!("/usr/share/sounds/freedesktop/stereo/{id}.oga")));
+	}
+}
+
 fn escape_applescript(s: &str) -> String {
 	s.replace('\\', "\\\\").replace('"', "\\\"")
 }
@@ -34,8 +137,32 @@ pub fn notify_done(agent_name: &str, sound: &str) {
 	notify("swarm", &format!("{} completed", agent_name), Some(sound));
 }
 
+/// Every agent in a group has finished
+pub fn notify_group_done(group: &str, sound: &str) {
+	notify("swarm", &format!("Group {} complete - report in your daily log", group), Some(sound));
+}
+
+/// Morning digest of overdue and due-today tasks
+pub fn notify_digest(summary: &str, sound: &str) {
+	notify("swarm: today's tasks", summary, Some(sound));
+}
+
+/// A timed task is about to be due
+pub fn notify_due(message: &str, sound: &str) {
+	notify("swarm: due soon", message, Some(sound));
+}
+
+/// Notify that a snoozed task is back in the list
+pub fn notify_snooze_over(message: &str, sound: &str) {
+	notify("swarm: snooze over", message, Some(sound));
+}
+
+/// Notify that a session ran past its soft deadline
+pub fn notify_over_time(agent_name: &str, message: &str, sound: &str) {
+	notify("swarm: over time", &format!("{}: {}", agent_name, message), Some(sound));
+}
+
 /// Notify of an error
-#[allow(dead_code)]
 pub fn notify_error(agent_name: &str, message: &str, sound: &str) {
 	notify(
 		"swarm",
@@ -43,3 +170,122 @@ pub fn notify_error(agent_name: &str, message: &str, sound: &str) {
 		Some(sound),
 	);
 }
+
+/// Events that can be pushed to a phone
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PushEvent {
+	NeedsInput,
+	Done,
+	Error,
+}
+
+impl PushEvent {
+	fn key(self) -> &'static str {
+		match self {
+			PushEvent::NeedsInput => "needs_input",
+			PushEvent::Done => "done",
+			PushEvent::Error => "error",
+		}
+	}
+
+	fn ntfy_topic(self, push: &Push) -> &str {
+		match self {
+			PushEvent::NeedsInput => &push.ntfy_topic_needs_input,
+			PushEvent::Done => &push.ntfy_topic_done,
+			PushEvent::Error => &push.ntfy_topic_error,
+		}
+	}
+}
+
+/// `path` under the dashboard, keeping any query (e.g. ?token=) on the base URL
+pub fn dashboard_link(dashboard_url: &str, path: &str) -> Option<String> {
+	if dashboard_url.is_empty() {
+		return None;
+	}
+	let (base, query) = match dashboard_url.split_once('?') {
+		Some((base, query)) => (base, format!("?{query}")),
+		None => (dashboard_url, String::new()),
+	};
+	Some(format!("{}/{}{}", base.trim_end_matches('/'), path, query))
+}
+
+/// Send an event to the configured ntfy topic and/or Pushover account.
+/// Runs on a background thread; failures are dropped like desktop notifications.
+pub fn push(push: &Push, event: PushEvent, agent_name: &str, session: &str, message: &str) {
+	if is_muted() {
+		return;
+	}
+	let topic = event.ntfy_topic(push).to_string();
+	let pushover = !push.pushover_token.is_empty()
+		&& !push.pushover_user.is_empty()
+		&& push.pushover_events.iter().any(|e| e == event.key());
+	if topic.is_empty() && !pushover {
+		return;
+	}
+	let push = push.clone();
+	let title = format!("swarm: {agent_name}");
+	let message = message.to_string();
+	let link = dashboard_link(&push.dashboard_url, &format!("session/{session}"));
+	// ntfy buttons POST straight back to `swarm serve`, which answers the prompt
+	let answer_links = match event {
+		PushEvent::NeedsInput => dashboard_link(&push.dashboard_url, &format!("api/session/{session}/approve"))
+			.zip(dashboard_link(&push.dashboard_url, &format!("api/session/{session}/deny"))),
+		_ => None,
+	};
+	std::thread::spawn(move || {
+		if crate::net::offline() {
+			return;
+		}
+		let Ok(client) = reqwest::blocking::Client::builder()
+			.user_agent("swarm")
+			.timeout(Duration::from_secs(10))
+			.build()
+		else {
+			return;
+		};
+		if !topic.is_empty() {
+			let url = format!("{}/{}", push.ntfy_server.trim_end_matches('/'), topic);
+			let mut request = client
+				.post(url)
+				.header("Title", title.as_str())
+				.header("Priority", if event == PushEvent::NeedsInput { "high" } else { "default" })
+				.header(
+					"Tags",
+					match event {
+						PushEvent::NeedsInput => "raising_hand",
+						PushEvent::Done => "white_check_mark",
+						PushEvent::Error => "x",
+					},
+				)
+				.body(message.clone());
+			if let Some(link) = &link {
+				let mut actions = format!("view, Open, {link}");
+				if let Some((approve, deny)) = &answer_links {
+					actions.push_str(&format!(
+						"; http, Approve, {approve}, method=POST, clear=true; http, Deny, {deny}, method=POST, clear=true"
+					));
+				}
+				request = request.header("Click", link.as_str()).header("Actions", actions);
+			}
+			let _ = request.send();
+		}
+		if pushover {
+			let priority = if event == PushEvent::NeedsInput { "1" } else { "0" };
+			let mut form = vec![
+				("token", push.pushover_token.as_str()),
+				("user", push.pushover_user.as_str()),
+				("title", title.as_str()),
+				("message", message.as_str()),
+				("priority", priority),
+			];
+			if let Some(link) = &link {
+				form.push(("url", link.as_str()));
+				form.push(("url_title", "Open in swarm"));
+			}
+			let _ = client
+				.post("https://api.pushover.net/1/messages.json")
+				.form(&form)
+				.send();
+		}
+	});
+}
@@ -0,0 +1,118 @@
+// Obsidian vault support ([obsidian] in config). Tasks and daily logs inside the
+// vault open in Obsidian through obsidian:// links, task frontmatter written into
+// the vault uses Obsidian's property formats, and with daily_notes on, swarm's
+// daily-log entries go to (and the daily logs view reads) the vault's daily notes.
+
+use crate::config::Config;
+use crate::model::Due;
+use chrono::NaiveDate;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The configured vault folder, if any
+pub fn vault(cfg: &Config) -> Option<PathBuf> {
+	let vault = cfg.obsidian.vault.trim();
+	(!vault.is_empty()).then(|| PathBuf::from(vault))
+}
+
+/// `path` relative to the vault, if it's inside it
+fn vault_relative(vault: &Path, path: &Path) -> Option<PathBuf> {
+	let vault = vault.canonicalize().unwrap_or_else(|_| vault.to_path_buf());
+	let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+	path.strip_prefix(&vault).ok().map(Path::to_path_buf)
+}
+
+/// Whether `path` (a file or folder) is inside the vault
+pub fn contains(cfg: &Config, path: &Path) -> bool {
+	vault(cfg).is_some_and(|v| vault_relative(&v, path).is_some())
+}
+
+/// obsidian://open link for a file inside `vault`
+pub fn uri(vault: &Path, relative: &Path) -> String {
+	let name = vault.file_name().unwrap_or_default().to_string_lossy();
+	let file: Vec<String> = relative.iter().map(|p| p.to_string_lossy().into_owned()).collect();
+	format!("obsidian://open?vault={}&file={}", encode(&name), encode(&file.join("/")))
+}
+
+/// Percent-encode everything but unreserved characters
+fn encode(text: &str) -> String {
+	text.bytes()
+		.map(|b| match b {
+			b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+			_ => format!("%{b:02X}"),
+		})
+		.collect()
+}
+
+/// Open a task or daily log: vault files in Obsidian (unless open_in_obsidian is
+/// off), everything else in Cursor. Returns the app, for the status line.
+pub fn open(cfg: &Config, path: &Path) -> &'static str {
+	let link = vault(cfg)
+		.filter(|_| cfg.obsidian.open_in_obsidian)
+		.and_then(|v| Some(uri(&v, &vault_relative(&v, path)?)));
+	if let Some(link) = link {
+		let opener = if cfg!(target_os = "macos") { "open" } else { "xdg-open" };
+		if Command::new(opener).arg(&link).status().is_ok_and(|s| s.success()) {
+			return "Obsidian";
+		}
+	}
+	let _ = Command::new("cursor").arg(path).status();
+	"Cursor"
+}
+
+/// Where daily logs live: the vault's daily notes folder with daily_notes on,
+/// else general.daily_dir
+pub fn daily_dir(cfg: &Config) -> PathBuf {
+	match vault(cfg).filter(|_| cfg.obsidian.daily_notes) {
+		Some(vault) => vault.join(cfg.obsidian.daily_note_folder.trim()),
+		None => PathBuf::from(&cfg.general.daily_dir),
+	}
+}
+
+/// File name format (without .md) of daily logs; an invalid daily_note_format
+/// falls back to YYYY-MM-DD
+pub fn daily_format(cfg: &Config) -> &str {
+	let format = cfg.obsidian.daily_note_format.trim();
+	let valid = !format.is_empty()
+		&& !chrono::format::StrftimeItems::new(format).any(|item| matches!(item, chrono::format::Item::Error));
+	if cfg.obsidian.daily_notes && valid {
+		format
+	} else {
+		"%Y-%m-%d"
+	}
+}
+
+/// The daily log (or daily note) for `date`
+pub fn daily_log(cfg: &Config, date: NaiveDate) -> PathBuf {
+	daily_dir(cfg).join(format!("{}.md", date.format(daily_format(cfg))))
+}
+
+/// A frontmatter value as Obsidian's YAML reads it: quoted when it would
+/// otherwise be misread (colons, leading symbols, surrounding spaces)
+pub fn property(value: &str) -> String {
+	let plain = !value.is_empty()
+		&& value.trim() == value
+		&& !value.contains(": ")
+		&& !value.contains(" #")
+		&& !value.contains('\n')
+		&& !value.starts_with(['[', ']', '{', '}', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`', '#', '-', '?', ':', ','])
+		&& !value.ends_with(':');
+	if plain {
+		value.to_string()
+	} else {
+		format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+	}
+}
+
+/// Obsidian tags can't contain spaces or start with #: " needs review" → "needs-review"
+pub fn tag(value: &str) -> String {
+	value.trim().trim_start_matches('#').split_whitespace().collect::<Vec<_>>().join("-")
+}
+
+/// Obsidian's Date (2025-03-14) or Date & time (2025-03-14T17:00) property format
+pub fn date_property(due: Due) -> String {
+	match due.time {
+		Some(time) => format!("{}T{}", due.date.format("%Y-%m-%d"), time.format("%H:%M")),
+		None => due.date.format("%Y-%m-%d").to_string(),
+	}
+}
@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::Path;
+
+/// One stage of a task pipeline, declared in the task frontmatter:
+///
+/// ```text
+/// stages:
+///   - plan: Write an implementation plan into the Process Log
+///   - implement (codex): Implement the plan
+///   - test: Run the tests and fix failures
+/// ```
+///
+/// or inline as `stages: plan, implement, test` (default prompts).
+#[derive(Debug, Clone)]
+pub struct Stage {
+	pub name: String,
+	pub agent: Option<String>,
+	pub prompt: String,
+}
+
+pub fn parse_stages(path: &Path) -> Vec<Stage> {
+	let Ok(content) = fs::read_to_string(path) else {
+		return vec![];
+	};
+	let mut lines = content.lines();
+	if lines.next() != Some("---") {
+		return vec![];
+	}
+
+	let mut stages = Vec::new();
+	let mut in_stages = false;
+	for line in lines {
+		let trimmed = line.trim();
+		if trimmed == "---" {
+			break;
+		}
+		if let Some(rest) = trimmed.strip_prefix("stages:") {
+			in_stages = true;
+			for name in rest.split(',').map(|n| n.trim().trim_matches(|c| c == '[' || c == ']')) {
+				if !name.is_empty() {
+					stages.push(parse_stage(name));
+				}
+			}
+			continue;
+		}
+		if in_stages {
+			match trimmed.strip_prefix("- ") {
+				Some(item) if line.starts_with(' ') || line.starts_with('\t') || line.starts_with('-') => {
+					stages.push(parse_stage(item));
+				}
+				_ => in_stages = false,
+			}
+		}
+	}
+	stages
+}
+
+/// Parse `name (agent): prompt`, where agent and prompt are optional
+fn parse_stage(item: &str) -> Stage {
+	let (head, prompt) = match item.split_once(':') {
+		Some((head, prompt)) => (head.trim(), prompt.trim().trim_matches('"').to_string()),
+		None => (item.trim(), String::new()),
+	};
+	let (name, agent) = match head.split_once('(') {
+		Some((name, agent)) => (
+			name.trim().to_string(),
+			Some(agent.trim_end_matches(')').trim().to_string()).filter(|a| !a.is_empty()),
+		),
+		None => (head.to_string(), None),
+	};
+	let prompt = if prompt.is_empty() {
+		format!("Complete the {} stage of the task.", name)
+	} else {
+		prompt
+	};
+	Stage { name, agent, prompt }
+}
+
+/// Instructions sent to the agent running a stage.
+/// The done marker is spelled out so the echoed prompt itself doesn't trigger it.
+pub fn stage_prompt(stage: &Stage, index: usize, total: usize) -> String {
+	format!(
+		"Pipeline stage {}/{} ({}): {}\n\nWhen this stage is complete, log it to the task's Process Log and reply with a final line consisting of a slash followed by swarm:done so the next stage can start.",
+		index + 1,
+		total,
+		stage.name,
+		stage.prompt
+	)
+}
+
+/// Instructions for a fan-out planner: split the task into subtask files that
+/// point back at it with `parent:`, so swarm can start a worker for each.
+pub fn planner_prompt(task: &Path, tasks_dir: &str, parent: &str) -> String {
+	format!(
+		"Fan-out planning: read {} and break it into independent subtasks that separate agents can work on in parallel. Don't implement anything yourself.\n\nWrite each subtask as its own markdown file in {} in this format:\n\n---\nstatus: todo\nparent: {}\nsummary: <one line>\n---\n\n# <title>\n\n<what to do, where, and how to verify it>\n\n## Process Log\n\nList the subtasks in the parent task's Process Log, then reply with a final line consisting of a slash followed by swarm:done - swarm starts a worker agent for each subtask once you're done.",
+		task.display(),
+		tasks_dir,
+		parent
+	)
+}
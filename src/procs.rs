@@ -0,0 +1,164 @@
+// Processes under a session's pane, read with sysinfo: the agent and whatever
+// it started (test runners, builds) with their CPU and memory for the Details
+// pane. The health check uses them to tell an agent that's quietly thinking
+// from one whose process is gone, and a child pinning a core for an hour is
+// flagged as a runaway.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System, UpdateKind};
+
+pub use sysinfo::Signal;
+
+/// Shells a pane runs the agent from; anything else counts as the agent or its work
+const SHELLS: &[&str] = &["zsh", "bash", "sh", "fish", "dash"];
+
+/// A process above this much of a core counts as busy
+const BUSY_CPU: f32 = 80.0;
+
+/// Busy this long without a break is a runaway
+const RUNAWAY_AFTER: Duration = Duration::from_secs(3600);
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Proc {
+	pub pid: u32,
+	pub depth: usize, // 0 = the pane's own process
+	pub name: String,
+	pub command: String,
+	pub cpu: f32,    // percent of one core since the last refresh
+	pub memory: u64, // bytes
+	pub alive: bool, // false for zombies and stopped processes
+	#[serde(skip)]
+	pub busy_for: Option<Duration>,
+}
+
+impl Proc {
+	pub fn is_shell(&self) -> bool {
+		SHELLS.contains(&self.name.as_str())
+	}
+
+	pub fn is_runaway(&self) -> bool {
+		self.busy_for.is_some_and(|d| d >= RUNAWAY_AFTER)
+	}
+}
+
+/// Keeps the process table between refreshes, since CPU usage is measured
+/// from one refresh to the next
+#[derive(Default)]
+pub struct Monitor {
+	sys: System,
+	busy_since: HashMap<Pid, Instant>,
+}
+
+impl Monitor {
+	pub fn refresh(&mut self) {
+		self.sys.refresh_processes_specifics(
+			ProcessesToUpdate::All,
+			true,
+			ProcessRefreshKind::nothing()
+				.with_cpu()
+				.with_memory()
+				.with_cmd(UpdateKind::OnlyIfNotSet),
+		);
+		let now = Instant::now();
+		let processes = self.sys.processes();
+		self.busy_since
+			.retain(|pid, _| processes.get(pid).is_some_and(|p| p.cpu_usage() >= BUSY_CPU));
+		for (pid, process) in processes {
+			if process.cpu_usage() >= BUSY_CPU {
+				self.busy_since.entry(*pid).or_insert(now);
+			}
+		}
+	}
+
+	/// `root` and everything under it, each parent before its children
+	pub fn tree(&self, root: u32) -> Vec<Proc> {
+		let mut out = Vec::new();
+		let mut stack = vec![(Pid::from_u32(root), 0)];
+		while let Some((pid, depth)) = stack.pop() {
+			let Some(process) = self.sys.process(pid) else {
+				continue;
+			};
+			let command: Vec<String> = process.cmd().iter().map(|a| a.to_string_lossy().into_owned()).collect();
+			out.push(Proc {
+				pid: pid.as_u32(),
+				depth,
+				name: process.name().to_string_lossy().into_owned(),
+				command: command.join(" "),
+				cpu: process.cpu_usage(),
+				memory: process.memory(),
+				alive: !matches!(process.status(), ProcessStatus::Zombie | ProcessStatus::Stop | ProcessStatus::Dead),
+				busy_for: self.busy_since.get(&pid).map(Instant::elapsed),
+			});
+			let mut children: Vec<Pid> = self
+				.sys
+				.processes()
+				.iter()
+				.filter(|(_, p)| p.parent() == Some(pid) && p.thread_kind().is_none())
+				.map(|(child, _)| *child)
+				.collect();
+			// Popped in pid order
+			children.sort_unstable_by(|a, b| b.cmp(a));
+			stack.extend(children.into_iter().map(|child| (child, depth + 1)));
+		}
+		out
+	}
+}
+
+/// The tree under `root` with CPU measured over a short interval, for one-off reads
+pub fn snapshot(root: u32) -> Vec<Proc> {
+	let mut monitor = Monitor::default();
+	monitor.refresh();
+	std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+	monitor.refresh();
+	monitor.tree(root)
+}
+
+/// The agent: the first process under the pane that isn't a shell
+pub fn agent(procs: &[Proc]) -> Option<&Proc> {
+	procs.iter().find(|p| !p.is_shell())
+}
+
+/// Send `signal` to one process, e.g. a hung test runner under an agent
+pub fn send(pid: u32, signal: Signal) -> anyhow::Result<()> {
+	let mut sys = System::new();
+	let pid = Pid::from_u32(pid);
+	sys.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+	let process = sys.process(pid).ok_or_else(|| anyhow::anyhow!("process {pid} is gone"))?;
+	match process.kill_with(signal) {
+		Some(true) => Ok(()),
+		Some(false) => Err(anyhow::anyhow!("could not signal process {pid}")),
+		None => Err(anyhow::anyhow!("{signal:?} isn't supported here")),
+	}
+}
+
+/// SIGKILL `root` and everything under it, deepest first (a cancelled land check
+/// and the test runner it started)
+pub fn kill_tree(root: u32) {
+	let mut monitor = Monitor::default();
+	monitor.refresh();
+	let mut procs = monitor.tree(root);
+	procs.sort_by_key(|p| std::cmp::Reverse(p.depth));
+	for proc in procs {
+		let _ = send(proc.pid, Signal::Kill);
+	}
+}
+
+/// Whether anything besides a shell is still running under `root`
+pub fn agent_alive(root: u32) -> bool {
+	let mut monitor = Monitor::default();
+	monitor.refresh();
+	monitor.tree(root).iter().any(|p| p.alive && !p.is_shell())
+}
+
+/// "512 KB", "48 MB", "1.2 GB"
+pub fn format_memory(bytes: u64) -> String {
+	const MB: u64 = 1024 * 1024;
+	if bytes < MB {
+		format!("{} KB", bytes / 1024)
+	} else if bytes < 1024 * MB {
+		format!("{} MB", bytes / MB)
+	} else {
+		format!("{:.1} GB", bytes as f64 / (1024 * MB) as f64)
+	}
+}
@@ -0,0 +1,200 @@
+// Per-repo settings from a `.swarm.toml` at the repo root, committed alongside
+// the code so everyone launching agents there gets the same behaviour:
+// pre-launch checks, lifecycle hooks, files to seed worktrees with, the
+// checks to pass before landing and the skeleton for new task files.
+//
+// The shell commands in it only run once you've trusted them for that repo
+// (`swarm trust`, kept in ~/.swarm/trusted.toml), so cloning a repo, or an
+// agent editing the file in its worktree, can't get anything run on its own.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+pub const FILE_NAME: &str = ".swarm.toml";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Project {
+	/// Checks that must pass before an agent is started here
+	#[serde(default)]
+	pub checks: Vec<Check>,
+	/// Run after the global [hooks] for sessions in this repo
+	#[serde(default)]
+	pub hooks: crate::config::Hooks,
+	/// Added to the global [worktrees] lists
+	#[serde(default)]
+	pub worktrees: crate::config::Worktrees,
+	/// Run after the global [land] checks
+	#[serde(default)]
+	pub land: crate::config::Land,
+	/// Task file template (relative to this file), used instead of
+	/// ~/.swarm/templates/task.md for tasks created from here
+	#[serde(default)]
+	pub task_template: Option<String>,
+}
+
+/// One pre-launch check: a path that must exist and/or a command that must exit 0
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Check {
+	#[serde(default)]
+	pub name: String,
+	#[serde(default)]
+	pub exists: Option<String>,
+	#[serde(default)]
+	pub run: Option<String>,
+	/// Shown with the failure, e.g. "run npm install"
+	#[serde(default)]
+	pub hint: Option<String>,
+}
+
+impl Project {
+	/// Every shell command the file would have swarm run
+	pub fn commands(&self) -> Vec<&str> {
+		let all = self
+			.checks
+			.iter()
+			.filter_map(|c| c.run.as_deref())
+			.chain([
+				self.hooks.on_create.as_str(),
+				self.hooks.on_done.as_str(),
+				self.hooks.on_kill.as_str(),
+			])
+			.chain(self.land.checks.iter().map(String::as_str));
+		let mut commands = Vec::new();
+		for command in all.filter(|c| !c.trim().is_empty()) {
+			if !commands.contains(&command) {
+				commands.push(command);
+			}
+		}
+		commands
+	}
+}
+
+impl Check {
+	fn label(&self) -> &str {
+		if !self.name.is_empty() {
+			&self.name
+		} else {
+			self.exists.as_deref().or(self.run.as_deref()).unwrap_or("check")
+		}
+	}
+}
+
+/// `.swarm.toml` in `dir`, if there is one
+pub fn load(dir: &Path) -> Result<Option<Project>> {
+	let path = dir.join(FILE_NAME);
+	if !path.exists() {
+		return Ok(None);
+	}
+	let content = fs::read_to_string(&path)?;
+	let project = toml::from_str(&content).with_context(|| format!("invalid {}", path.display()))?;
+	Ok(Some(project))
+}
+
+/// `.swarm.toml` as committed at `repo`'s HEAD, ignoring uncommitted edits (and
+/// anything an agent wrote in its worktree)
+pub fn load_committed(repo: &Path) -> Result<Option<Project>> {
+	let output = Command::new("git")
+		.arg("-C")
+		.arg(repo)
+		.args(["show", &format!("HEAD:{FILE_NAME}")])
+		.stderr(Stdio::null())
+		.output()?;
+	if !output.status.success() {
+		return Ok(None);
+	}
+	let content = String::from_utf8_lossy(&output.stdout);
+	let project = toml::from_str(&content).with_context(|| format!("invalid {FILE_NAME} at HEAD in {}", repo.display()))?;
+	Ok(Some(project))
+}
+
+/// Run the checks in `dir`; returns one line per failed check
+pub fn run_checks(project: &Project, dir: &Path) -> Vec<String> {
+	let mut failures = Vec::new();
+	for check in &project.checks {
+		let ok = check.exists.as_ref().is_none_or(|p| dir.join(p).exists())
+			&& check.run.as_ref().is_none_or(|cmd| {
+				Command::new("sh")
+					.args(["-c", cmd])
+					.current_dir(dir)
+					.stdin(Stdio::null())
+					.stdout(Stdio::null())
+					.stderr(Stdio::null())
+					.status()
+					.is_ok_and(|s| s.success())
+			});
+		if !ok {
+			failures.push(match &check.hint {
+				Some(hint) => format!("{} ({})", check.label(), hint),
+				None => check.label().to_string(),
+			});
+		}
+	}
+	failures
+}
+
+/// ~/.swarm/trusted.toml: the commands (and task template) you've allowed, per repo
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Trusted {
+	#[serde(default)]
+	repo: Vec<TrustedRepo>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TrustedRepo {
+	path: String,
+	#[serde(default)]
+	commands: Vec<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	task_template: Option<String>,
+}
+
+fn trusted_path() -> Result<std::path::PathBuf> {
+	Ok(crate::config::base_dir()?.join("trusted.toml"))
+}
+
+fn load_trusted() -> Trusted {
+	trusted_path()
+		.ok()
+		.and_then(|p| fs::read_to_string(p).ok())
+		.and_then(|s| toml::from_str(&s).ok())
+		.unwrap_or_default()
+}
+
+/// Commands in `project` that haven't been trusted for `repo` (all of them for
+/// a new repo, the changed ones after an edit)
+pub fn untrusted<'a>(project: &'a Project, repo: &Path) -> Vec<&'a str> {
+	let trusted = load_trusted();
+	let key = repo.display().to_string();
+	let allowed = trusted.repo.iter().find(|r| r.path == key).map(|r| &r.commands);
+	project
+		.commands()
+		.into_iter()
+		.filter(|c| !allowed.is_some_and(|a| a.iter().any(|t| t == c)))
+		.collect()
+}
+
+/// Whether `repo` is trusted with the task_template `project` names now
+pub fn template_trusted(project: &Project, repo: &Path) -> bool {
+	let key = repo.display().to_string();
+	load_trusted()
+		.repo
+		.iter()
+		.any(|r| r.path == key && r.task_template.is_some() && r.task_template == project.task_template)
+}
+
+/// Trust exactly the commands and task template `project` has now for `repo`
+pub fn trust(repo: &Path, project: &Project) -> Result<()> {
+	let mut trusted = load_trusted();
+	let key = repo.display().to_string();
+	trusted.repo.retain(|r| r.path != key);
+	trusted.repo.push(TrustedRepo {
+		path: key,
+		commands: project.commands().into_iter().map(str::to_string).collect(),
+		task_template: project.task_template.clone(),
+	});
+	let path = trusted_path()?;
+	fs::write(&path, toml::to_string_pretty(&trusted)?).with_context(|| format!("failed to write {}", path.display()))
+}
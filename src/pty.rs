@@ -0,0 +1,189 @@
+// Fallback backend used when tmux isn't installed: agents run as child
+// processes of swarm on a PTY. Sessions live only as long as swarm does and
+// can't be attached to, but status, preview, input and kill all work.
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use portable_pty::{Child, CommandBuilder, MasterPty, PtySize, native_pty_system};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+struct PtySession {
+	child: Box<dyn Child + Send + Sync>,
+	writer: Box<dyn Write + Send>,
+	_master: Box<dyn MasterPty + Send>,
+	dir: PathBuf,
+	log: Arc<Mutex<LogSink>>,
+	started: std::time::SystemTime,
+}
+
+/// Output goes to a buffer until `pipe_to` names the log file (mirrors tmux pipe-pane)
+#[derive(Default)]
+struct LogSink {
+	file: Option<fs::File>,
+	path: Option<PathBuf>,
+	pending: Vec<u8>,
+}
+
+static SESSIONS: Lazy<Mutex<HashMap<String, PtySession>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn spawn(session: &str, dir: &Path, program: &str, args: &[&str]) -> Result<()> {
+	let pair = native_pty_system()
+		.openpty(PtySize {
+			rows: 50,
+			cols: 200,
+			pixel_width: 0,
+			pixel_height: 0,
+		})
+		.context("failed to open pty")?;
+	let mut cmd = CommandBuilder::new(program);
+	cmd.args(args);
+	cmd.cwd(dir);
+	let child = pair
+		.slave
+		.spawn_command(cmd)
+		.with_context(|| format!("failed to start {}", session))?;
+	drop(pair.slave);
+
+	let mut reader = pair.master.try_clone_reader()?;
+	let writer = pair.master.take_writer()?;
+	let log = Arc::new(Mutex::new(LogSink::default()));
+	let sink = Arc::clone(&log);
+	std::thread::spawn(move || {
+		let mut buf = [0u8; 4096];
+		while let Ok(n) = reader.read(&mut buf) {
+			if n == 0 {
+				break;
+			}
+			if let Ok(mut sink) = sink.lock() {
+				match sink.file.as_mut() {
+					Some(file) => {
+						let _ = file.write_all(&buf[..n]);
+					}
+					None => sink.pending.extend_from_slice(&buf[..n]),
+				}
+			}
+		}
+	});
+
+	let mut sessions = SESSIONS.lock().unwrap_or_else(|e| e.into_inner());
+	sessions.insert(
+		session.to_string(),
+		PtySession {
+			child,
+			writer,
+			_master: pair.master,
+			dir: dir.to_path_buf(),
+			log,
+			started: std::time::SystemTime::now(),
+		},
+	);
+	Ok(())
+}
+
+/// Session names, oldest first
+pub fn list_sessions() -> Vec<String> {
+	let sessions = SESSIONS.lock().unwrap_or_else(|e| e.into_inner());
+	let mut names: Vec<(&String, std::time::SystemTime)> =
+		sessions.iter().map(|(name, s)| (name, s.started)).collect();
+	names.sort_by_key(|(_, started)| *started);
+	names.into_iter().map(|(name, _)| name.clone()).collect()
+}
+
+pub fn pipe_to(session: &str, log_path: &Path) -> Result<()> {
+	let sessions = SESSIONS.lock().unwrap_or_else(|e| e.into_inner());
+	let Some(s) = sessions.get(session) else {
+		return Err(anyhow::anyhow!("no such session: {}", session));
+	};
+	let mut sink = s.log.lock().unwrap_or_else(|e| e.into_inner());
+	if sink.file.is_none() {
+		let mut file = fs::OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(log_path)?;
+		file.write_all(&sink.pending)?;
+		sink.pending.clear();
+		sink.file = Some(file);
+		sink.path = Some(log_path.to_path_buf());
+	}
+	Ok(())
+}
+
+/// Latest output lines from the session's log (plain text; no pane to capture)
+pub fn capture_tail(session: &str, lines: usize) -> Result<Vec<String>> {
+	let path = {
+		let sessions = SESSIONS.lock().unwrap_or_else(|e| e.into_inner());
+		let s = sessions
+			.get(session)
+			.ok_or_else(|| anyhow::anyhow!("no such session: {}", session))?;
+		let sink = s.log.lock().unwrap_or_else(|e| e.into_inner());
+		sink.path.clone()
+	};
+	match path {
+		Some(path) => crate::logs::tail_lines(&path, lines),
+		None => Ok(vec![]),
+	}
+}
+
+pub fn write(session: &str, bytes: &[u8]) -> Result<()> {
+	let mut sessions = SESSIONS.lock().unwrap_or_else(|e| e.into_inner());
+	let s = sessions
+		.get_mut(session)
+		.ok_or_else(|| anyhow::anyhow!("no such session: {}", session))?;
+	s.writer.write_all(bytes)?;
+	s.writer.flush()?;
+	Ok(())
+}
+
+/// Bytes a tmux key name (as used with send-keys) stands for
+pub fn key_bytes(key: &str) -> &'static [u8] {
+	match key {
+		"Enter" => b"\r",
+		"Escape" => b"\x1b",
+		"BTab" => b"\x1b[Z",
+		"Tab" => b"\t",
+		"C-c" => b"\x03",
+		"C-d" => b"\x04",
+		"Up" => b"\x1b[A",
+		"Down" => b"\x1b[B",
+		_ => b"",
+	}
+}
+
+pub fn exit_status(session: &str) -> Option<i32> {
+	let mut sessions = SESSIONS.lock().unwrap_or_else(|e| e.into_inner());
+	let status = sessions.get_mut(session)?.child.try_wait().ok()??;
+	Some(status.exit_code() as i32)
+}
+
+pub fn pid(session: &str) -> Option<u32> {
+	let sessions = SESSIONS.lock().unwrap_or_else(|e| e.into_inner());
+	sessions.get(session)?.child.process_id()
+}
+
+pub fn kill(session: &str) -> Result<()> {
+	let mut sessions = SESSIONS.lock().unwrap_or_else(|e| e.into_inner());
+	if let Some(mut s) = sessions.remove(session) {
+		let _ = s.child.kill();
+	}
+	Ok(())
+}
+
+pub fn rename(old: &str, new: &str) -> Result<()> {
+	let mut sessions = SESSIONS.lock().unwrap_or_else(|e| e.into_inner());
+	let s = sessions
+		.remove(old)
+		.ok_or_else(|| anyhow::anyhow!("no such session: {}", old))?;
+	sessions.insert(new.to_string(), s);
+	Ok(())
+}
+
+pub fn session_dir(session: &str) -> Option<String> {
+	let sessions = SESSIONS.lock().unwrap_or_else(|e| e.into_inner());
+	sessions
+		.get(session)
+		.map(|s| s.dir.to_string_lossy().into_owned())
+}
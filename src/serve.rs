@@ -0,0 +1,180 @@
+// `swarm serve`: a web dashboard mirroring the Agents and Tasks views, so
+// overnight agents can be checked from a phone browser. The page polls two
+// JSON endpoints; fleet state is refreshed in the background, previews on demand.
+// The only write is answering a waiting agent's prompt (approve/deny), which
+// ntfy notification buttons also POST to. It always needs a token: --token, or
+// on localhost without one a random per-run token only the served page knows,
+// so other sites open in the browser can't approve anything. /calendar.ics
+// serves task due dates for calendar apps to subscribe to.
+
+use anyhow::Result;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+const DASHBOARD_HTML: &str = include_str!("../assets/dashboard.html");
+
+pub struct ServeOptions {
+	pub host: String,
+	pub port: u16,
+	pub token: Option<String>,
+	pub refresh: Duration,
+}
+
+/// Serve until the process is killed. `snapshot` returns the fleet JSON (agents + tasks);
+/// `preview` returns recent output lines for a session, or None if it doesn't exist;
+/// `answer` approves (true) or denies a session's pending prompt; `calendar`
+/// returns the task due dates as iCalendar.
+pub fn run<S, P, A, C>(opts: ServeOptions, snapshot: S, preview: P, answer: A, calendar: C) -> Result<()>
+where
+	S: Fn() -> serde_json::Value + Send + 'static,
+	P: Fn(&str) -> Option<Vec<String>>,
+	A: Fn(&str, bool) -> Result<String>,
+	C: Fn() -> String,
+{
+	let addr = format!("{}:{}", opts.host, opts.port);
+	let server = Server::http(&addr).map_err(|e| anyhow::anyhow!("failed to listen on {addr}: {e}"))?;
+
+	let cached = Arc::new(Mutex::new(snapshot().to_string()));
+	{
+		let cached = Arc::clone(&cached);
+		let refresh = opts.refresh;
+		std::thread::spawn(move || loop {
+			std::thread::sleep(refresh);
+			let json = snapshot().to_string();
+			*cached.lock().unwrap_or_else(|e| e.into_inner()) = json;
+		});
+	}
+
+	println!("swarm dashboard on http://{addr}/");
+	let local_only = opts.host == "127.0.0.1" || opts.host == "localhost";
+	if !local_only && opts.token.is_none() {
+		println!("Warning: listening beyond localhost without --token; anyone on the network can read agent output");
+		println!("Approve/deny is disabled until a token is set");
+	}
+	let action_token = opts.token.clone().unwrap_or_else(random_token);
+	// Names this server answers to on localhost; anything else is DNS rebinding
+	let local_hosts: Vec<String> = ["127.0.0.1", "localhost", "[::1]"]
+		.iter()
+		.map(|host| format!("{host}:{}", opts.port))
+		.collect();
+	for request in server.incoming_requests() {
+		let (path, query) = match request.url().split_once('?') {
+			Some((path, query)) => (path.to_string(), query.to_string()),
+			None => (request.url().to_string(), String::new()),
+		};
+		let given = query
+			.split('&')
+			.find_map(|kv| kv.strip_prefix("token="))
+			.map(percent_decode)
+			.or_else(|| header(&request, "X-Swarm-Token"));
+		let host = header(&request, "Host").unwrap_or_default();
+		if local_only && !local_hosts.contains(&host) {
+			respond(request, 403, "text/plain", "unexpected Host".to_string());
+			continue;
+		}
+		if let Some(token) = &opts.token {
+			if !given.as_deref().is_some_and(|given| same_token(given, token)) {
+				respond(request, 401, "text/plain", "unauthorized - add ?token=...".to_string());
+				continue;
+			}
+		}
+		let action = path.strip_prefix("/api/session/").and_then(|rest| {
+			rest.strip_suffix("/approve")
+				.map(|name| (name, true))
+				.or_else(|| rest.strip_suffix("/deny").map(|name| (name, false)))
+		});
+		if let Some((name, approve)) = action {
+			// Browsers send Origin on cross-site POSTs; ntfy's buttons send none
+			let cross_site = header(&request, "Origin").is_some_and(|origin| origin != format!("http://{host}"));
+			if *request.method() != Method::Post {
+				respond(request, 405, "text/plain", "use POST".to_string());
+			} else if !local_only && opts.token.is_none() {
+				respond(request, 403, "text/plain", "set --token to allow approvals".to_string());
+			} else if cross_site {
+				respond(request, 403, "text/plain", "cross-site request refused".to_string());
+			} else if !given.as_deref().is_some_and(|given| same_token(given, &action_token)) {
+				respond(request, 401, "text/plain", "unauthorized - reload the dashboard".to_string());
+			} else {
+				match answer(&percent_decode(name), approve) {
+					Ok(message) => respond(request, 200, "text/plain", message),
+					Err(e) => respond(request, 409, "text/plain", e.to_string()),
+				}
+			}
+		} else if path == "/" || path.starts_with("/session/") {
+			// Other sites can't read this response, so the token stays with the page
+			let page = DASHBOARD_HTML.replace("__ACTION_TOKEN__", &action_token);
+			respond(request, 200, "text/html; charset=utf-8", page);
+		} else if path == "/calendar.ics" {
+			respond(request, 200, "text/calendar; charset=utf-8", calendar());
+		} else if path == "/api/status" {
+			let body = cached.lock().unwrap_or_else(|e| e.into_inner()).clone();
+			respond(request, 200, "application/json", body);
+		} else if let Some(name) = path.strip_prefix("/api/session/") {
+			match preview(&percent_decode(name)) {
+				Some(lines) => {
+					let body = serde_json::json!({ "name": name, "lines": lines }).to_string();
+					respond(request, 200, "application/json", body);
+				}
+				None => respond(request, 404, "text/plain", "no such session".to_string()),
+			}
+		} else {
+			respond(request, 404, "text/plain", "not found".to_string());
+		}
+	}
+	Ok(())
+}
+
+/// A request header's value, e.g. header(&request, "Host")
+fn header(request: &Request, name: &'static str) -> Option<String> {
+	request
+		.headers()
+		.iter()
+		.find(|h| h.field.equiv(name))
+		.map(|h| h.value.as_str().to_string())
+}
+
+/// Compare tokens without leaking how much of them matched through timing
+fn same_token(given: &str, token: &str) -> bool {
+	given.len() == token.len() && given.bytes().zip(token.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// 128 random bits as hex. RandomState is seeded from the OS's random source,
+/// and each one gets different keys.
+fn random_token() -> String {
+	use std::hash::{BuildHasher, Hasher};
+	(0..2)
+		.map(|_| format!("{:016x}", std::collections::hash_map::RandomState::new().build_hasher().finish()))
+		.collect()
+}
+
+fn respond(request: Request, status: u16, content_type: &str, body: String) {
+	let mut response = Response::from_string(body).with_status_code(status);
+	if let Ok(header) = Header::from_bytes("Content-Type", content_type) {
+		response = response.with_header(header);
+	}
+	if let Ok(header) = Header::from_bytes("Cache-Control", "no-store") {
+		response = response.with_header(header);
+	}
+	let _ = request.respond(response);
+}
+
+/// Session names are slugs, but browsers may still escape them
+fn percent_decode(input: &str) -> String {
+	let bytes = input.as_bytes();
+	let mut out = Vec::with_capacity(bytes.len());
+	let mut i = 0;
+	while i < bytes.len() {
+		if bytes[i] == b'%' && i + 2 < bytes.len() {
+			let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+			if let Some(byte) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+				out.push(byte);
+				i += 3;
+				continue;
+			}
+		}
+		out.push(bytes[i]);
+		i += 1;
+	}
+	String::from_utf8_lossy(&out).into_owned()
+}
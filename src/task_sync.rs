@@ -0,0 +1,114 @@
+// Keeps tasks_dir in sync between machines when it's a git repo: commit local
+// edits, rebase onto the remote, push. Runs off the UI thread on an interval.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+	let output = Command::new("git")
+		.arg("-C")
+		.arg(dir)
+		.args(args)
+		.output()
+		.context("failed to run git")?;
+	if !output.status.success() {
+		let stderr = String::from_utf8_lossy(&output.stderr);
+		return Err(anyhow::anyhow!(
+			"git {} failed: {}",
+			args.first().unwrap_or(&""),
+			stderr.lines().last().unwrap_or("").trim()
+		));
+	}
+	Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Whether `dir` is the root of a git repo. A folder inside a bigger repo (a
+/// dotfiles repo, a vault) doesn't count: syncing would commit, rebase and push
+/// everything else in it too.
+pub fn is_repo(dir: &Path) -> bool {
+	repo_root(dir).is_some_and(|root| same_dir(&root, dir))
+}
+
+fn repo_root(dir: &Path) -> Option<std::path::PathBuf> {
+	git(dir, &["rev-parse", "--show-toplevel"]).ok().map(Into::into)
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+	match (a.canonicalize(), b.canonicalize()) {
+		(Ok(a), Ok(b)) => a == b,
+		_ => a == b,
+	}
+}
+
+/// Commit, pull --rebase and push. Returns a short description of what happened.
+pub fn sync(dir: &Path, remote: &str) -> Result<String> {
+	match repo_root(dir) {
+		None => return Err(anyhow::anyhow!("{} is not a git repo", dir.display())),
+		Some(root) if !same_dir(&root, dir) => {
+			return Err(anyhow::anyhow!(
+				"{} is inside the git repo at {}; task sync needs tasks_dir to be a repo of its own",
+				dir.display(),
+				root.display()
+			))
+		}
+		Some(_) => {}
+	}
+	let mut done = Vec::new();
+
+	git(dir, &["add", "-A"])?;
+	let changes = git(dir, &["status", "--porcelain"])?;
+	if !changes.is_empty() {
+		let host = Command::new("hostname")
+			.output()
+			.ok()
+			.map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+			.unwrap_or_default();
+		let message = format!("swarm: sync tasks from {}", if host.is_empty() { "unknown host" } else { &host });
+		git(dir, &["commit", "-q", "-m", &message])?;
+		let count = changes.lines().count();
+		done.push(format!("committed {} change{}", count, if count == 1 { "" } else { "s" }));
+	}
+
+	// symbolic-ref also works on a fresh clone with no commits yet
+	let branch = git(dir, &["symbolic-ref", "--short", "HEAD"])?;
+	git(dir, &["fetch", "-q", remote])?;
+	let remote_ref = format!("{remote}/{branch}");
+	let has_remote_branch = git(dir, &["rev-parse", "--verify", "-q", &remote_ref]).is_ok();
+	let has_commits = git(dir, &["rev-parse", "--verify", "-q", "HEAD"]).is_ok();
+	if !has_commits {
+		// Nothing local to keep (local edits would have been committed above)
+		if has_remote_branch {
+			git(dir, &["reset", "-q", "--hard", &remote_ref])?;
+			done.push("pulled".to_string());
+		}
+		return Ok(if done.is_empty() { "up to date".to_string() } else { done.join(", ") });
+	}
+	if has_remote_branch {
+		let behind = git(dir, &["rev-list", "--count", &format!("HEAD..{remote_ref}")])?;
+		if behind != "0" {
+			if let Err(e) = git(dir, &["rebase", "-q", "--autostash", &remote_ref]) {
+				// Leave the repo as it was; the user resolves by hand
+				let _ = git(dir, &["rebase", "--abort"]);
+				return Err(anyhow::anyhow!("conflict pulling tasks ({e}); resolve in {}", dir.display()));
+			}
+			done.push(format!("pulled {} commit{}", behind, if behind == "1" { "" } else { "s" }));
+		}
+	}
+
+	let ahead = if has_remote_branch {
+		git(dir, &["rev-list", "--count", &format!("{remote_ref}..HEAD")])?
+	} else {
+		"1".to_string()
+	};
+	if ahead != "0" {
+		git(dir, &["push", "-q", "-u", remote, &branch])?;
+		done.push("pushed".to_string());
+	}
+
+	Ok(if done.is_empty() {
+		"up to date".to_string()
+	} else {
+		done.join(", ")
+	})
+}
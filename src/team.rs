@@ -0,0 +1,99 @@
+// Team mode: publish this machine's fleet status to a shared location and read
+// teammates' back for `swarm fleet`. Locations are http(s) URLs (PUT/GET, with
+// [team] token sent as a bearer token), s3:// paths (via the aws CLI) or plain
+// file paths on a shared drive.
+
+use crate::model::TeamStatus;
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+fn client() -> Result<reqwest::blocking::Client> {
+	if crate::net::offline() {
+		anyhow::bail!("offline (--offline, or no connection)");
+	}
+	Ok(reqwest::blocking::Client::builder()
+		.user_agent("swarm-team")
+		.timeout(Duration::from_secs(10))
+		.build()?)
+}
+
+/// [team] token as a bearer token on http(s) requests ("" = none)
+fn with_token(request: reqwest::blocking::RequestBuilder, token: &str) -> reqwest::blocking::RequestBuilder {
+	if token.trim().is_empty() {
+		request
+	} else {
+		request.bearer_auth(token.trim())
+	}
+}
+
+/// Write the status JSON to `target`
+pub fn publish(target: &str, token: &str, status: &TeamStatus) -> Result<()> {
+	let body = serde_json::to_string_pretty(status)?;
+	if target.starts_with("http://") || target.starts_with("https://") {
+		let response = with_token(client()?.put(target), token)
+			.header("content-type", "application/json")
+			.body(body)
+			.send()
+			.with_context(|| format!("failed to publish to {target}"))?;
+		if !response.status().is_success() {
+			return Err(anyhow::anyhow!("publish to {} returned {}", target, response.status()));
+		}
+	} else if target.starts_with("s3://") {
+		let mut child = Command::new("aws")
+			.args(["s3", "cp", "-", target, "--content-type", "application/json"])
+			.stdin(Stdio::piped())
+			.stdout(Stdio::null())
+			.stderr(Stdio::piped())
+			.spawn()
+			.context("failed to run aws CLI (needed for s3:// publish targets)")?;
+		if let Some(mut stdin) = child.stdin.take() {
+			stdin.write_all(body.as_bytes())?;
+		}
+		let output = child.wait_with_output()?;
+		if !output.status.success() {
+			return Err(anyhow::anyhow!(
+				"aws s3 cp to {} failed: {}",
+				target,
+				String::from_utf8_lossy(&output.stderr).trim()
+			));
+		}
+	} else {
+		let path = crate::config::expand_path(target);
+		let tmp = format!("{path}.tmp");
+		std::fs::write(&tmp, body)?;
+		std::fs::rename(&tmp, &path)?;
+	}
+	Ok(())
+}
+
+/// Read one teammate's published status
+pub fn fetch(source: &str, token: &str) -> Result<TeamStatus> {
+	let body = if source.starts_with("http://") || source.starts_with("https://") {
+		let response = with_token(client()?.get(source), token)
+			.send()
+			.with_context(|| format!("failed to fetch {source}"))?;
+		if !response.status().is_success() {
+			return Err(anyhow::anyhow!("{} returned {}", source, response.status()));
+		}
+		response.text()?
+	} else if source.starts_with("s3://") {
+		let output = Command::new("aws")
+			.args(["s3", "cp", source, "-"])
+			.output()
+			.context("failed to run aws CLI (needed for s3:// members)")?;
+		if !output.status.success() {
+			return Err(anyhow::anyhow!(
+				"aws s3 cp from {} failed: {}",
+				source,
+				String::from_utf8_lossy(&output.stderr).trim()
+			));
+		}
+		String::from_utf8_lossy(&output.stdout).into_owned()
+	} else {
+		std::fs::read_to_string(crate::config::expand_path(source))
+			.with_context(|| format!("failed to read {source}"))?
+	};
+	serde_json::from_str(&body).with_context(|| format!("{source} is not a swarm status"))
+}
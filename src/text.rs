@@ -0,0 +1,179 @@
+// Fitting untrusted text (agent output, notes, task titles) into terminal
+// columns. Widths are display columns, so emoji and CJK count as two and
+// combining marks as none; nothing here slices by byte.
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Columns `text` takes up in a terminal
+pub fn width(text: &str) -> usize {
+	text.width()
+}
+
+/// Fit `text` in `max_width` columns, ending with "…" when it had to be cut
+pub fn truncate(text: &str, max_width: usize) -> String {
+	if text.width() <= max_width {
+		return text.to_string();
+	}
+	let Some(budget) = max_width.checked_sub(1) else {
+		return String::new();
+	};
+	let mut out = String::new();
+	let mut used = 0;
+	for c in text.chars() {
+		let w = c.width().unwrap_or(0);
+		if used + w > budget {
+			break;
+		}
+		used += w;
+		out.push(c);
+	}
+	out.push('…');
+	out
+}
+
+/// Pad with spaces to `width` columns (format!'s `{:<w$}` counts chars)
+pub fn pad(text: &str, width: usize) -> String {
+	format!("{text}{}", " ".repeat(width.saturating_sub(text.width())))
+}
+
+/// One displayable line: escape sequences and control characters removed,
+/// tabs and newlines turned into spaces
+pub fn sanitize(text: &str) -> String {
+	let mut out = String::with_capacity(text.len());
+	let mut chars = text.chars().peekable();
+	while let Some(c) = chars.next() {
+		match c {
+			'\x1b' => match chars.next() {
+				// CSI: parameters, then a final byte in @..~
+				Some('[') => {
+					for c in chars.by_ref() {
+						if ('@'..='~').contains(&c) {
+							break;
+						}
+					}
+				}
+				// OSC: ends at BEL or ESC \
+				Some(']') => {
+					while let Some(c) = chars.next() {
+						if c == '\x07' {
+							break;
+						}
+						if c == '\x1b' {
+							chars.next_if_eq(&'\\');
+							break;
+						}
+					}
+				}
+				_ => {}
+			},
+			'\t' | '\n' => out.push(' '),
+			c if c.is_control() => {}
+			c => out.push(c),
+		}
+	}
+	out
+}
+
+/// How alike two titles are, 0.0 to 1.0: shared letter pairs (Dice
+/// coefficient) after lowercasing and treating punctuation and dashes as spaces,
+/// so "Fix login redirect" and "fix-the-login-redirect" score high
+pub fn similarity(a: &str, b: &str) -> f64 {
+	fn bigrams(text: &str) -> Vec<(char, char)> {
+		let normal: String = text
+			.to_lowercase()
+			.chars()
+			.map(|c| if c.is_alphanumeric() { c } else { ' ' })
+			.collect();
+		let words = normal.split_whitespace().collect::<Vec<_>>().join(" ");
+		let chars: Vec<char> = words.chars().collect();
+		chars.windows(2).map(|w| (w[0], w[1])).collect()
+	}
+	let (a, mut b) = (bigrams(a), bigrams(b));
+	if a.is_empty() || b.is_empty() {
+		return 0.0;
+	}
+	let total = a.len() + b.len();
+	let mut shared = 0;
+	for pair in &a {
+		if let Some(idx) = b.iter().position(|p| p == pair) {
+			b.swap_remove(idx);
+			shared += 1;
+		}
+	}
+	2.0 * shared as f64 / total as f64
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Emoji (incl. ZWJ and flags), CJK, combining marks, controls, escapes
+	const PIECES: &[&str] = &[
+		"a", "Z", " ", "é", "e\u{301}", "日本", "語", "한", "👍", "👨‍👩‍👧", "🇯🇵", "\u{200b}", "\t", "\n", "\r",
+		"\x07", "\x00", "\u{9b}", "\x1b[31m", "\x1b[0m", "\x1b]0;title\x07", "\x1b", "…", "—", "ｱ",
+	];
+
+	/// Deterministic pseudo-random strings built from PIECES
+	fn samples() -> Vec<String> {
+		let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+		let mut next = || {
+			seed ^= seed << 13;
+			seed ^= seed >> 7;
+			seed ^= seed << 17;
+			seed as usize
+		};
+		(0..2000)
+			.map(|_| {
+				let len = next() % 40;
+				(0..len).map(|_| PIECES[next() % PIECES.len()]).collect()
+			})
+			.collect()
+	}
+
+	#[test]
+	fn truncate_never_exceeds_width() {
+		for text in samples() {
+			let clean = sanitize(&text);
+			for max in 0..30 {
+				let cut = truncate(&clean, max);
+				assert!(width(&cut) <= max, "{clean:?} cut to {max}: {cut:?}");
+				assert!(clean.starts_with(cut.trim_end_matches('…')) || cut == clean);
+			}
+		}
+	}
+
+	#[test]
+	fn truncate_keeps_text_that_fits() {
+		assert_eq!(truncate("hello", 5), "hello");
+		assert_eq!(truncate("hello!", 5), "hell…");
+		assert_eq!(truncate("日本語です", 6), "日本…");
+		assert_eq!(truncate("日本語です", 5), "日本…");
+		assert_eq!(truncate("👍👍👍", 4), "👍…");
+		assert_eq!(truncate("abc", 0), "");
+	}
+
+	#[test]
+	fn sanitize_strips_controls_and_escapes() {
+		for text in samples() {
+			let clean = sanitize(&text);
+			assert!(!clean.chars().any(|c| c.is_control()), "{text:?} -> {clean:?}");
+		}
+		assert_eq!(sanitize("\x1b[1;31mred\x1b[0m\tdone\r\x07"), "red done");
+		assert_eq!(sanitize("\x1b]0;title\x1b\\ok"), "ok");
+		assert_eq!(sanitize("e\u{301} 👨‍👩‍👧"), "e\u{301} 👨‍👩‍👧");
+	}
+
+	#[test]
+	fn similarity_ignores_case_and_punctuation() {
+		assert_eq!(similarity("Fix login redirect", "fix-login-redirect"), 1.0);
+		assert!(similarity("Fix login redirect", "Fix the login redirect") > 0.8);
+		assert!(similarity("Fix login redirect", "Write release notes") < 0.3);
+		assert_eq!(similarity("", "anything"), 0.0);
+	}
+
+	#[test]
+	fn pad_counts_columns() {
+		assert_eq!(pad("日本", 6), "日本  ");
+		assert_eq!(pad("abc", 2), "abc");
+	}
+}
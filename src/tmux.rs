@@ -5,6 +5,8 @@ use std::process::Command;
 use std::sync::OnceLock;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use crate::pty;
+
 /// Get the default tmux socket path for the current user
 #[cfg(unix)]
 fn default_socket_path() -> Option<std::path::PathBuf> {
@@ -13,7 +15,8 @@ fn default_socket_path() -> Option<std::path::PathBuf> {
 		if output.status.success() {
 			if let Ok(uid_str) = String::from_utf8(output.stdout) {
 				if let Ok(uid) = uid_str.trim().parse::<u32>() {
-					let socket_dir = std::path::PathBuf::from(format!("/tmp/tmux-{}", uid));
+					let tmpdir = std::env::var("TMUX_TMPDIR").unwrap_or_else(|_| "/tmp".to_string());
+					let socket_dir = Path::new(&tmpdir).join(format!("tmux-{}", uid));
 					return Some(socket_dir.join("default"));
 				}
 			}
@@ -29,6 +32,9 @@ fn default_socket_path() -> Option<std::path::PathBuf> {
 
 pub const SWARM_PREFIX: &str = "swarm-";
 
+/// Window that commands run from the TUI (:) get, next to the agent's
+const RUN_WINDOW: &str = "swarm-run";
+
 /// Common tmux installation paths
 const TMUX_PATHS: &[&str] = &[
     "/opt/homebrew/bin/tmux",  // Apple Silicon Homebrew
@@ -65,6 +71,20 @@ pub fn find_tmux() -> &'static str {
     })
 }
 
+static TMUX_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+/// Whether a working tmux binary exists. When it doesn't, sessions fall back
+/// to the in-process PTY backend (see pty.rs).
+pub fn is_available() -> bool {
+	*TMUX_AVAILABLE.get_or_init(|| {
+		Command::new(find_tmux())
+			.arg("-V")
+			.output()
+			.map(|o| o.status.success())
+			.unwrap_or(false)
+	})
+}
+
 /// Create a Command for tmux with the correct path
 fn tmux_cmd() -> Command {
     Command::new(find_tmux())
@@ -92,23 +112,15 @@ pub fn ensure_server() -> Result<()> {
 			if !stderr.contains("no server running") {
 				return Ok(());
 			}
-			// Stale socket detected - clean it up
-			if let Some(socket_path) = default_socket_path() {
-				if socket_path.exists() {
-					let _ = fs::remove_file(&socket_path);
-				}
-			}
+			// Possibly stale socket - clean it up only if nothing is listening
+			remove_stale_socket();
 		}
 		Ok(out) => {
 			// Check if error is about no server running (stale socket)
 			let stderr = String::from_utf8_lossy(&out.stderr);
 			if stderr.contains("no server running") {
-				// Clean up stale socket file if it exists
-				if let Some(socket_path) = default_socket_path() {
-					if socket_path.exists() {
-						let _ = fs::remove_file(&socket_path);
-					}
-				}
+				// Clean up stale socket file, if it really is stale
+				remove_stale_socket();
 			}
 		}
 		Err(_) => {
@@ -121,7 +133,54 @@ pub fn ensure_server() -> Result<()> {
 	Ok(())
 }
 
+/// Remove the default socket only when it is provably dead: it must be a Unix
+/// socket, refuse connections, and `tmux -S <socket> has-session` must fail.
+/// A live personal server (or anything else holding the socket) is never touched.
+fn remove_stale_socket() {
+	if let Some(socket_path) = default_socket_path() {
+		if is_stale_socket(&socket_path) && !socket_has_sessions(&socket_path) {
+			let _ = fs::remove_file(&socket_path);
+		}
+	}
+}
+
+fn socket_has_sessions(socket_path: &Path) -> bool {
+	tmux_cmd()
+		.arg("-S")
+		.arg(socket_path)
+		.arg("has-session")
+		.output()
+		.map(|o| o.status.success())
+		.unwrap_or(true) // can't tell - assume it's live
+}
+
+/// True only for a Unix socket with no listener behind it
+#[cfg(unix)]
+fn is_stale_socket(path: &Path) -> bool {
+	use std::os::unix::fs::FileTypeExt;
+	use std::os::unix::net::UnixStream;
+	let Ok(meta) = fs::symlink_metadata(path) else {
+		return false;
+	};
+	if !meta.file_type().is_socket() {
+		return false;
+	}
+	match UnixStream::connect(path) {
+		Ok(_) => false, // a server accepted the connection
+		Err(e) => e.kind() == std::io::ErrorKind::ConnectionRefused,
+	}
+}
+
+#[cfg(not(unix))]
+fn is_stale_socket(_path: &Path) -> bool {
+	false
+}
+
+#[tracing::instrument(level = "debug", err)]
 pub fn list_sessions() -> Result<Vec<String>> {
+	if !is_available() {
+		return Ok(pty::list_sessions());
+	}
 	// Ensure server is running (handles stale sockets)
 	ensure_server()?;
 
@@ -144,6 +203,7 @@ pub fn list_sessions() -> Result<Vec<String>> {
 	};
 
 	if !output.status.success() {
+		tracing::debug!(stderr = %String::from_utf8_lossy(&output.stderr).trim(), "tmux list-sessions failed");
 		return Ok(vec![]);
 	}
 
@@ -167,7 +227,14 @@ pub fn list_sessions() -> Result<Vec<String>> {
 	Ok(sessions.into_iter().map(|(name, _)| name).collect())
 }
 
+#[tracing::instrument(level = "debug", err)]
 pub fn ensure_pipe(session: &str, log_path: &Path) -> Result<()> {
+	if !is_available() {
+		if let Some(parent) = log_path.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		return pty::pipe_to(session, log_path);
+	}
 	if let Some(parent) = log_path.parent() {
 		fs::create_dir_all(parent)?;
 	}
@@ -209,7 +276,6 @@ pub fn ensure_pipe(session: &str, log_path: &Path) -> Result<()> {
 	))
 }
 
-#[allow(dead_code)]
 pub fn capture_tail(session: &str, lines: usize) -> Result<Vec<String>> {
 	capture_tail_inner(session, lines, false)
 }
@@ -219,7 +285,11 @@ pub fn capture_tail_ansi(session: &str, lines: usize) -> Result<Vec<String>> {
 	capture_tail_inner(session, lines, true)
 }
 
+#[tracing::instrument(level = "debug", err)]
 fn capture_tail_inner(session: &str, lines: usize, with_ansi: bool) -> Result<Vec<String>> {
+	if !is_available() {
+		return pty::capture_tail(session, lines);
+	}
 	let mut cmd = tmux_cmd();
 	cmd.arg("capture-pane").arg("-p").arg("-J");
 	if with_ansi {
@@ -243,11 +313,15 @@ fn capture_tail_inner(session: &str, lines: usize, with_ansi: bool) -> Result<Ve
 	Ok(stdout.lines().map(|s| s.to_string()).collect())
 }
 
+#[tracing::instrument(level = "debug", err)]
 pub fn pane_last_used(session: &str) -> Result<Option<SystemTime>> {
+	if !is_available() {
+		return Ok(None);
+	}
 	let output = tmux_cmd()
 		.arg("list-panes")
 		.arg("-t")
-		.arg(session)
+		.arg(format!("{session}:0"))
 		.arg("-F")
 		.arg("#{pane_last_used}")
 		.output()?;
@@ -274,6 +348,7 @@ pub fn start_session_with_mise(session: &str, dir: &Path, command: &str) -> Resu
 	start_session_with_options(session, dir, command, true)
 }
 
+#[tracing::instrument(level = "debug", skip(command), err)]
 fn start_session_with_options(
 	session: &str,
 	dir: &Path,
@@ -287,9 +362,6 @@ fn start_session_with_options(
 		));
 	}
 
-	// Ensure server is running (handles stale sockets)
-	ensure_server()?;
-
 	// Build the shell script to run via zsh -c
 	// This sets up PATH for tools like claude (installed in ~/.claude/local)
 	// The command is passed as a separate arg to avoid shell quoting issues
@@ -306,6 +378,13 @@ fn start_session_with_options(
 		)
 	};
 
+	if !is_available() {
+		return pty::spawn(session, dir, "zsh", &["-c", &final_command]);
+	}
+
+	// Ensure server is running (handles stale sockets)
+	ensure_server()?;
+
 	let tmux_bin = find_tmux();
 	let mut cmd = Command::new(tmux_bin);
 
@@ -317,8 +396,16 @@ fn start_session_with_options(
 		}
 	}
 
+	// tmux reads an argument ending in ; as the end of a command, even after --
+	let final_command = match final_command.strip_suffix(';') {
+		Some(rest) => format!("{rest}\\;"),
+		None => final_command,
+	};
+
 	// Use -- to separate tmux options from the shell command
-	// Pass shell and args separately to avoid quote escaping issues
+	// Pass shell and args separately to avoid quote escaping issues.
+	// remain-on-exit is set in the same invocation so an agent that exits
+	// right away still leaves a dead pane to read its status from.
 	let status = cmd
 		.arg("new-session")
 		.arg("-d")
@@ -330,6 +417,12 @@ fn start_session_with_options(
 		.arg("zsh")
 		.arg("-c")
 		.arg(&final_command)
+		.arg(";")
+		.arg("set-option")
+		.arg("-t")
+		.arg(session)
+		.arg("remain-on-exit")
+		.arg("on")
 		.status()
 		.with_context(|| format!("failed to start tmux session {} (using {})", session, tmux_bin))?;
 
@@ -344,25 +437,29 @@ fn start_session_with_options(
 	Ok(())
 }
 
+#[tracing::instrument(level = "debug", skip(text), err)]
 pub fn send_keys(session: &str, text: &str) -> Result<()> {
-	// Send the text literally first
-	let status = tmux_cmd()
-		.arg("send-keys")
-		.arg("-l") // literal mode - don't interpret special chars in text
-		.arg("-t")
-		.arg(session)
-		.arg(text)
-		.status()
-		.with_context(|| format!("failed to send keys to {}", session))?;
-	if !status.success() {
-		return Err(anyhow::anyhow!("tmux send-keys failed for {}", session));
+	if !is_available() {
+		if text.contains('\n') {
+			// Bracketed paste so the agent takes it as one message, not one per line
+			pty::write(session, format!("\x1b[200~{text}\x1b[201~").as_bytes())?;
+		} else {
+			pty::write(session, text.as_bytes())?;
+		}
+		return pty::write(session, pty::key_bytes("Enter"));
+	}
+	if text.contains('\n') {
+		paste_text(session, text)?;
+	} else {
+		// Send the text literally first
+		send_literal(session, text)?;
 	}
 
 	// Then send Enter separately
 	let status = tmux_cmd()
 		.arg("send-keys")
 		.arg("-t")
-		.arg(session)
+		.arg(format!("{session}:0.0"))
 		.arg("Enter")
 		.status()
 		.with_context(|| format!("failed to send Enter to {}", session))?;
@@ -375,12 +472,57 @@ pub fn send_keys(session: &str, text: &str) -> Result<()> {
 	Ok(())
 }
 
+fn send_literal(session: &str, text: &str) -> Result<()> {
+	let status = tmux_cmd()
+		.arg("send-keys")
+		.arg("-l") // literal mode - don't interpret special chars in text
+		.arg("-t")
+		.arg(format!("{session}:0.0"))
+		.arg(text)
+		.status()
+		.with_context(|| format!("failed to send keys to {}", session))?;
+	if !status.success() {
+		return Err(anyhow::anyhow!("tmux send-keys failed for {}", session));
+	}
+	Ok(())
+}
+
+/// Multi-line text goes through a tmux buffer pasted with -p (bracketed paste when
+/// the agent asked for it), so newlines don't submit each line separately
+fn paste_text(session: &str, text: &str) -> Result<()> {
+	use std::io::Write;
+	let buffer = format!("swarm-input-{}", std::process::id());
+	let mut child = tmux_cmd()
+		.args(["load-buffer", "-b", &buffer, "-"])
+		.stdin(std::process::Stdio::piped())
+		.spawn()
+		.with_context(|| format!("failed to load paste buffer for {}", session))?;
+	if let Some(mut stdin) = child.stdin.take() {
+		stdin.write_all(text.as_bytes())?;
+	}
+	if !child.wait()?.success() {
+		return Err(anyhow::anyhow!("tmux load-buffer failed for {}", session));
+	}
+	let status = tmux_cmd()
+		.args(["paste-buffer", "-p", "-d", "-b", &buffer, "-t", &format!("{session}:0.0")])
+		.status()
+		.with_context(|| format!("failed to paste into {}", session))?;
+	if !status.success() {
+		return Err(anyhow::anyhow!("tmux paste-buffer failed for {}", session));
+	}
+	Ok(())
+}
+
 /// Send a special key like "BTab" (Shift+Tab), "C-c" (Ctrl+C), etc.
+#[tracing::instrument(level = "debug", err)]
 pub fn send_special_key(session: &str, key: &str) -> Result<()> {
+	if !is_available() {
+		return pty::write(session, pty::key_bytes(key));
+	}
 	let status = tmux_cmd()
 		.arg("send-keys")
 		.arg("-t")
-		.arg(session)
+		.arg(format!("{session}:0.0"))
 		.arg(key)
 		.status()
 		.with_context(|| format!("failed to send {} to {}", key, session))?;
@@ -394,7 +536,250 @@ pub fn send_special_key(session: &str, key: &str) -> Result<()> {
 	Ok(())
 }
 
+/// When the session was created (None for PTY-backed sessions)
+pub fn session_created(session: &str) -> Option<std::time::SystemTime> {
+	if !is_available() {
+		return None;
+	}
+	let output = tmux_cmd()
+		.args(["display-message", "-p", "-t", session, "#{session_created}"])
+		.output()
+		.ok()
+		.filter(|o| o.status.success())?;
+	let secs: u64 = String::from_utf8_lossy(&output.stdout).trim().parse().ok()?;
+	Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs))
+}
+
+/// Exit status of the agent process in the session's first pane, from tmux's
+/// dead-pane flag. Returns None while the pane is alive; a shell in the pane
+/// isn't a sign of exit, since agents start under `zsh -c` before the exec.
+#[tracing::instrument(level = "debug", err)]
+pub fn pane_exit_status(session: &str) -> Result<Option<i32>> {
+	if !is_available() {
+		return Ok(pty::exit_status(session));
+	}
+	let output = tmux_cmd()
+		.arg("display-message")
+		.arg("-p")
+		.arg("-t")
+		.arg(format!("{session}:0.0"))
+		.arg("#{pane_dead}|#{pane_dead_status}")
+		.output()?;
+	if !output.status.success() {
+		return Ok(None);
+	}
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	match stdout.trim().split_once('|') {
+		Some(("1", code)) => Ok(Some(code.parse::<i32>().unwrap_or(1))),
+		_ => Ok(None),
+	}
+}
+
+/// Process id of the session's first pane (the shell or agent it started)
+pub fn pane_pid(session: &str) -> Option<u32> {
+	if !is_available() {
+		return pty::pid(session);
+	}
+	let output = tmux_cmd()
+		.args(["display-message", "-p", "-t", &format!("{session}:0.0"), "#{pane_pid}"])
+		.output()
+		.ok()
+		.filter(|o| o.status.success())?;
+	String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// Restart the original agent command in a dead pane
+#[tracing::instrument(level = "debug", err)]
+pub fn respawn_pane(session: &str) -> Result<()> {
+	if !is_available() {
+		return Err(anyhow::anyhow!("respawn needs tmux (brew install tmux)"));
+	}
+	let status = tmux_cmd()
+		.arg("respawn-pane")
+		.arg("-k")
+		.arg("-t")
+		.arg(format!("{session}:0.0"))
+		.status()
+		.with_context(|| format!("failed to respawn {}", session))?;
+	if !status.success() {
+		return Err(anyhow::anyhow!(
+			"tmux respawn-pane failed for {} (status {})",
+			session,
+			status
+		));
+	}
+	Ok(())
+}
+
+/// Ring the bell in the dashboard's own pane so tmux sets its window's bell alert
+/// flag (shown in status bars / terminal tabs until the window is visited). Does
+/// nothing when swarm isn't running inside tmux.
+pub fn ring_bell() -> Result<()> {
+	let Some(pane) = std::env::var_os("TMUX_PANE") else {
+		return Ok(());
+	};
+	if !is_available() {
+		return Ok(());
+	}
+	let output = tmux_cmd()
+		.arg("display-message")
+		.arg("-p")
+		.arg("-t")
+		.arg(&pane)
+		.arg("#{pane_tty}")
+		.output()
+		.context("failed to query pane tty")?;
+	let tty = String::from_utf8_lossy(&output.stdout).trim().to_string();
+	if !output.status.success() || tty.is_empty() {
+		return Err(anyhow::anyhow!("no pane tty for {}", pane.to_string_lossy()));
+	}
+	let _ = tmux_cmd()
+		.arg("set-option")
+		.arg("-w")
+		.arg("-t")
+		.arg(&pane)
+		.arg("monitor-bell")
+		.arg("on")
+		.status();
+	std::fs::OpenOptions::new()
+		.write(true)
+		.open(&tty)
+		.and_then(|mut f| std::io::Write::write_all(&mut f, b"\x07"))
+		.with_context(|| format!("failed to ring bell on {}", tty))?;
+	Ok(())
+}
+
+#[tracing::instrument(level = "debug", err)]
+pub fn rename_session(old: &str, new: &str) -> Result<()> {
+	if !is_available() {
+		return pty::rename(old, new);
+	}
+	let status = tmux_cmd()
+		.arg("rename-session")
+		.arg("-t")
+		.arg(old)
+		.arg(new)
+		.status()
+		.with_context(|| format!("failed to rename session {}", old))?;
+	if !status.success() {
+		return Err(anyhow::anyhow!(
+			"tmux rename-session failed for {} (status {})",
+			old,
+			status
+		));
+	}
+	Ok(())
+}
+
+/// The session this process runs in, when it runs inside tmux
+pub fn current_session() -> Option<String> {
+	std::env::var_os("TMUX")?;
+	let output = tmux_cmd()
+		.args(["display-message", "-p", "#{session_name}"])
+		.output()
+		.ok()
+		.filter(|o| o.status.success())?;
+	let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+	(!name.is_empty()).then_some(name)
+}
+
+/// Whether a session by this exact name exists (swarm's or not)
+pub fn has_session(session: &str) -> bool {
+	tmux_cmd()
+		.args(["has-session", "-t", &format!("={session}")])
+		.stderr(std::process::Stdio::null())
+		.status()
+		.is_ok_and(|s| s.success())
+}
+
+/// Move a client (#{client_name}; None = the most recent one) to another session
+#[tracing::instrument(level = "debug", err)]
+pub fn switch_client(client: Option<&str>, session: &str) -> Result<()> {
+	let mut cmd = tmux_cmd();
+	cmd.arg("switch-client");
+	if let Some(client) = client {
+		cmd.arg("-c").arg(client);
+	}
+	let status = cmd
+		.arg("-t")
+		.arg(session)
+		.status()
+		.with_context(|| format!("failed to switch to {}", session))?;
+	if !status.success() {
+		return Err(anyhow::anyhow!("tmux switch-client failed for {}", session));
+	}
+	Ok(())
+}
+
+/// Detach a client (None = the most recent one)
+#[tracing::instrument(level = "debug", err)]
+pub fn detach_client(client: Option<&str>) -> Result<()> {
+	let mut cmd = tmux_cmd();
+	cmd.arg("detach-client");
+	if let Some(client) = client {
+		cmd.arg("-t").arg(client);
+	}
+	let status = cmd.status().context("failed to detach")?;
+	if !status.success() {
+		return Err(anyhow::anyhow!("tmux detach-client failed"));
+	}
+	Ok(())
+}
+
+/// Show a READ-ONLY badge in the session's status bar for read-only clients
+/// (`attach -r`); normal attaches are unaffected. Returns the session's own
+/// status-left (None when it uses the global one) for `restore_status_left`.
+pub fn show_readonly_badge(session: &str) -> Result<Option<String>> {
+	let previous = tmux_cmd()
+		.arg("show-options")
+		.arg("-qv")
+		.arg("-t")
+		.arg(session)
+		.arg("status-left")
+		.output()
+		.ok()
+		.filter(|o| o.status.success())
+		.map(|o| String::from_utf8_lossy(&o.stdout).trim_end_matches('\n').to_string())
+		.filter(|v| !v.is_empty());
+	let status = tmux_cmd()
+		.arg("set-option")
+		.arg("-t")
+		.arg(session)
+		.arg("status-left")
+		.arg("#{?client_readonly,#[fg=black#,bg=yellow#,bold] READ-ONLY - Alt+d to detach #[default] ,}[#{session_name}] ")
+		.status()
+		.with_context(|| format!("failed to set status bar for {}", session))?;
+	if !status.success() {
+		return Err(anyhow::anyhow!("tmux set-option failed for {}", session));
+	}
+	Ok(previous)
+}
+
+/// Put back the status-left `show_readonly_badge` replaced, or unset it so the
+/// global one applies again
+pub fn restore_status_left(session: &str, previous: Option<&str>) -> Result<()> {
+	let mut cmd = tmux_cmd();
+	cmd.arg("set-option");
+	match previous {
+		Some(value) => cmd.arg("-t").arg(session).arg("status-left").arg(value),
+		None => cmd.arg("-u").arg("-t").arg(session).arg("status-left"),
+	};
+	let status = cmd.status().with_context(|| format!("failed to restore status bar for {}", session))?;
+	if !status.success() {
+		return Err(anyhow::anyhow!("tmux set-option failed for {}", session));
+	}
+	Ok(())
+}
+
+#[tracing::instrument(level = "debug", err)]
 pub fn kill_session(session: &str) -> Result<()> {
+	// Never touch the user's own (non-swarm) sessions
+	if !session.starts_with(SWARM_PREFIX) {
+		return Err(anyhow::anyhow!("refusing to kill non-swarm session {}", session));
+	}
+	if !is_available() {
+		return pty::kill(session);
+	}
 	let status = tmux_cmd()
 		.arg("kill-session")
 		.arg("-t")
@@ -411,12 +796,74 @@ pub fn kill_session(session: &str) -> Result<()> {
 	Ok(())
 }
 
+/// Run `command` in a background window of `session` (the agent keeps window 0,
+/// which everything else here targets explicitly), starting in `dir`. Output
+/// goes to the window and to `output`; the exit code is written to `status`
+/// once it's done. A previous run's window is replaced.
+#[tracing::instrument(level = "debug", skip(command), err)]
+pub fn run_in_window(session: &str, dir: &Path, command: &str, output: &Path, status: &Path) -> Result<()> {
+	if !is_available() {
+		return Err(anyhow::anyhow!("running commands needs tmux (brew install tmux)"));
+	}
+	let _ = tmux_cmd()
+		.arg("kill-window")
+		.arg("-t")
+		.arg(format!("{session}:{RUN_WINDOW}"))
+		.output();
+	// The command and paths go in as $1..$3 so nothing needs quoting
+	let script = r#"{ ${SHELL:-sh} -c "$1"; echo $? > "$3"; } 2>&1 | tee "$2""#;
+	let out = tmux_cmd()
+		.arg("new-window")
+		.arg("-d")
+		.arg("-P")
+		.arg("-F")
+		.arg("#{window_id}")
+		.arg("-t")
+		.arg(format!("{session}:"))
+		.arg("-n")
+		.arg(RUN_WINDOW)
+		.arg("-c")
+		.arg(dir)
+		.arg("--")
+		.arg("sh")
+		.arg("-c")
+		.arg(script)
+		.arg(RUN_WINDOW)
+		.arg(command)
+		.arg(output)
+		.arg(status)
+		.output()
+		.with_context(|| format!("failed to open a window in {}", session))?;
+	if !out.status.success() {
+		return Err(anyhow::anyhow!(
+			"tmux new-window failed for {}: {}",
+			session,
+			String::from_utf8_lossy(&out.stderr).trim()
+		));
+	}
+	// Sessions keep dead panes around for respawn; this window should just close
+	let window = String::from_utf8_lossy(&out.stdout).trim().to_string();
+	let _ = tmux_cmd()
+		.arg("set-option")
+		.arg("-w")
+		.arg("-t")
+		.arg(&window)
+		.arg("remain-on-exit")
+		.arg("off")
+		.status();
+	Ok(())
+}
+
+#[tracing::instrument(level = "debug", err)]
 pub fn session_path(session: &str) -> Result<Option<String>> {
+	if !is_available() {
+		return Ok(pty::session_dir(session));
+	}
 	let output = tmux_cmd()
 		.arg("display-message")
 		.arg("-p")
 		.arg("-t")
-		.arg(session)
+		.arg(format!("{session}:0.0"))
 		.arg("#{pane_current_path}")
 		.output()?;
 	if !output.status.success() {
@@ -429,3 +876,57 @@ pub fn session_path(session: &str) -> Result<Option<String>> {
 		Ok(Some(stdout))
 	}
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+	use super::*;
+	use std::os::unix::net::UnixListener;
+
+	fn temp_dir(name: &str) -> std::path::PathBuf {
+		let dir = std::env::temp_dir().join(format!("swarm-tmux-test-{}-{}", name, std::process::id()));
+		let _ = fs::remove_dir_all(&dir);
+		fs::create_dir_all(&dir).unwrap();
+		dir
+	}
+
+	#[test]
+	fn live_socket_is_not_stale() {
+		let dir = temp_dir("live");
+		let path = dir.join("default");
+		let _listener = UnixListener::bind(&path).unwrap();
+		assert!(!is_stale_socket(&path));
+		let _ = fs::remove_dir_all(&dir);
+	}
+
+	#[test]
+	fn socket_without_listener_is_stale() {
+		let dir = temp_dir("dead");
+		let path = dir.join("default");
+		drop(UnixListener::bind(&path).unwrap());
+		assert!(path.exists());
+		assert!(is_stale_socket(&path));
+		let _ = fs::remove_dir_all(&dir);
+	}
+
+	#[test]
+	fn regular_file_is_never_stale() {
+		let dir = temp_dir("file");
+		let path = dir.join("default");
+		fs::write(&path, "not a socket").unwrap();
+		assert!(!is_stale_socket(&path));
+		let _ = fs::remove_dir_all(&dir);
+	}
+
+	#[test]
+	fn kill_refuses_non_swarm_sessions() {
+		assert!(kill_session("work").is_err());
+		assert!(kill_session("my-swarm-notes").is_err());
+	}
+
+	#[test]
+	fn missing_socket_is_not_stale() {
+		let dir = temp_dir("missing");
+		assert!(!is_stale_socket(&dir.join("default")));
+		let _ = fs::remove_dir_all(&dir);
+	}
+}